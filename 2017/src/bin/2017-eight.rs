@@ -0,0 +1,6 @@
+extern crate advent_of_code_2017;
+extern crate aoc_common;
+
+fn main() {
+    aoc_common::runner::main_day(advent_of_code_2017::year(), 8);
+}
@@ -0,0 +1,157 @@
+/*
+--- Day 8: I Heard You Like Registers ---
+
+You receive a signal directly from the CPU. Because of your recent assistance with jump instructions, it would like you to compute the result of a series of unusual register instructions.
+
+Each instruction consists of several parts: the register to modify, whether to increase or decrease that register's value, the amount by which to increase or decrease it, and a condition. If the condition fails, skip the instruction without modifying the register. The registers all start at 0. The instructions look like this:
+
+b inc 5 if a > 1
+a inc 1 if b < 5
+c dec -10 if a >= 1
+c inc -20 if c == 10
+
+These instructions would be processed as follows:
+
+    Because a starts at 0, it is not greater than 1, and so b is not modified.
+    a is increased by 1 (to 1) because b is less than 5 (it is 0).
+    c is decreased by -10 (to 10) because a is now greater than or equal to 1 (it is 1).
+    c is increased by -20 (to -10) because c is equal to 10.
+
+After this process, the largest value in any register is 1.
+
+You might also encounter <= (less than or equal to) or != (not equal to). However, the CPU doesn't have the bandwidth to tell you what all the registers are named, and leaves that to you to determine.
+
+What is the largest value in any register after completing the instructions in your puzzle input?
+*/
+
+use aoc_common::parse::ParseError;
+use aoc_common::vm::{self, Assembler, Flow, Program, State};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Gt,
+    Eq,
+    Ne,
+    Le,
+    Ge,
+}
+
+impl Comparison {
+    pub fn test(self, a: i64, b: i64) -> bool {
+        match self {
+            Comparison::Lt => a < b,
+            Comparison::Gt => a > b,
+            Comparison::Eq => a == b,
+            Comparison::Ne => a != b,
+            Comparison::Le => a <= b,
+            Comparison::Ge => a >= b,
+        }
+    }
+}
+
+/// `register += delta if cmp_reg <comparison> cmp_val`, with `dec` folded
+/// into a negative delta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub register: usize,
+    pub delta: i64,
+    pub cmp_reg: usize,
+    pub comparison: Comparison,
+    pub cmp_val: i64,
+}
+
+impl vm::Instruction for Instruction {
+    fn execute(self, state: &mut State) -> Flow {
+        if self.comparison.test(state.registers[self.cmp_reg], self.cmp_val) {
+            state.registers[self.register] += self.delta;
+        }
+
+        Flow::Next
+    }
+}
+
+/// Parses lines like `c dec -10 if a >= 1`, with registers named however
+/// the program likes.
+pub fn parse(input: &str) -> Result<Program<Instruction>, ParseError> {
+    Assembler::named(input).assemble(|line| {
+        let register = line.register()?;
+        let op = line.word("inc or dec")?;
+        let val = line.number::<i64>()?;
+        let delta = match op {
+            "inc" => val,
+            "dec" => -val,
+            _ => return Err(line.error(op, "inc or dec")),
+        };
+
+        line.keyword("if")?;
+
+        let cmp_reg = line.register()?;
+        let cmp_op = line.word("a comparison")?;
+        let comparison = match cmp_op {
+            "<" => Comparison::Lt,
+            ">" => Comparison::Gt,
+            "==" => Comparison::Eq,
+            "!=" => Comparison::Ne,
+            "<=" => Comparison::Le,
+            ">=" => Comparison::Ge,
+            _ => return Err(line.error(cmp_op, "a comparison")),
+        };
+        let cmp_val = line.number()?;
+
+        Ok(Instruction { register, delta, cmp_reg, comparison, cmp_val })
+    })
+}
+
+pub fn solve_a(program: &Program<Instruction>) -> i64 {
+    solve(program).0
+}
+
+fn solve(program: &Program<Instruction>) -> (i64, i64) {
+    let mut machine = program.machine();
+    let mut max_ever = 0;
+
+    // There are no jumps, so the instruction just run is the one before.
+    while machine.step().is_none() {
+        let changed = program[machine.pc() as usize - 1].register;
+        max_ever = max_ever.max(machine.state.registers[changed]);
+    }
+
+    (machine.state.registers.iter().cloned().max().unwrap_or(0), max_ever)
+}
+
+/*
+--- Part Two ---
+
+To be safe, the CPU also needs to know the highest value held in any register during this process so that it can decide how much memory to allocate to these operations. For example, in the above instructions, the highest value ever held was 10 (in register c after the third instruction was evaluated).
+*/
+
+pub fn solve_b(program: &Program<Instruction>) -> i64 {
+    solve(program).1
+}
+
+#[test]
+fn test_a() {
+    assert_eq!(solve_a(&parse("b inc 5 if a > 1
+a inc 1 if b < 5
+c dec -10 if a >= 1
+c inc -20 if c == 10").unwrap()), 1);
+}
+
+#[test]
+fn test_b() {
+    assert_eq!(solve_b(&parse("b inc 5 if a > 1
+a inc 1 if b < 5
+c dec -10 if a >= 1
+c inc -20 if c == 10").unwrap()), 10);
+}
+
+#[test]
+fn test_parse() {
+    let error = parse("b inc 5 if a > 1\nc dec -10 if a =< 1").unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (2, 16, "=<"));
+    assert_eq!(error.expected, "a comparison");
+
+    let error = parse("b inc 5 if a >").unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (1, 15, ""));
+}
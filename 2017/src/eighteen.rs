@@ -42,67 +42,75 @@ At the time the recover operation is executed, the frequency of the last sound p
 What is the value of the recovered frequency (the value of the most recently played sound) the first time a rcv instruction is executed with a non-zero value?
 */
 
-use std::io::Read;
-use std::fs::File;
-use std::collections::VecDeque;
-
-fn main() {
-    let mut input = File::open("inputs/eighteen.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
-
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+use aoc_common::Failure;
+use aoc_common::cancel::{Cancelled, Token};
+use aoc_common::parse::ParseError;
+use aoc_common::vm::{self, Assembler, Flow, Machine, Operand, Program, State, Stop};
+
+use self::Instruction::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Snd(Operand),
+    Set(usize, Operand),
+    Add(usize, Operand),
+    Mul(usize, Operand),
+    Mod(usize, Operand),
+    Rcv(usize),
+    Jgz(Operand, Operand),
 }
 
-fn solve_a(input: &str) -> isize {
-    let mut playing = 0;
-    let mut program_counter = 0isize;
-    let mut registers = vec![0; 26];
-
-    let reg_key = |key: &str| { (key.as_bytes()[0] - b'a') as usize };
-
-    macro_rules! val {
-        ($key:ident) => {{
-            if !$key.chars().all(|c| c.is_alphabetic()) {
-                $key.parse::<isize>().unwrap()
-            } else {
-                registers[reg_key($key)]
-            }
-        }}
-    }
+pub fn parse(input: &str) -> Result<Program<Instruction>, ParseError> {
+    Assembler::letters(input, 26).assemble(|line| {
+        let op = line.word("an instruction")?;
+
+        Ok(match op {
+            "snd" => Snd(line.operand()?),
+            "set" => Set(line.register()?, line.operand()?),
+            "add" => Add(line.register()?, line.operand()?),
+            "mul" => Mul(line.register()?, line.operand()?),
+            "mod" => Mod(line.register()?, line.operand()?),
+            "rcv" => Rcv(line.register()?),
+            "jgz" => Jgz(line.operand()?, line.operand()?),
+            _ => return Err(line.error(op, "snd, set, add, mul, mod, rcv or jgz")),
+        })
+    })
+}
 
-    let program = input.lines().map(|s| {
-        let mut split = s.split(" ");
-        (split.next().unwrap(), split.next().unwrap(), split.next())
-    }).collect::<Vec<_>>();
+/// The instructions as first guessed, with `snd` and `rcv` playing and
+/// recovering sounds.
+#[derive(Debug, Clone, Copy)]
+struct Sound(Instruction);
 
-    loop {
-        let (op, x, y) = program[program_counter as usize].clone();
-        let y = y.map(|s| val!(s));
-        match (op, x, y) {
+impl vm::Instruction for Sound {
+    fn execute(self, state: &mut State) -> Flow {
+        match self.0 {
             // snd X plays a sound with a frequency equal to the value of X.
-            ("snd", freq, _) => playing = val!(freq),
-            // set X Y sets register X to the value of Y.
-            ("set", x, y) => registers[reg_key(x)] = y.unwrap(),
-            // add X Y increases register X by the value of Y.
-            ("add", x, y) => registers[reg_key(x)] += y.unwrap(),
-            // mul X Y sets register X to the result of multiplying the value contained in register X by the value of Y.
-            ("mul", x, y) => registers[reg_key(x)] *= y.unwrap(),
-            // mod X Y sets register X to the remainder of dividing the value contained in register X by the value of Y (that is, it sets X to the result of X modulo Y).
-            ("mod", x, y) => registers[reg_key(x)] %= y.unwrap(),
+            Snd(freq) => {
+                let freq = state.get(freq);
+                state.output.push_back(freq);
+            },
             // rcv X recovers the frequency of the last sound played, but only when the value of X is not zero. (If it is zero, the command does nothing.)
-            ("rcv", x, _) => if val!(x) != 0 { return playing },
-            // jgz X Y jumps with an offset of the value of Y, but only if the value of X is greater than zero. (An offset of 2 skips the next instruction, an offset of -1 jumps to the previous instruction, and so on.)
-            ("jgz", x, y) => if val!(x) > 0 {
-                program_counter += y.unwrap();
-                continue
+            Rcv(x) => if state.registers[x] != 0 {
+                return Flow::Halt
             },
-            _ => unreachable!()
+            instruction => return instruction.execute(state),
         }
 
-        program_counter += 1;
+        Flow::Next
+    }
+}
+
+/// The first sound recovered, which there isn't if the program exits before
+/// it recovers one or recovers before it plays any.
+pub fn solve_a(program: &Program<Instruction>, token: &Token) -> Result<i64, Failure> {
+    let sounds = program.iter().map(|&instruction| Sound(instruction)).collect::<Vec<_>>();
+    let mut machine = Machine::new(&sounds, program.registers().len());
+
+    match machine.run(token)? {
+        Stop::Halted => machine.state.output.back().cloned()
+            .ok_or_else(|| Failure::no_answer("recovered a sound before any played")),
+        _ => Err(Failure::no_answer("the program exits without recovering a sound")),
     }
 }
 
@@ -139,108 +147,63 @@ Once both of your programs have terminated (regardless of what caused them to do
 
 */
 
-#[derive(Clone, Default)]
-struct Machine<'a> {
-    program_counter: isize,
-    registers: [isize; 26],
-    program: Vec<(&'a str, &'a str, Option<&'a str>)>
-}
-
-impl<'a> Machine<'a> {
-    fn run(&mut self, input_buffer: &mut VecDeque<isize>, output_buffer: &mut VecDeque<isize>) -> isize {
-        let Machine { ref mut program_counter, ref mut registers, ref mut program } = *self;
-
-        let reg_key = |key: &str| { (key.as_bytes()[0] - b'a') as usize };
-        let mut sent = 0;
-
-        macro_rules! val {
-            ($key:ident) => {{
-                if !$key.chars().all(|c| c.is_alphabetic()) {
-                    $key.parse::<isize>().unwrap()
-                } else {
-                    registers[reg_key($key)]
-                }
-            }}
+impl vm::Instruction for Instruction {
+    fn execute(self, state: &mut State) -> Flow {
+        match self {
+            // snd X sends the value of X to the other program. These values wait in a queue until that program is ready to receive them. Each program has its own message queue, so a program can never receive a message it sent.
+            Snd(x) => {
+                let value = state.get(x);
+                state.output.push_back(value);
+            },
+            // set X Y sets register X to the value of Y.
+            Set(x, y) => state.registers[x] = state.get(y),
+            // add X Y increases register X by the value of Y.
+            Add(x, y) => state.registers[x] += state.get(y),
+            // mul X Y sets register X to the result of multiplying the value contained in register X by the value of Y.
+            Mul(x, y) => state.registers[x] *= state.get(y),
+            // mod X Y sets register X to the remainder of dividing the value contained in register X by the value of Y (that is, it sets X to the result of X modulo Y).
+            Mod(x, y) => state.registers[x] %= state.get(y),
+            // rcv X receives the next value and stores it in register X. If no values are in the queue, the program waits for a value to be sent to it. Programs do not continue to the next instruction until they have received a value. Values are received in the order they are sent.
+            Rcv(x) => match state.input.pop_front() {
+                Some(value) => state.registers[x] = value,
+                None => return Flow::Wait,
+            },
+            // jgz X Y jumps with an offset of the value of Y, but only if the value of X is greater than zero. (An offset of 2 skips the next instruction, an offset of -1 jumps to the previous instruction, and so on.)
+            Jgz(x, y) => if state.get(x) > 0 {
+                return Flow::Jump(state.get(y))
+            },
         }
 
-        loop {
-            let (op, x, y) = program[*program_counter as usize].clone();
-            let y = y.map(|s| val!(s));
-            match (op, x, y) {
-                // snd X sends the value of X to the other program. These values wait in a queue until that program is ready to receive them. Each program has its own message queue, so a program can never receive a message it sent.
-                ("snd", freq, _) => {
-                    output_buffer.push_back(val!(freq));
-                    sent += 1
-                },
-                // set X Y sets register X to the value of Y.
-                ("set", x, y) => registers[reg_key(x)] = y.unwrap(),
-                // add X Y increases register X by the value of Y.
-                ("add", x, y) => registers[reg_key(x)] += y.unwrap(),
-                // mul X Y sets register X to the result of multiplying the value contained in register X by the value of Y.
-                ("mul", x, y) => registers[reg_key(x)] *= y.unwrap(),
-                // mod X Y sets register X to the remainder of dividing the value contained in register X by the value of Y (that is, it sets X to the result of X modulo Y).
-                ("mod", x, y) => registers[reg_key(x)] %= y.unwrap(),
-                // rcv X receives the next value and stores it in register X. If no values are in the queue, the program waits for a value to be sent to it. Programs do not continue to the next instruction until they have received a value. Values are received in the order they are sent.
-                ("rcv", x, _) => {
-                    if let Some(value) = input_buffer.pop_front() {
-                        registers[reg_key(x)] = value;
-                    } else {
-                        return sent
-                    }
-                },
-                // jgz X Y jumps with an offset of the value of Y, but only if the value of X is greater than zero. (An offset of 2 skips the next instruction, an offset of -1 jumps to the previous instruction, and so on.)
-                ("jgz", x, y) => if val!(x) > 0 {
-                    *program_counter += y.unwrap();
-                    continue
-                },
-                _ => unreachable!()
-            }
-
-            *program_counter += 1;
-        }
+        Flow::Next
     }
 }
 
-fn solve_b(input: &str) -> isize {
-    let program = input.lines().map(|s| {
-        let mut split = s.split(" ");
-        (split.next().unwrap(), split.next().unwrap(), split.next())
-    }).collect::<Vec<_>>();
+pub fn solve_b(program: &Program<Instruction>, token: &Token) -> Result<usize, Cancelled> {
+    let mut zero = program.machine();
+    let mut one = program.machine();
+    one.state.registers[program.register("p").expect("a register p")] = 1;
 
-    let mut current = Machine {
-        program,
-        ..Default::default()
-    };
-
-    let mut next = current.clone();
-    next.registers[(b'p' - b'a') as usize] = 1;
-
-    let mut input = VecDeque::new();
-    let mut output = VecDeque::new();
-
-    let mut program_1_running = false;
     let mut sends = 0;
 
     loop {
-        let sent = current.run(&mut input, &mut output);
-        if program_1_running {
-            sends += sent
-        }
+        let stop = zero.run(token)?;
+        one.state.input.extend(zero.state.output.drain(..));
 
-        program_1_running = !program_1_running;
+        one.run(token)?;
+        sends += one.state.output.len();
+        zero.state.input.extend(one.state.output.drain(..));
 
-        if output.is_empty() && input.is_empty() {
-            return sends
+        // Program 0 has exited or has nothing new to receive, so it can't
+        // send program 1 anything more and both are stuck for good.
+        if stop != Stop::Waiting || zero.state.input.is_empty() {
+            return Ok(sends)
         }
-
-        std::mem::swap(&mut input, &mut output);
-        std::mem::swap(&mut current, &mut next);
     }
 }
 
 #[test]
 fn test_a() {
-    assert_eq!(solve_a("set a 1
+    assert_eq!(solve_a(&parse("set a 1
 add a 2
 mul a a
 mod a 5
@@ -249,16 +212,28 @@ set a 0
 rcv a
 jgz a -1
 set a 1
-jgz a -2"), 4);
+jgz a -2").unwrap(), &Token::none()), Ok(4));
+
+    assert_eq!(solve_a(&parse("snd 1\nset a 0\nrcv a").unwrap(), &Token::none()),
+               Err(Failure::no_answer("the program exits without recovering a sound")));
+    assert_eq!(solve_a(&parse("set a 1\nrcv a\nsnd 1").unwrap(), &Token::none()),
+               Err(Failure::no_answer("recovered a sound before any played")));
 }
 
 #[test]
 fn test_b() {
-    assert_eq!(solve_b("snd 1
+    assert_eq!(solve_b(&parse("snd 1
 snd 2
 snd p
 rcv a
 rcv b
 rcv c
-rcv d"), 3);
+rcv d").unwrap(), &Token::none()), Ok(3));
+
+    // Program 0 exits at once, leaving program 1 sending into the void.
+    assert_eq!(solve_b(&parse("jgz p 2
+jgz 1 10
+snd 1
+rcv a
+jgz 1 -2").unwrap(), &Token::none()), Ok(1));
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+/*
+--- Day 11: Hex Ed ---
+
+Crossing the bridge, you've barely reached the other side of the stream when a program comes up to you, clearly in distress. "It's my child process," she says, "he's gotten lost in an infinite grid!"
+
+Fortunately for her, you have plenty of experience with infinite grids.
+
+Unfortunately for you, it's a hex grid.
+
+The hexagons ("hexes") in this grid are aligned such that adjacent hexes can be found to the north, northeast, southeast, south, southwest, and northwest:
+
+  \ n  /
+nw +--+ ne
+  /    \
+-+      +-
+  \    /
+sw +--+ se
+  / s  \
+
+You have the path the child process took. Starting where he started, you need to determine the fewest number of steps required to reach him. (A "step" means to move from the hex you are in to any adjacent hex.)
+
+For example:
+
+    ne,ne,ne is 3 steps away.
+    ne,ne,sw,sw is 0 steps away (back where you started).
+    ne,ne,s,s is 2 steps away (se,se).
+    se,sw,se,sw,sw is 3 steps away (s,s,sw).
+*/
+
+use aoc_common::hex::{Hex, Orientation};
+use aoc_common::parse::{ParseError, Parser};
+
+/// Each step of the path, on a grid of flat-topped hexagons.
+pub fn parse(input: &str) -> Result<Vec<Hex>, ParseError> {
+    let p = Parser::new(input);
+
+    Orientation::Flat.parse_steps(input.trim()).map_err(|step| p.error(step, "n, ne, se, s, sw or nw"))
+}
+
+pub fn solve_a(path: &[Hex]) -> i64 {
+    Hex::ORIGIN.path(path).last().map_or(0, |end| end.length())
+}
+
+/*
+--- Part Two ---
+
+How many steps away is the furthest he ever got from his starting position?
+*/
+
+pub fn solve_b(path: &[Hex]) -> i64 {
+    Hex::ORIGIN.path(path).iter().map(|hex| hex.length()).max().unwrap_or(0)
+}
+
+#[test]
+fn test_a() {
+    assert_eq!(solve_a(&parse("ne,ne,ne").unwrap()), 3);
+    assert_eq!(solve_a(&parse("ne,ne,sw,sw").unwrap()), 0);
+    assert_eq!(solve_a(&parse("ne,ne,s,s").unwrap()), 2);
+    assert_eq!(solve_a(&parse("se,sw,se,sw,sw").unwrap()), 3);
+    assert_eq!(solve_a(&parse("se,s").unwrap()), 2);
+}
+
+#[test]
+fn test_b() {
+    assert_eq!(solve_b(&parse("ne,ne,ne").unwrap()), 3);
+    assert_eq!(solve_b(&parse("ne,ne,sw,sw").unwrap()), 2);
+    assert_eq!(solve_b(&parse("ne,ne,s,s").unwrap()), 2);
+    assert_eq!(solve_b(&parse("se,sw,se,sw,sw").unwrap()), 3);
+}
\ No newline at end of file
@@ -43,12 +43,23 @@ After 40 million pairs, what is the judge's final count?
 
 */
 
-fn main() {
-    println!("a: {}", solve_a(634, 301));
-    println!("b: {}", solve_b(634, 301));
+use aoc_common::parse::{ParseError, Parser};
+
+/// Reads the two starting values, e.g. `Generator A starts with 65`.
+pub fn parse(input: &str) -> Result<(u64, u64), ParseError> {
+    let p = Parser::new(input);
+    let mut lines = input.lines();
+
+    let line = p.token(&mut lines, input, "generator A")?;
+    let a = p.number(p.literal(line, "Generator A starts with ")?)?;
+    let line = p.token(&mut lines, input, "generator B")?;
+    let b = p.number(p.literal(line, "Generator B starts with ")?)?;
+    p.end(&mut lines.filter(|line| !line.is_empty()))?;
+
+    Ok((a, b))
 }
 
-fn solve_a(mut a: u64, mut b: u64) -> u64 {
+pub fn solve_a(mut a: u64, mut b: u64) -> u64 {
     let mut matching = 0;
 
     for _ in 0..40_000_000u64 {
@@ -116,17 +127,17 @@ This change makes the generators much slower, and the judge is getting impatient
 After 5 million pairs, but using this new generator logic, what is the judge's final count?
 */
 
-fn solve_b(mut a: u64, mut b: u64) -> u64 {
+pub fn solve_b(mut a: u64, mut b: u64) -> u64 {
     let mut matching = 0;
 
     for _ in 0..5_000_000u64 {
         loop {
             a = (a * 16807) % 2147483647;
-            if a % 4 == 0 { break }
+            if a.is_multiple_of(4) { break }
         }
         loop {
             b = (b * 48271) % 2147483647;
-            if b % 8 == 0 { break }
+            if b.is_multiple_of(8) { break }
         }
 
         if a & 0xFFFF == b & 0xFFFF {
@@ -136,6 +147,14 @@ fn solve_b(mut a: u64, mut b: u64) -> u64 {
     matching
 }
 
+#[test]
+fn test_parse() {
+    assert_eq!(parse("Generator A starts with 65\nGenerator B starts with 8921\n"), Ok((65, 8921)));
+
+    let error = parse("Generator A starts with 65\nGenerator B starts at 8921").unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (2, 20, "a"));
+}
+
 #[test]
 fn test_a() {
     assert_eq!(solve_a(65, 8921), 588);
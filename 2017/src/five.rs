@@ -28,21 +28,17 @@ In this example, the exit is reached in 5 steps.
 
 How many steps does it take to reach the exit?
 */
-use std::io::Read;
-use std::fs::File;
 
-fn main() {
-    let mut input = File::open("inputs/five.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::parse::{ParseError, Parser};
 
-    input.read_to_string(&mut s).unwrap();
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    let p = Parser::new(input);
 
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+    p.lines(|line| p.number(line))
 }
 
-fn solve_a(input: &str) -> usize {
-    let mut jumps = input.lines().map(|s| s.parse().unwrap()).collect::<Vec<i64>>();
+pub fn solve_a(jumps: &[i64]) -> usize {
+    let mut jumps = jumps.to_vec();
     let mut idx = 0i64;
     let mut steps = 0;
 
@@ -67,8 +63,8 @@ Using this rule with the above example, the process now takes 10 steps, and the
 How many steps does it now take to reach the exit?
 */
 
-fn solve_b(input: &str) -> usize {
-    let mut jumps = input.lines().map(|s| s.parse().unwrap()).collect::<Vec<i64>>();
+pub fn solve_b(jumps: &[i64]) -> usize {
+    let mut jumps = jumps.to_vec();
     let mut idx = 0i64;
     let mut steps = 0;
 
@@ -90,18 +86,18 @@ fn solve_b(input: &str) -> usize {
 
 #[test]
 fn test_a() {
-    assert_eq!(solve_a("0
+    assert_eq!(solve_a(&parse("0
 3
 0
 1
--3"), 5);
+-3").unwrap()), 5);
 }
 
 #[test]
 fn test_b() {
-    assert_eq!(solve_b("0
+    assert_eq!(solve_b(&parse("0
 3
 0
 1
--3"), 10);
+-3").unwrap()), 10);
 }
\ No newline at end of file
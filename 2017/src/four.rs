@@ -13,26 +13,33 @@ For example:
 
 The system's full passphrase list is available as your puzzle input. How many passphrases are valid?
 */
-use std::io::Read;
-use std::fs::File;
+
 use std::collections::HashSet;
 
-fn main() {
-    let mut input = File::open("inputs/four.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::parse::{ParseError, Parser};
+
+/// The passphrases, each checked to be lowercase words separated by single
+/// spaces.
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    let p = Parser::new(input);
 
-    input.read_to_string(&mut s).unwrap();
+    p.lines(|line| {
+        for word in line.split(' ') {
+            if word.is_empty() || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+                return Err(p.error(word, "a lowercase word"));
+            }
+        }
 
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+        Ok(line.to_owned())
+    })
 }
 
-fn solve_a(input: &str) -> usize {
-    input.lines().filter(|s| valid_a(s)).count()
+pub fn solve_a(passphrases: &[String]) -> usize {
+    passphrases.iter().filter(|s| valid_a(s)).count()
 }
 
-fn solve_b(input: &str) -> usize {
-    input.lines().filter(|s| valid_b(s)).count()
+pub fn solve_b(passphrases: &[String]) -> usize {
+    passphrases.iter().filter(|s| valid_b(s)).count()
 }
 
 fn valid_a(input: &str) -> bool {
@@ -81,16 +88,16 @@ fn valid_b(input: &str) -> bool {
 
 #[test]
 fn test_a() {
-    assert_eq!(valid_a("aa bb cc dd ee"), true);
-    assert_eq!(valid_a("aa bb cc dd aa"), false);
-    assert_eq!(valid_a("aa bb cc dd aaa"), true);
+    assert!(valid_a("aa bb cc dd ee"));
+    assert!(!valid_a("aa bb cc dd aa"));
+    assert!(valid_a("aa bb cc dd aaa"));
 }
 
 #[test]
 fn test_b() {
-    assert_eq!(valid_b("abcde fghij"), true);
-    assert_eq!(valid_b("abcde xyz ecdab"), false);
-    assert_eq!(valid_b("a ab abc abd abf abj"), true);
-    assert_eq!(valid_b("iiii oiii ooii oooi oooo"), true);
-    assert_eq!(valid_b("oiii ioii iioi iiio"), false);
+    assert!(valid_b("abcde fghij"));
+    assert!(!valid_b("abcde xyz ecdab"));
+    assert!(valid_b("a ab abc abd abf abj"));
+    assert!(valid_b("iiii oiii ooii oooi oooo"));
+    assert!(!valid_b("oiii ioii iioi iiio"));
 }
\ No newline at end of file
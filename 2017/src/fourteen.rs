@@ -29,23 +29,23 @@ In this example, 8108 squares are used across the entire 128x128 grid.
 Given your actual key string, how many squares are used?
 */
 
-#[allow(dead_code)]
-mod ten;
+use aoc_common::grid::Grid;
+use aoc_common::parse::{ParseError, Parser};
 
-use std::io::Read;
-use std::fs::File;
+use ten;
 
-fn main() {
-    let mut input = File::open("inputs/fourteen.txt").unwrap();
-    let mut s = String::new();
+/// The key string every row's hash is derived from.
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    let key = input.trim();
 
-    input.read_to_string(&mut s).unwrap();
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        return Err(Parser::new(input).error(key, "a key string"));
+    }
 
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+    Ok(key.to_owned())
 }
 
-fn solve_a(input: &str) -> u32 {
+pub fn solve_a(input: &str) -> u32 {
     let mut used = 0;
 
     for i in 0..128 {
@@ -83,41 +83,33 @@ How many regions are present given your key string?
 
 */
 
-fn solve_b(input: &str) -> usize {
-    let mut matrix = vec![vec![5; 128]; 128];
-
-    fn flood_fill(matrix: &mut [Vec<u8>], x: usize, y: usize) {
-        if x >= 128 || y >= 128 || matrix[y][x] == 0 {
-            return
-        }
-
-        matrix[y][x] = 0;
-        if x > 0 { flood_fill(matrix, x - 1, y) }
-        if y > 0 { flood_fill(matrix, x, y - 1) }
-        flood_fill(matrix, x + 1, y);
-        flood_fill(matrix, x, y + 1);
-    }
-
-    // Setup the matrix
-    for row in 0..128 {
+pub fn solve_b(input: &str) -> usize {
+    // Setup the grid
+    let rows = (0..128).map(|row| {
         let hash = ten::knot_hash(&format!("{}-{}", input, row));
 
-        for (bi, byte) in hash.into_iter().enumerate() {
-            // println!("{:b}", byte);
-            for i in 0..8 {
-                let col = (bi * 8) + i;
-                matrix[row][col] = (byte >> (7 - i) & 1 == 1) as u8;
-            }
-        }
-    }
+        hash.into_iter()
+            .flat_map(|byte| (0..8).map(move |i| byte >> (7 - i) & 1 == 1))
+            .collect()
+    }).collect();
+    let mut used = Grid::from_rows(rows, false);
 
     let mut regions = 0;
-    // Count the regions
-    for y in 0..128 {
-        for x in 0..128 {
-            if matrix[y][x] == 1 {
-                flood_fill(&mut matrix, x, y);
-                regions += 1;
+    // Count the regions, clearing each one out as it's found
+    for point in used.points() {
+        if !used[point] {
+            continue;
+        }
+
+        regions += 1;
+        used[point] = false;
+        let mut stack = vec![point];
+        while let Some(square) = stack.pop() {
+            for neighbour in used.neighbours_4(square).collect::<Vec<_>>() {
+                if used[neighbour] {
+                    used[neighbour] = false;
+                    stack.push(neighbour);
+                }
             }
         }
     }
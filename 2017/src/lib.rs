@@ -1,7 +1,193 @@
+#[macro_use]
+extern crate aoc_common;
+
+use aoc_common::{Day, Failure, Part, Year};
+use aoc_common::input::Normalise;
+
+pub mod one;
+pub mod two;
+pub mod three;
+pub mod four;
+pub mod five;
+pub mod six;
+pub mod seven;
+pub mod eight;
+pub mod nine;
+pub mod ten;
+pub mod eleven;
+pub mod twelve;
+pub mod thirteen;
+pub mod fourteen;
+pub mod fifteen;
+pub mod sixteen;
+pub mod seventeen;
+pub mod eighteen;
+pub mod nineteen;
+pub mod twenty;
+pub mod twenty_one;
+pub mod twenty_two;
+pub mod twenty_three;
+pub mod twenty_four;
+pub mod twenty_five;
+
+// Each day by number as well, e.g. `day10::knot_hash`.
+pub use one as day01;
+pub use two as day02;
+pub use three as day03;
+pub use four as day04;
+pub use five as day05;
+pub use six as day06;
+pub use seven as day07;
+pub use eight as day08;
+pub use nine as day09;
+pub use ten as day10;
+pub use eleven as day11;
+pub use twelve as day12;
+pub use thirteen as day13;
+pub use fourteen as day14;
+pub use fifteen as day15;
+pub use sixteen as day16;
+pub use seventeen as day17;
+pub use eighteen as day18;
+pub use nineteen as day19;
+pub use twenty as day20;
+pub use twenty_one as day21;
+pub use twenty_two as day22;
+pub use twenty_three as day23;
+pub use twenty_four as day24;
+pub use twenty_five as day25;
+
+/// Every day solved for 2017.
+///
+/// Parts are all registered through closures, like the ones `aoc new`
+/// writes, whether or not a day's types need one to coerce.
+#[allow(clippy::redundant_closure)]
+pub fn year() -> Year {
+    Year::new(2017, env!("CARGO_MANIFEST_DIR"))
+        .register(Day::new(1, "one")
+            .parse(one::parse)
+            .part_a(|digits| one::solve_a(digits))
+            .part_b(|digits| one::solve_b(digits)))
+        .register(Day::new(2, "two")
+            .parse(two::parse)
+            .part_a(|rows| two::solve_a(rows))
+            .part_b(|rows| two::solve_b(rows)))
+        .register(Day::new(3, "three")
+            .parse(three::parse)
+            .part_a(|&target| three::solve_a(target))
+            .part_b(|&target| three::solve_b(target)))
+        .register(Day::new(4, "four")
+            .parse(four::parse)
+            .part_a(|passphrases| four::solve_a(passphrases))
+            .part_b(|passphrases| four::solve_b(passphrases)))
+        .register(Day::new(5, "five")
+            .parse(five::parse)
+            .part_a(|jumps| five::solve_a(jumps))
+            .part_b(|jumps| five::solve_b(jumps)))
+        .register(Day::new(6, "six")
+            .parse(six::parse)
+            .part_a(|memory| six::solve_a(memory))
+            .part_b(|memory| six::solve_b(memory)))
+        .register(Day::new(7, "seven")
+            .parse(seven::parse)
+            .part_a(|programs| seven::solve_a(programs))
+            .part_b(|programs| seven::solve_b(programs))
+            .explain_b(|programs| Some(seven::explain_b(programs))))
+        .register(Day::new(8, "eight")
+            .parse(eight::parse)
+            .part_a(|program| eight::solve_a(program))
+            .part_b(|program| eight::solve_b(program)))
+        .register(Day::new(9, "nine")
+            .parse(nine::parse)
+            .part_a(|stream| nine::solve_a(stream))
+            .part_b(|stream| nine::solve_b(stream)))
+        .register(Day::new(10, "ten")
+            .parse(ten::parse)
+            .part_a(|lengths| ten::solve_a(&lengths.numbers, 256))
+            .part_b(|lengths| ten::solve_b(&lengths.text))
+            .alternative_a("rotating", |lengths| ten::solve_a_with(&lengths.numbers, 256, ten::reverse_by_rotating))
+            .alternative_b("rotating", |lengths| ten::solve_b_with(&lengths.text, ten::reverse_by_rotating))
+            .generate(ten::generate))
+        .register(Day::new(11, "eleven")
+            .parse(eleven::parse)
+            .part_a(|path| eleven::solve_a(path))
+            .part_b(|path| eleven::solve_b(path)))
+        .register(Day::new(12, "twelve")
+            .parse(twelve::parse)
+            .part_a(|pipes| twelve::solve_a(pipes))
+            .part_b(|pipes| twelve::solve_b(pipes)))
+        .register(Day::new(13, "thirteen")
+            .parse(thirteen::parse)
+            .part_a(|layers| thirteen::solve_a(layers))
+            .part_b_cancellable(|layers, token| thirteen::solve_b(layers, token))
+            .generate(thirteen::generate))
+        .register(Day::new(14, "fourteen")
+            .parse(fourteen::parse)
+            .part_a(|key| fourteen::solve_a(key))
+            .part_b(|key| fourteen::solve_b(key)))
+        .register(Day::new(15, "fifteen")
+            .parse(fifteen::parse)
+            .part_a(|&(a, b)| fifteen::solve_a(a, b))
+            .part_b(|&(a, b)| fifteen::solve_b(a, b)))
+        .register(Day::new(16, "sixteen")
+            .parse(|input| sixteen::parse(16, input))
+            .part_a(|moves| sixteen::solve_a(16, moves))
+            .part_b(|moves| sixteen::solve_b(16, moves, 1_000_000_000))
+            .alternative_a("permutations", |moves| sixteen::solve_by_permutations(16, moves, 1))
+            .alternative_b("permutations", |moves| sixteen::solve_by_permutations(16, moves, 1_000_000_000))
+            .generate(sixteen::generate))
+        .register(Day::new(17, "seventeen")
+            .parse(seventeen::parse)
+            .part_a(|&spins| seventeen::solve_a(spins))
+            .part_b(|&spins| seventeen::solve_b(spins)))
+        .register(Day::new(18, "eighteen")
+            .parse(eighteen::parse)
+            .part_a_cancellable(|program, token| eighteen::solve_a(program, token))
+            .part_b_cancellable(|program, token| eighteen::solve_b(program, token)))
+        .register(Day::new(19, "nineteen")
+            .normalise(Normalise::LineEndings)
+            .parse(nineteen::parse)
+            .part_a(|grid| nineteen::solve_a(grid))
+            .part_b(|grid| nineteen::solve_b(grid)))
+        .register(Day::new(20, "twenty")
+            .parse(twenty::parse)
+            .part_a(|particles| twenty::solve_a(particles))
+            .part_b(|particles| twenty::solve_b(particles))
+            .generate(twenty::generate))
+        .register(Day::new(21, "twenty_one")
+            .parse(twenty_one::parse)
+            .part_a(|rules| twenty_one::solve(rules, 5))
+            .part_b(|rules| twenty_one::solve(rules, 18)))
+        .register(Day::new(22, "twenty_two")
+            .parse(twenty_two::parse)
+            .part_a(|grid| twenty_two::solve_a(grid))
+            .part_b(|grid| twenty_two::solve_b(grid)))
+        .register(Day::new(23, "twenty_three")
+            .parse(twenty_three::parse)
+            .part_a(|program| twenty_three::solve(program))
+            .part_b(|program| twenty_three::solve_b(program))
+            .alternative_a("counted", |program| {
+                twenty_three::Constants::find(program).map(|constants| twenty_three::counted(&constants))
+            })
+            .alternative_b_cancellable("translated", |program, token| -> Result<i64, Failure> {
+                Ok(twenty_three::translated(&twenty_three::Constants::find(program)?, token)?)
+            })
+            .alternative_b_cancellable("interpreted", |program, token| twenty_three::interpreted(program, token))
+            .generate(twenty_three::generate))
+        .register(Day::new(24, "twenty_four")
+            .parse(twenty_four::parse)
+            .part_a(|components| twenty_four::solve_a(components))
+            .part_b(|components| twenty_four::solve_b(components))
+            .explain_a(|components| Some(twenty_four::explain_a(components)))
+            .explain_b(|components| Some(twenty_four::explain_b(components))))
+        .register(Day::new(25, "twenty_five")
+            .parse(twenty_five::parse)
+            .part_a(|blueprint| twenty_five::solve_a(blueprint))
+            .part_b(|blueprint| twenty_five::solve_b(blueprint))
+            .placeholder(Part::B))
+}
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
+    year_tests!(::year());
 }
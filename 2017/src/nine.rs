@@ -47,31 +47,32 @@ What is the total score for all groups in your input?
 
 */
 
-use std::io::Read;
-use std::fs::File;
+use aoc_common::parse::{char_at, ParseError, Parser};
 
-fn main() {
-    let mut input = File::open("inputs/nine.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
-
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+/// The total score of every group in a stream, and how much garbage it held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stream {
+    pub score: u64,
+    pub garbage: u64,
 }
 
-fn solve_a(input: &str) -> u64 {
-    solve(input).0
+pub fn solve_a(stream: &Stream) -> u64 {
+    stream.score
 }
 
-fn solve(input: &str) -> (u64, u64) {
+/// There's nothing to the stream but its structure, so parsing it is all
+/// the work there is.
+pub fn parse(input: &str) -> Result<Stream, ParseError> {
+    let p = Parser::new(input);
+    let input = input.trim();
+
     let mut in_garbage = false;
     let mut ignore_next = false;
     let mut depth = 0;
     let mut score = 0;
     let mut removed_garbage = 0;
 
-    for c in input.chars() {
+    for (i, c) in input.char_indices() {
         if in_garbage {
             if ignore_next {
                 ignore_next = false;
@@ -90,12 +91,19 @@ fn solve(input: &str) -> (u64, u64) {
                     score += depth;
                     depth -= 1;
                 }
-                _ => {}
+                ',' => {}
+                _ => return Err(p.error(char_at(input, i), "a group or garbage")),
             }
         }
     }
 
-    (score, removed_garbage)
+    if in_garbage {
+        return Err(p.error_after(input, "\">\""));
+    } else if depth > 0 {
+        return Err(p.error_after(input, "\"}\""));
+    }
+
+    Ok(Stream { score, garbage: removed_garbage })
 }
 
 /*
@@ -116,30 +124,30 @@ To prove you've removed it, you need to count all of the characters within the g
 How many non-canceled characters are within the garbage in your puzzle input?
 */
 
-fn solve_b(input: &str) -> u64 {
-    solve(input).1
+pub fn solve_b(stream: &Stream) -> u64 {
+    stream.garbage
 }
 
 #[test]
 fn test_a() {
-    assert_eq!(solve_a("{}"), 1);
-    assert_eq!(solve_a("{{{}}}"), 6);
-    assert_eq!(solve_a("{{},{}}"), 5);
-    assert_eq!(solve_a("{{{},{},{{}}}}"), 16);
-    assert_eq!(solve_a("{<{},{},{{}}>}"), 1);
-    assert_eq!(solve_a("{<a>,<a>,<a>,<a>}"), 1);
-    assert_eq!(solve_a("{{<ab>},{<ab>},{<ab>},{<ab>}}"), 9);
-    assert_eq!(solve_a("{{<!!>},{<!!>},{<!!>},{<!!>}}"), 9);
-    assert_eq!(solve_a("{{<a!>},{<a!>},{<a!>},{<ab>}}"), 3);
+    assert_eq!(solve_a(&parse("{}").unwrap()), 1);
+    assert_eq!(solve_a(&parse("{{{}}}").unwrap()), 6);
+    assert_eq!(solve_a(&parse("{{},{}}").unwrap()), 5);
+    assert_eq!(solve_a(&parse("{{{},{},{{}}}}").unwrap()), 16);
+    assert_eq!(solve_a(&parse("{<{},{},{{}}>}").unwrap()), 1);
+    assert_eq!(solve_a(&parse("{<a>,<a>,<a>,<a>}").unwrap()), 1);
+    assert_eq!(solve_a(&parse("{{<ab>},{<ab>},{<ab>},{<ab>}}").unwrap()), 9);
+    assert_eq!(solve_a(&parse("{{<!!>},{<!!>},{<!!>},{<!!>}}").unwrap()), 9);
+    assert_eq!(solve_a(&parse("{{<a!>},{<a!>},{<a!>},{<ab>}}").unwrap()), 3);
 }
 
 #[test]
 fn test_b() {
-    assert_eq!(solve_b("<>"), 0);
-    assert_eq!(solve_b("<random characters>"), 17);
-    assert_eq!(solve_b("<<<<>"), 3);
-    assert_eq!(solve_b("<{!>}>"), 2);
-    assert_eq!(solve_b("<!!>"), 0);
-    assert_eq!(solve_b("<!!!>>"), 0);
-    assert_eq!(solve_b(r#"<{o"i!a,<{i<a>"#), 10);
+    assert_eq!(solve_b(&parse("<>").unwrap()), 0);
+    assert_eq!(solve_b(&parse("<random characters>").unwrap()), 17);
+    assert_eq!(solve_b(&parse("<<<<>").unwrap()), 3);
+    assert_eq!(solve_b(&parse("<{!>}>").unwrap()), 2);
+    assert_eq!(solve_b(&parse("<!!>").unwrap()), 0);
+    assert_eq!(solve_b(&parse("<!!!>>").unwrap()), 0);
+    assert_eq!(solve_b(&parse(r#"<{o"i!a,<{i<a>"#).unwrap()), 10);
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+/*
+--- Day 19: A Series of Tubes ---
+
+Somehow, a network packet got lost and ended up here. It's trying to follow a routing diagram (your puzzle input), but it's confused about where to go.
+
+Its starting point is just off the top of the diagram. Lines (drawn with |, -, and +) show the path it needs to take, starting by going down onto the only line connected to the top of the diagram. It needs to follow this path until it reaches the end (located somewhere within the diagram) and stop there.
+
+Sometimes, the lines cross over each other; in these cases, it needs to continue going the same direction, and only turn left or right when there's no other option. In addition, someone has left letters on the line; these also don't change its direction, but it can use them to keep track of where it's been. For example:
+
+     |
+     |  +--+
+     A  |  C
+ F---|----E|--+
+     |  |  |  D
+     +B-+  +--+
+
+Given this diagram, the packet needs to take the following path:
+
+    Starting at the only line touching the top of the diagram, it must go down, pass through A, and continue onward to the first +.
+    Travel right, up, and right, passing through B in the process.
+    Continue down (collecting C), right, and up (collecting D).
+    Finally, go all the way left through E and stopping at F.
+
+Following the path to the end, the letters it sees on its path are ABCDEF.
+
+The little packet looks up at you, hoping you can help it find the way. What letters will it see (in the order it would see them) if it follows the path? (The routing diagram is very wide; make sure you view it without line wrapping.)
+*/
+
+use aoc_common::grid::Grid;
+use aoc_common::heading::Heading;
+use aoc_common::parse::{char_at, ParseError, Parser};
+
+/// The diagram, padded out to a rectangle. Leading whitespace is
+/// significant so the input mustn't be trimmed.
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    let p = Parser::new(input);
+    let mut rows = vec![];
+    for line in input.lines() {
+        if let Some(i) = line.find(|c: char| !(c == ' ' || c == '|' || c == '-' || c == '+' || c.is_ascii_uppercase())) {
+            return Err(p.error(char_at(line, i), "a path, a letter or a space"));
+        }
+
+        rows.push(line.chars().collect());
+    }
+
+    match input.lines().next() {
+        Some(first) if first.contains('|') => Ok(Grid::from_rows(rows, ' ')),
+        first => Err(p.error_after(first.unwrap_or(input), "a \"|\" to start from")),
+    }
+}
+
+pub fn solve_a(grid: &Grid<char>) -> String {
+    solve(grid).0
+}
+
+fn solve(grid: &Grid<char>) -> (String, usize) {
+    let mut position = (grid.row(0).iter().position(|&c| c == '|').unwrap(), 0);
+    let mut heading = Heading::South;
+    let mut seen = String::new();
+    let mut steps = 0;
+
+    loop {
+        steps += 1;
+
+        match grid[position] {
+            '+' => {
+                // Turn whichever way the path carries on.
+                let turn = [heading.left(), heading.right()].iter().cloned()
+                    .find(|&turn| grid.step(position, turn.offset()).is_some_and(|next| grid[next] != ' '));
+
+                match turn {
+                    Some(turn) => heading = turn,
+                    None => break,
+                }
+            }
+            c if c.is_ascii_uppercase() => seen.push(c),
+            _ => {}
+        }
+
+        match grid.step(position, heading.offset()) {
+            Some(next) if grid[next] != ' ' => position = next,
+            _ => break,
+        }
+    }
+
+    (seen, steps)
+}
+
+/*
+--- Part Two ---
+
+The packet is curious how many steps it needs to go.
+
+For example, using the same routing diagram from the example above...
+
+     |
+     |  +--+
+     A  |  C
+ F---|--|-E---+
+     |  |  |  D
+     +B-+  +--+
+
+...the packet would go:
+
+    6 steps down (including the first line at the top of the diagram).
+    3 steps right.
+    4 steps up.
+    3 steps right.
+    4 steps down.
+    3 steps right.
+    2 steps up.
+    13 steps left (including the F it stops on).
+
+This would result in a total of 38 steps.
+
+How many steps does the packet need to go?
+
+*/
+
+pub fn solve_b(grid: &Grid<char>) -> usize {
+    solve(grid).1
+}
+
+#[test]
+fn test_a() {
+    assert_eq!(solve_a(&parse(
+"     |
+     |  +--+
+     A  |  C
+ F---|----E|--+
+     |  |  |  D
+     +B-+  +--+ ").unwrap()), "ABCDEF");
+}
+
+#[test]
+fn test_b() {
+    assert_eq!(solve_b(&parse(
+"     |
+     |  +--+
+     A  |  C
+ F---|----E|--+
+     |  |  |  D
+     +B-+  +--+ ").unwrap()), 38);
+}
\ No newline at end of file
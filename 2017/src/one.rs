@@ -23,17 +23,18 @@ For example:
 What is the solution to your captcha?
 */
 
-use std::io::{self, BufRead, BufReader};
+use aoc_common::parse::{char_at, ParseError, Parser};
 
-fn main() {
-    let stdin = BufReader::new(io::stdin());
-    let input = stdin.lines().next().unwrap().unwrap();
-    println!("a: {}", solve_a(&input));
-    println!("b: {}", solve_b(&input));
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    let p = Parser::new(input);
+    let input = input.trim();
+
+    input.char_indices()
+        .map(|(i, c)| c.to_digit(10).ok_or_else(|| p.error(char_at(input, i), "a digit")))
+        .collect()
 }
 
-fn solve(input: &str, offset: usize) -> u32 {
-    let digits = input.chars().map(|c| c.to_digit(10).unwrap()).collect::<Vec<_>>();
+fn solve(digits: &[u32], offset: usize) -> u32 {
     let mut sum = 0;
 
     for i in 0..digits.len() {
@@ -47,13 +48,12 @@ fn solve(input: &str, offset: usize) -> u32 {
     sum
 }
 
-fn solve_a(input: &str) -> u32 {
-    solve(input, 1)
+pub fn solve_a(digits: &[u32]) -> u32 {
+    solve(digits, 1)
 }
 
-fn solve_b(input: &str) -> u32 {
-    let len = input.len();
-    solve(input, len / 2)
+pub fn solve_b(digits: &[u32]) -> u32 {
+    solve(digits, digits.len() / 2)
 }
 
 /*
@@ -76,17 +76,17 @@ What is the solution to your new captcha?
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("1122"), 3);
-    assert_eq!(solve_a("1111"), 4);
-    assert_eq!(solve_a("1234"), 0);
-    assert_eq!(solve_a("91212129"), 9);
+    assert_eq!(solve_a(&parse("1122").unwrap()), 3);
+    assert_eq!(solve_a(&parse("1111").unwrap()), 4);
+    assert_eq!(solve_a(&parse("1234").unwrap()), 0);
+    assert_eq!(solve_a(&parse("91212129").unwrap()), 9);
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("1212"), 6);
-    assert_eq!(solve_b("1221"), 0);
-    assert_eq!(solve_b("123425"), 4);
-    assert_eq!(solve_b("123123"), 12);
-    assert_eq!(solve_b("12131415"), 4);
+    assert_eq!(solve_b(&parse("1212").unwrap()), 6);
+    assert_eq!(solve_b(&parse("1221").unwrap()), 0);
+    assert_eq!(solve_b(&parse("123425").unwrap()), 4);
+    assert_eq!(solve_b(&parse("123123").unwrap()), 12);
+    assert_eq!(solve_b(&parse("12131415").unwrap()), 4);
 }
\ No newline at end of file
@@ -49,48 +49,68 @@ Before you're ready to help them, you need to make sure your information is corr
 
 */
 
-use std::io::Read;
-use std::fs::File;
 use std::collections::{HashSet, HashMap};
+use std::fmt::{self, Display};
 
-fn main() {
-    let mut input = File::open("inputs/seven.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::parse::{ParseError, Parser};
 
-    input.read_to_string(&mut s).unwrap();
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    pub name: String,
+    pub weight: usize,
+    pub children: Vec<String>,
+}
+
+/// Parses lines like `fwft (72) -> ktlj, cntj, xhth`.
+pub fn parse(input: &str) -> Result<Vec<Program>, ParseError> {
+    let p = Parser::new(input);
+
+    p.lines(|line| {
+        let mut split = line.splitn(2, " -> ");
+
+        // ugml (68)
+        let mut nw = split.next().unwrap_or(line).split(' ');
+        let name = p.token(&mut nw, line, "a program name")?;
+        let weight = p.literal(p.token(&mut nw, line, "a weight")?, "(")?;
+        let weight = p.number(p.suffix(weight, ")")?)?;
+        p.end(&mut nw)?;
+
+        // gyxo, ebii, jptl
+        let mut children = vec![];
+        if let Some(list) = split.next() {
+            for child in list.split(", ") {
+                if child.is_empty() {
+                    return Err(p.error(child, "a program name"));
+                }
+                children.push(child.to_owned());
+            }
+        }
 
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+        Ok(Program { name: name.to_owned(), weight, children })
+    })
 }
 
-fn solve_a(input: &str) -> &str {
+pub fn solve_a(programs: &[Program]) -> String {
     let mut parents = vec![];
     let mut children = HashSet::new();
 
-    for line in input.lines() {
-        let mut split = line.split("->");
-        let name_weight = split.next().unwrap();
+    for program in programs {
+        if !program.children.is_empty() {
+            parents.push(&*program.name);
 
-        if let Some(list) = split.next() {
-            // ugml (68)
-            let parent = name_weight.split(' ').next().unwrap();
-            parents.push(parent);
-
-            // gyxo, ebii, jptl
-            for child in list.trim().split(',').map(|s| s.trim()) {
-                children.insert(child);
+            for child in &program.children {
+                children.insert(&**child);
             }
         }
     }
 
     for parent in &parents {
         if !children.contains(parent) {
-            return parent
+            return parent.to_string()
         }
     }
 
-    println!("{:?}", parents);
-    println!("{:?}", children);
+    error!("no root among parents {:?} with children {:?}", parents, children);
     unreachable!()
 }
 
@@ -114,39 +134,50 @@ As you can see, tknk's disc is unbalanced: ugml's stack is heavier than the othe
 Given that exactly one program is the wrong weight, what would its weight need to be to balance the entire tower?
 */
 
-fn solve_b(input: &str) -> usize {
+/// The program with the wrong weight, and what its tower weighs against
+/// those of its siblings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Imbalance {
+    pub program: String,
+    pub weight: usize,
+    pub tower_weight: usize,
+    pub sibling_tower_weight: usize,
+    pub should_be: usize,
+}
+
+impl Display for Imbalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} weighs {} and its tower {}, while its siblings' towers weigh {}; it should weigh {}",
+               self.program, self.weight, self.tower_weight, self.sibling_tower_weight, self.should_be)
+    }
+}
+
+pub fn solve_b(programs: &[Program]) -> usize {
+    explain_b(programs).should_be
+}
+
+pub fn explain_b(programs: &[Program]) -> Imbalance {
     let mut parents = HashMap::new();
     let mut weights = HashMap::new();
     let mut children = HashSet::new();
 
-    for line in input.lines() {
-        let mut split = line.split("->");
-
-        // ugml (68)
-        let name_weight = split.next().unwrap();
-        let mut nw = name_weight.split(' ');
-        let (parent, weight) = (
-            nw.next().unwrap(),
-            nw.next().unwrap()
-                .trim_matches(&['(', ')'][..])
-                .parse::<usize>().unwrap()
-        );
-        weights.insert(parent, weight);
+    for program in programs {
+        let parent = &*program.name;
+        weights.insert(parent, program.weight);
 
-        if let Some(list) = split.next() {
+        if !program.children.is_empty() {
             let parent = parents.entry(parent).or_insert(vec![]);
 
-            // gyxo, ebii, jptl
-            for child in list.trim().split(',').map(|s| s.trim()) {
-                children.insert(child);
-                parent.push(child);
+            for child in &program.children {
+                children.insert(&**child);
+                parent.push(&**child);
             }
         }
     }
 
 
     let mut current = None;
-    for (parent, _) in &parents {
+    for parent in parents.keys() {
         if !children.contains(parent) {
             current = Some(parent);
         }
@@ -169,7 +200,7 @@ fn solve_b(input: &str) -> usize {
             let cw =  get_weight(child, &parents, &weights);
             *child_weights.entry(cw).or_insert(0) += 1;
 
-            println!("[{}] {} ({}) => {}", current, child, weights[child],cw);
+            trace!("[{}] {} ({}) => {}", current, child, weights[child], cw);
         }
 
         let mut child_weights = child_weights.into_iter().collect::<Vec<(_, _)>>();
@@ -195,29 +226,35 @@ fn solve_b(input: &str) -> usize {
 
             let culprit = subchild_weights.iter()
                 .position(|&cw| cw != subchild_weights[0]);
-            println!("subchildren: {:?}", subchild_weights);
+            trace!("subchildren: {:?}", subchild_weights);
 
-            if let Some(_) = culprit {
+            if culprit.is_some() {
                 // If we have an unbalanced child then check them instead
                 current = child;
-                println!("recursing to {}", current);
+                debug!("recursing to {}", current);
             } else {
-                let subweight = subchild_weights.get(0).unwrap_or(&0) * subchild_weights.len();
+                let subweight = subchild_weights.first().unwrap_or(&0) * subchild_weights.len();
 
                 let adjusted = common - subweight;
 
-                println!("Should adjust {} to {} - {} = {}", cw, common, subweight, adjusted);
-                return adjusted;
+                debug!("Should adjust {} to {} - {} = {}", cw, common, subweight, adjusted);
+                return Imbalance {
+                    program: child.to_string(),
+                    weight: weights[child],
+                    tower_weight: cw,
+                    sibling_tower_weight: common,
+                    should_be: adjusted,
+                };
             }
         }
 
-        println!("{:?}", child_weights);
+        trace!("{:?}", child_weights);
     }
 }
 
 #[test]
 fn test_a() {
-    assert_eq!(solve_a("pbga (66)
+    assert_eq!(solve_a(&parse("pbga (66)
 xhth (57)
 ebii (61)
 havc (66)
@@ -229,12 +266,12 @@ tknk (41) -> ugml, padx, fwft
 jptl (61)
 ugml (68) -> gyxo, ebii, jptl
 gyxo (61)
-cntj (57)"), "tknk");
+cntj (57)").unwrap()), "tknk");
 }
 
 #[test]
 fn test_b() {
-    assert_eq!(solve_b("pbga (66)
+    assert_eq!(solve_b(&parse("pbga (66)
 xhth (57)
 ebii (61)
 havc (66)
@@ -246,5 +283,26 @@ tknk (41) -> ugml, padx, fwft
 jptl (61)
 ugml (68) -> gyxo, ebii, jptl
 gyxo (61)
-cntj (57)"), 60);
-}
\ No newline at end of file
+cntj (57)").unwrap()), 60);
+}
+
+#[test]
+fn test_explain_b() {
+    let programs = parse("pbga (66)\nxhth (57)\nebii (61)\nhavc (66)\nktlj (57)\nfwft (72) -> ktlj, cntj, xhth\nqoyq (66)\npadx (45) -> pbga, havc, qoyq\ntknk (41) -> ugml, padx, fwft\njptl (61)\nugml (68) -> gyxo, ebii, jptl\ngyxo (61)\ncntj (57)").unwrap();
+
+    assert_eq!(explain_b(&programs).to_string(), "ugml weighs 68 and its tower 251, while its siblings' towers weigh 243; it should weigh 60");
+}
+
+#[test]
+fn test_parse() {
+    let input = "pbga (66)\nfwft (72) -> ktlj, cntj\npadx 45 -> pbga";
+
+    assert_eq!(parse("fwft (72) -> ktlj, cntj").unwrap(), vec![Program {
+        name: "fwft".to_string(),
+        weight: 72,
+        children: vec!["ktlj".to_string(), "cntj".to_string()],
+    }]);
+
+    let error = parse(input).unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (3, 6, "4"));
+}
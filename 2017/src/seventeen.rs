@@ -35,12 +35,13 @@ What is the value after 2017 in your completed circular buffer?
 
 */
 
-fn main() {
-    println!("a: {}", solve_a(382));
-    println!("b: {}", solve_b(382));
+use aoc_common::parse::{ParseError, Parser};
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    Parser::new(input).number(input.trim())
 }
 
-fn solve_a(spins: usize) -> usize {
+pub fn solve_a(spins: usize) -> usize {
     let mut idx = 0;
     let mut items = vec![0];
 
@@ -71,7 +72,7 @@ The bad news is that while you were determining this, the spinlock has just fini
 What is the value after 0 the moment 50000000 is inserted?
 */
 
-fn solve_b(spins: usize) -> usize {
+pub fn solve_b(spins: usize) -> usize {
     let mut idx = 0;
     let mut last = 0;
 
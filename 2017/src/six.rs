@@ -23,24 +23,18 @@ At this point, we've reached a state we've seen before: 2 4 1 2 was already seen
 Given the initial block counts in your puzzle input, how many redistribution cycles must be completed before a configuration is produced that has been seen before?
 */
 
-use std::io::Read;
-use std::fs::File;
 use std::collections::HashSet;
 
-fn main() {
-    let mut input = File::open("inputs/six.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::parse::{ParseError, Parser};
 
-    input.read_to_string(&mut s).unwrap();
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    let p = Parser::new(input);
 
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+    input.split_whitespace().map(|s| p.number(s)).collect()
 }
 
-fn solve_a(input: &str) -> usize {
-    let mut memory = input.split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<u32>>();
+pub fn solve_a(memory: &[u32]) -> usize {
+    let mut memory = memory.to_vec();
 
     let mut seen = HashSet::new();
     let mut steps = 0;
@@ -83,10 +77,8 @@ In the example above, 2 4 1 2 is seen again after four cycles, and so the answer
 How many cycles are in the infinite loop that arises from the configuration in your puzzle input?
 */
 
-fn solve_b(input: &str) -> usize {
-    let mut memory = input.split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<u32>>();
+pub fn solve_b(memory: &[u32]) -> usize {
+    let mut memory = memory.to_vec();
 
     let mut seen = HashSet::new();
     let mut steps = 0;
@@ -124,10 +116,10 @@ fn solve_b(input: &str) -> usize {
 
 #[test]
 fn test_a() {
-    assert_eq!(solve_a("0   2   7   0"), 5);
+    assert_eq!(solve_a(&parse("0   2   7   0").unwrap()), 5);
 }
 
 #[test]
 fn test_b() {
-    assert_eq!(solve_b("0   2   7   0"), 4);
+    assert_eq!(solve_b(&parse("0   2   7   0").unwrap()), 4);
 }
\ No newline at end of file
@@ -0,0 +1,240 @@
+/*
+--- Day 16: Permutation Promenade ---
+
+You come upon a very unusual sight; a group of programs here appear to be dancing.
+
+There are sixteen programs in total, named a through p. They start by standing in a line: a stands in position 0, b stands in position 1, and so on until p, which stands in position 15.
+
+The programs' dance consists of a sequence of dance moves:
+
+    Spin, written sX, makes X programs move from the end to the front, but maintain their order otherwise. (For example, s3 on abcde produces cdeab).
+    Exchange, written xA/B, makes the programs at positions A and B swap places.
+    Partner, written pA/B, makes the programs named A and B swap places.
+
+For example, with only five programs standing in a line (abcde), they could do the following dance:
+
+    s1, a spin of size 1: eabcd.
+    x3/4, swapping the last two programs: eabdc.
+    pe/b, swapping programs e and b: baedc.
+
+After finishing their dance, the programs end up in order baedc.
+
+You watch the dance for a while and record their dance moves (your puzzle input). In what order are the programs standing after their dance?
+*/
+
+use std::collections::HashMap;
+
+use aoc_common::generate::Rng;
+use aoc_common::parse::{ParseError, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Spin(usize),
+    Exchange(usize, usize),
+    Partner(char, char),
+}
+
+/// Parses moves like `s1,x3/4,pe/b` for a line of `count` programs, which
+/// every move has to stay within.
+pub fn parse(count: usize, input: &str) -> Result<Vec<Move>, ParseError> {
+    let p = Parser::new(input);
+    let last = (b'a' + count.clamp(1, 26) as u8 - 1) as char;
+
+    input.trim().split(',').map(|command| {
+        let (kind, args) = command.split_at(command.chars().next().map_or(0, char::len_utf8));
+        let program = |name: &str| {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c @ 'a'..='z'), None) if c <= last => Ok(c),
+                _ => Err(p.error(name, format!("a program from a to {}", last))),
+            }
+        };
+        let place = |text: &str| match p.number(text)? {
+            n if n < count => Ok(n),
+            _ => Err(p.error(text, format!("a place below {}", count))),
+        };
+
+        // Exchanges and partners both take a pair, e.g. `x3/4` or `pe/b`.
+        let pair = || {
+            let mut split = args.split('/');
+            let left = p.token(&mut split, args, "a pair")?;
+            let right = p.token(&mut split, args, "\"/\"")?;
+            p.end(&mut split)?;
+
+            Ok((left, right))
+        };
+
+        match kind {
+            "s" => match p.number(args)? {
+                n if n <= count => Ok(Move::Spin(n)),
+                _ => Err(p.error(args, format!("a spin of at most {}", count))),
+            },
+            "x" => pair().and_then(|(a, b)| Ok(Move::Exchange(place(a)?, place(b)?))),
+            "p" => pair().and_then(|(a, b)| Ok(Move::Partner(program(a)?, program(b)?))),
+            kind => Err(p.error(kind, "s, x or p")),
+        }
+    }).collect()
+}
+
+pub fn solve_a(count: usize, moves: &[Move]) -> String {
+    let chars = (0..count).map(|i| (i as u8 + b'a') as char).collect::<Vec<_>>();
+    solve(chars, moves).into_iter().collect()
+}
+
+fn solve(mut chars: Vec<char>, moves: &[Move]) -> Vec<char> {
+    for &command in moves {
+        match command {
+            Move::Spin(pivot) => {
+                let next = {
+                    let len = chars.len();
+                    let (before, after) = chars.split_at(len - pivot);
+                    let mut after = after.to_vec();
+                    after.extend(before);
+                    after
+                };
+                chars = next;
+            }
+            Move::Exchange(left, right) => {
+                chars.swap(left, right);
+            }
+            Move::Partner(left, right) => {
+                let left_idx = chars.iter().position(|&c| c == left).unwrap();
+                let right_idx = chars.iter().position(|&c| c == right).unwrap();
+
+                chars.swap(left_idx, right_idx);
+            }
+        }
+    }
+
+    chars
+}
+
+/*
+--- Part Two ---
+
+Now that you're starting to get a feel for the dance moves, you turn your attention to the dance as a whole.
+
+Keeping the positions they ended up in from their previous dance, the programs perform it again and again: including the first dance, a total of one billion (1000000000) times.
+
+In the example above, their second dance would begin with the order baedc, and use the same dance moves:
+
+    s1, a spin of size 1: cbaed.
+    x3/4, swapping the last two programs: cbade.
+    pe/b, swapping programs e and b: ceadb.
+
+In what order are the programs standing after their billion dances?
+
+*/
+
+pub fn solve_b(count: usize, moves: &[Move], reps: usize) -> String {
+    let mut idx = 0;
+    let mut chars = (0..count).map(|i| (i as u8 + b'a') as char).collect::<Vec<_>>();
+    let mut map = HashMap::<_, usize>::new();
+    let mut positions = vec![];
+
+    loop {
+        let result = solve(chars, moves);
+        chars = result.clone();
+
+        if map.contains_key(&result) {
+            assert_eq!(map[&result], 0);
+            break
+        }
+
+        map.insert(result.clone(), idx);
+        positions.push(result);
+        idx += 1;
+    }
+
+    positions[(reps - 1) % idx].iter().collect()
+}
+
+/// The dance done `reps` times without looking for a cycle.
+///
+/// Spins and exchanges only move places around and partners only swap names,
+/// so a dance is a shuffle of places followed by a renaming. Each of those
+/// is a permutation that can be raised to a power by squaring on its own.
+pub fn solve_by_permutations(count: usize, moves: &[Move], reps: usize) -> String {
+    // Which place each place's program comes from, and what each name
+    // becomes.
+    let mut places = (0..count).collect::<Vec<_>>();
+    let mut names = (0..count).collect::<Vec<_>>();
+
+    for &command in moves {
+        match command {
+            Move::Spin(pivot) => places.rotate_right(pivot),
+            Move::Exchange(left, right) => places.swap(left, right),
+            Move::Partner(left, right) => {
+                let left = names.iter().position(|&n| n == (left as u8 - b'a') as usize).unwrap();
+                let right = names.iter().position(|&n| n == (right as u8 - b'a') as usize).unwrap();
+                names.swap(left, right);
+            }
+        }
+    }
+
+    fn power(permutation: &[usize], mut exponent: usize) -> Vec<usize> {
+        let mut result = (0..permutation.len()).collect::<Vec<_>>();
+        let mut square = permutation.to_vec();
+
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.iter().map(|&i| square[i]).collect();
+            }
+            square = square.iter().map(|&i| square[i]).collect();
+            exponent /= 2;
+        }
+
+        result
+    }
+
+    let (places, names) = (power(&places, reps), power(&names, reps));
+    places.iter().map(|&place| (names[place] as u8 + b'a') as char).collect()
+}
+
+/// `size` moves for sixteen programs.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let program = |rng: &mut Rng| (rng.below(16) as u8 + b'a') as char;
+    let moves = (0..size.max(1))
+        .map(|_| match rng.below(3) {
+            0 => format!("s{}", rng.range(1, 15)),
+            1 => format!("x{}/{}", rng.below(16), rng.below(16)),
+            _ => format!("p{}/{}", program(rng), program(rng)),
+        })
+        .collect::<Vec<_>>();
+
+    moves.join(",") + "\n"
+}
+
+#[test]
+fn test_a() {
+    assert_eq!(solve_a(5, &parse(5, "s1,x3/4,pe/b").unwrap()), "baedc");
+}
+
+#[test]
+fn test_b() {
+    assert_eq!(solve_b(5, &parse(5, "s1,x3/4,pe/b").unwrap(), 2), "ceadb");
+}
+
+#[test]
+fn test_by_permutations() {
+    let moves = parse(5, "s1,x3/4,pe/b").unwrap();
+    assert_eq!(solve_by_permutations(5, &moves, 1), "baedc");
+    assert_eq!(solve_by_permutations(5, &moves, 2), "ceadb");
+
+    let mut rng = Rng::new(16);
+    for _ in 0..10 {
+        let moves = parse(16, &generate(&mut rng, 50)).unwrap();
+        let reps = rng.range(1, 1000) as usize;
+        assert_eq!(solve_by_permutations(16, &moves, reps), solve_b(16, &moves, reps));
+    }
+}
+
+#[test]
+fn test_parse() {
+    let error = parse(5, "s1,x3/4,pe/p").unwrap_err();
+    assert_eq!((error.column, &*error.found, &*error.expected), (12, "p", "a program from a to e"));
+
+    let error = parse(5, "s1,x3/5").unwrap_err();
+    assert_eq!((error.column, &*error.found), (7, "5"));
+    assert_eq!(parse(5, "s6").unwrap_err().expected, "a spin of at most 5");
+}
@@ -43,48 +43,74 @@ In this example, the first two numbers in the list end up being 3 and 4; to chec
 However, you should instead use the standard list size of 256 (with values 0 to 255) and the sequence of lengths in your puzzle input. Once this process is complete, what is the result of multiplying the first two numbers in the list?
 */
 
-use std::io::Read;
-use std::fs::File;
+use aoc_common::generate::Rng;
+use aoc_common::parse::{ParseError, Parser};
+
+/// The input read both ways: as a list of lengths for part one, and as a
+/// string of bytes for part two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lengths {
+    pub numbers: Vec<usize>,
+    pub text: String,
+}
+
+pub fn parse(input: &str) -> Result<Lengths, ParseError> {
+    let p = Parser::new(input);
+    let text = input.trim();
+    let numbers = text.split(',').map(|s| p.number(s)).collect::<Result<_, _>>()?;
 
-fn main() {
-    let mut input = File::open("inputs/ten.txt").unwrap();
-    let mut s = String::new();
+    Ok(Lengths { numbers, text: text.to_owned() })
+}
 
-    input.read_to_string(&mut s).unwrap();
+/// Anything that can reverse a run of the list, to check `reverse` against.
+pub type Reverse = fn(&mut [usize], usize, usize);
 
-    println!("a: {}", solve_a(&s, 256));
-    println!("b: {}", solve_b(&s));
+pub fn solve_a(lengths: &[usize], list_len: usize) -> usize {
+    solve_a_with(lengths, list_len, reverse)
 }
 
-fn solve_a(input: &str, list_len: usize) -> usize {
+pub fn solve_a_with(lengths: &[usize], list_len: usize, reverse: Reverse) -> usize {
     let mut list = (0..list_len).collect::<Vec<_>>();
-    let mut skip = 0;
     let mut idx = 0;
-    let input = input.split(',').map(|s| s.parse().unwrap()).collect::<Vec<usize>>();
 
-    for length in input {
+    for (skip, &length) in lengths.iter().enumerate() {
         reverse(&mut list, idx, length);
 
         idx = (idx + length + skip) % list.len();
-        skip += 1;
     }
 
     list[0] * list[1]
 }
 
-fn reverse(arr: &mut [usize], start: usize, len: usize) {
+/// Reverses the `len` elements from `start`, wrapping around the end.
+pub fn reverse(arr: &mut [usize], start: usize, len: usize) {
     let n = arr.len();
 
     for idx in 0..len / 2 {
         let i = (idx + start) % n;
         let j = (start + len - idx - 1) % n;
 
-        let tmp = arr[i];
-        arr[i] = arr[j];
-        arr[j] = tmp;
+        arr.swap(i, j);
     }
 }
 
+/// `reverse` the long way round: rotating the run to the front, reversing it
+/// there and rotating it back.
+pub fn reverse_by_rotating(arr: &mut [usize], start: usize, len: usize) {
+    let start = start % arr.len();
+
+    arr.rotate_left(start);
+    arr[..len].reverse();
+    arr.rotate_right(start);
+}
+
+/// Lengths of up to 255, `size` of them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let lengths = (0..size.max(1)).map(|_| rng.below(256).to_string()).collect::<Vec<_>>();
+
+    lengths.join(",") + "\n"
+}
+
 /*
 --- Part Two ---
 
@@ -116,13 +142,21 @@ Here are some example hashes:
 Treating your puzzle input as a string of ASCII characters, what is the Knot Hash of your puzzle input? Ignore any leading or trailing whitespace you might encounter.
 */
 
-fn solve_b(input: &str) -> String {
-    let xored = knot_hash(input);
+pub fn solve_b(input: &str) -> String {
+    solve_b_with(input, reverse)
+}
+
+pub fn solve_b_with(input: &str, reverse: Reverse) -> String {
+    let xored = knot_hash_with(input, reverse);
 
-    xored.into_iter().map(|x| format!("{:02x}", x as u8)).collect()
+    xored.into_iter().map(|x| format!("{:02x}", x)).collect()
 }
 
 pub fn knot_hash(input: &str) -> Vec<u8> {
+    knot_hash_with(input, reverse)
+}
+
+pub fn knot_hash_with(input: &str, reverse: Reverse) -> Vec<u8> {
     let mut list = (0..256).collect::<Vec<_>>();
     let mut skip = 0;
     let mut idx = 0;
@@ -138,12 +172,12 @@ pub fn knot_hash(input: &str) -> Vec<u8> {
         }
     }
 
-    list.chunks(16).map(|chunk| chunk.into_iter().fold(0 as u8, |acc, x| acc ^ *x as u8)).collect()
+    list.chunks(16).map(|chunk| chunk.iter().fold(0, |acc, &x| acc ^ x as u8)).collect()
 }
 
 #[test]
 fn test_a() {
-    assert_eq!(solve_a("3,4,1,5", 5), 12);
+    assert_eq!(solve_a(&parse("3,4,1,5").unwrap().numbers, 5), 12);
 }
 
 #[test]
@@ -162,6 +196,20 @@ fn test_reverse() {
     assert_eq!(arr, &[3, 4, 2, 1, 0]);
 }
 
+#[test]
+fn test_reverse_by_rotating() {
+    let mut rng = Rng::new(10);
+
+    for _ in 0..100 {
+        let (start, len) = (rng.below(20), rng.below(9));
+        let (mut swapped, mut rotated) = ((0..8).collect::<Vec<_>>(), (0..8).collect::<Vec<_>>());
+        reverse(&mut swapped, start, len);
+        reverse_by_rotating(&mut rotated, start, len);
+
+        assert_eq!(swapped, rotated, "reversing {} from {}", len, start);
+    }
+}
+
 #[test]
 fn test_b() {
     assert_eq!(solve_b(""), "a2582a3a0e66e6e86e3812dcb672a272");
@@ -164,26 +164,32 @@ The severity of getting caught on a layer is equal to its depth multiplied by it
 Given the details of the firewall you've recorded, if you leave immediately, what is the severity of your whole trip?
 */
 
-use std::io::Read;
-use std::fs::File;
-
-fn main() {
-    let mut input = File::open("inputs/thirteen.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
+use aoc_common::cancel::{Cancelled, Token};
+use aoc_common::generate::Rng;
+use aoc_common::parse::{ParseError, Parser};
+
+/// Parses lines like `4: 4` into each layer's depth and range.
+pub fn parse(input: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    let p = Parser::new(input);
+
+    p.lines(|line| {
+        let mut split = line.split(": ");
+        let depth = p.number(p.token(&mut split, line, "a depth")?)?;
+        let range = p.number(p.token(&mut split, line, "\": \"")?)?;
+        p.end(&mut split)?;
+
+        if range == 0 {
+            return Err(p.error_after(line, "a range of at least 1"));
+        }
 
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+        Ok((depth, range))
+    })
 }
 
-fn solve_a(input: &str) -> usize {
+pub fn solve_a(layers: &[(usize, usize)]) -> usize {
     let mut severity = 0;
 
-    for line in input.lines() {
-        let mut split = line.split(":").map(|s| s.trim().parse::<usize>().unwrap());
-        let depth = split.next().unwrap();
-        let range = split.next().unwrap();
+    for &(depth, range) in layers {
 
         let mut steps = range;
         if range > 2 {
@@ -318,13 +324,10 @@ What is the fewest number of picoseconds that you need to delay the packet to pa
 
 */
 
-fn solve_b(input: &str) -> usize {
+pub fn solve_b(scanners: &[(usize, usize)], token: &Token) -> Result<usize, Cancelled> {
     let mut layers = vec![];
 
-    for line in input.lines() {
-        let mut split = line.split(":").map(|s| s.trim().parse::<usize>().unwrap());
-        let depth = split.next().unwrap();
-        let range = split.next().unwrap();
+    for &(depth, range) in scanners {
         let mut steps = range;
         if range > 2 {
             steps += range - 2;
@@ -334,6 +337,8 @@ fn solve_b(input: &str) -> usize {
 
     let mut delay = 0;
     'check_delay: loop {
+        token.check()?;
+
         for &(depth, steps) in &layers {
             if (depth + delay) % steps == 0 {
                 // println!("Collision at depth {}", depth);
@@ -343,22 +348,65 @@ fn solve_b(input: &str) -> usize {
         }
 
         // No collisions!
-        return delay
+        return Ok(delay)
     }
 }
 
+/// A firewall of `size` layers that some delay of up to 100000 gets
+/// through: each layer's range is picked so that its scanner is away from
+/// the top when a packet delayed that long arrives.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let delay = rng.range(1, 100_000) as usize;
+    let mut depth = 0;
+    let mut layers = String::new();
+
+    for _ in 0..size {
+        let range = loop {
+            let range = rng.range(2, 20) as usize;
+            if !(depth + delay).is_multiple_of(2 * range - 2) {
+                break range;
+            }
+        };
+        layers += &format!("{}: {}\n", depth, range);
+        depth += rng.range(1, 2) as usize;
+    }
+
+    layers
+}
+
 #[test]
 fn test_a() {
-    assert_eq!(solve_a("0: 3
+    assert_eq!(solve_a(&parse("0: 3
 1: 2
 4: 4
-6: 4"), 24);
+6: 4").unwrap()), 24);
 }
 
 #[test]
 fn test_b() {
-    assert_eq!(solve_b("0: 3
+    assert_eq!(solve_b(&parse("0: 3
 1: 2
 4: 4
-6: 4"), 10);
-}
\ No newline at end of file
+6: 4").unwrap(), &Token::none()), Ok(10));
+}
+
+#[test]
+fn test_b_cancelled() {
+    use std::time::Duration;
+
+    // A scanner with a range of 1 never leaves the top, so no delay works.
+    let token = Token::within(Duration::from_millis(10));
+    assert_eq!(solve_b(&parse("0: 1").unwrap(), &token), Err(Cancelled));
+}
+
+#[test]
+fn test_generate() {
+    for seed in 0..20 {
+        let layers = parse(&generate(&mut Rng::new(seed), 30)).unwrap();
+        assert_eq!(layers.len(), 30);
+
+        let delay = solve_b(&layers, &Token::none()).unwrap();
+        assert!(delay <= 100_000);
+        assert!(layers.iter().all(|&(depth, range)| !(depth + delay).is_multiple_of(2 * range - 2)), "seed {}", seed);
+    }
+}
@@ -20,21 +20,16 @@ For example:
 
 How many steps are required to carry the data from the square identified in your puzzle input all the way to the access port?
 */
-use std::io::Read;
-use std::fs::File;
+
 use std::cmp;
 
-fn main() {
-    let mut input = File::open("inputs/three.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::parse::{ParseError, Parser};
 
-    input.read_to_string(&mut s).unwrap();
-    let input = s.parse().unwrap();
-    println!("a: {}", solve_a(input));
-    println!("b: {}", solve_b(input));
+pub fn parse(input: &str) -> Result<u32, ParseError> {
+    Parser::new(input).number(input.trim())
 }
 
-fn solve_a(target: u32) -> u32 {
+pub fn solve_a(target: u32) -> u32 {
     let mut n = (target as f64).sqrt().ceil() as i32;
 
     // always generate an odd sized spiral
@@ -49,7 +44,7 @@ fn solve_a(target: u32) -> u32 {
         ($y:ident, $x:ident) => {{
             if current == target as i32 {
                 let mid = n / 2;
-                println!("Found solution at {:?} mid = {:?}", ($x, $y), (mid, mid));
+                debug!("Found solution at {:?} mid = {:?}", ($x, $y), (mid, mid));
                 let dx = (mid - $x).abs();
                 let dy = (mid - $y).abs();
                 return (dx + dy) as u32;
@@ -87,7 +82,7 @@ fn solve_a(target: u32) -> u32 {
             check!(y, right_column)
         }
 
-        println!("");
+        trace!("finished ring {}", depth);
         depth += 1;
     }
 }
@@ -116,7 +111,7 @@ Once a square is written, its value does not change. Therefore, the first few sq
 What is the first value written that is larger than your puzzle input?
 */
 
-fn solve_b(target: u32) -> u32 {
+pub fn solve_b(target: u32) -> u32 {
     let mut n = (target as f64).sqrt().ceil() as i32;
 
     // always generate an odd sized spiral
@@ -35,32 +35,36 @@ Therefore, a total of 6 programs are in this group; all but program 1, which has
 How many programs are in the group that contains program ID 0?
 */
 
-use std::io::Read;
-use std::fs::File;
 use std::collections::{HashMap, HashSet};
 
-fn main() {
-    let mut input = File::open("inputs/twelve.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::parse::{ParseError, Parser};
 
-    input.read_to_string(&mut s).unwrap();
+/// Parses lines like `2 <-> 0, 3, 4`.
+pub fn parse(input: &str) -> Result<Vec<(usize, Vec<usize>)>, ParseError> {
+    let p = Parser::new(input);
 
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+    p.lines(|line| {
+        let mut split = line.split(" <-> ");
+        let left = p.number(p.token(&mut split, line, "a program")?)?;
+        let right = p.token(&mut split, line, "\" <-> \"")?
+            .split(", ")
+            .map(|s| p.number(s))
+            .collect::<Result<_, _>>()?;
+        p.end(&mut split)?;
+
+        Ok((left, right))
+    })
 }
 
-fn solve_a(input: &str) -> usize {
-    solve(input).0
+pub fn solve_a(pipes: &[(usize, Vec<usize>)]) -> usize {
+    solve(pipes).0
 }
 
-fn solve(input: &str) -> (usize, usize) {
+fn solve(pipes: &[(usize, Vec<usize>)]) -> (usize, usize) {
     let mut maps = HashMap::new();
     let mut sets = vec![];
 
-    for line in input.lines() {
-        // println!("{}", line);
-        let mut split = line.split(" <-> ");
-        let left = split.next().unwrap().parse::<usize>().unwrap();
+    for &(left, ref rights) in pipes {
 
         let left_set = *maps.entry(left).or_insert_with(|| {
             sets.push(HashSet::<usize>::new());
@@ -69,7 +73,7 @@ fn solve(input: &str) -> (usize, usize) {
 
         sets[left_set].insert(left);
 
-        for right in split.next().unwrap().split(", ").map(|s| s.parse::<usize>().unwrap()) {
+        for &right in rights {
             if left == right { continue }
 
             if maps.contains_key(&right) {
@@ -107,28 +111,28 @@ In the example above, there were 2 groups: one consisting of programs 0,2,3,4,5,
 How many groups are there in total?
 */
 
-fn solve_b(input: &str) -> usize {
-    solve(input).1
+pub fn solve_b(pipes: &[(usize, Vec<usize>)]) -> usize {
+    solve(pipes).1
 }
 
 #[test]
 fn test_a() {
-    assert_eq!(solve_a("0 <-> 2
+    assert_eq!(solve_a(&parse("0 <-> 2
 1 <-> 1
 2 <-> 0, 3, 4
 3 <-> 2, 4
 4 <-> 2, 3, 6
 5 <-> 6
-6 <-> 4, 5"), 6);
+6 <-> 4, 5").unwrap()), 6);
 }
 
 #[test]
 fn test_b() {
-    assert_eq!(solve_b("0 <-> 2
+    assert_eq!(solve_b(&parse("0 <-> 2
 1 <-> 1
 2 <-> 0, 3, 4
 3 <-> 2, 4
 4 <-> 2, 3, 6
 5 <-> 6
-6 <-> 4, 5"), 2);
+6 <-> 4, 5").unwrap()), 2);
 }
\ No newline at end of file
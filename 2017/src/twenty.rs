@@ -35,48 +35,43 @@ At this point, particle 1 will never be closer to <0,0,0> than particle 0, and s
 Which particle will stay closest to position <0,0,0> in the long term?
 */
 
-#[macro_use]
-extern crate text_io;
-
-use std::io::Read;
-use std::fs::File;
 use std::collections::HashMap;
 
-fn main() {
-    let mut input = File::open("inputs/twenty.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::generate::Rng;
+use aoc_common::parse::{ParseError, Parser};
 
-    input.read_to_string(&mut s).unwrap();
+type Particle = (i64, i64, i64);
 
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
-}
+/// Parses lines like `p=<3,0,0>, v=<2,0,0>, a=<-1,0,0>`.
+pub fn parse(input: &str) -> Result<Vec<(Particle, Particle, Particle)>, ParseError> {
+    let p = Parser::new(input);
 
-type Particle = (i64, i64, i64);
+    p.lines(|line| {
+        let mut parts = line.split(", ");
+        let mut vector = |name| -> Result<Particle, ParseError> {
+            let part = p.literal(p.token(&mut parts, line, name)?, name)?;
+            let xyz = p.suffix(part, ">")?;
+
+            let mut split = xyz.split(',');
+            let mut next = || p.token(&mut split, xyz, "a coordinate").and_then(|n| p.number(n));
+            let vector = (next()?, next()?, next()?);
+            p.end(&mut split)?;
+
+            Ok(vector)
+        };
+
+        let particle = (vector("p=<")?, vector("v=<")?, vector("a=<")?);
+        p.end(&mut parts)?;
 
-fn parse_particles(input: &str) -> Vec<(Particle, Particle, Particle)> {
-    input.lines().map(|line| {
-        let (px, py, pz): (i64, i64, i64);
-        let (vx, vy, vz): (i64, i64, i64);
-        let (ax, ay, az): (i64, i64, i64);
-        scan!(
-            line.bytes() => "p=<{},{},{}>, v=<{},{},{}>, a=<{},{},{}>",
-            px, py, pz,
-            vx, vy, vz,
-            ax, ay, az
-        );
-
-        ((px, py, pz), (vx, vy, vz), (ax, ay, az))
-    }).collect()
+        Ok(particle)
+    })
 }
 
-fn solve_a(input: &str) -> usize {
-    let mut smallest_magnitude = std::f64::MAX;
+pub fn solve_a(particles: &[(Particle, Particle, Particle)]) -> usize {
+    let mut smallest_magnitude = f64::MAX;
     let mut smallest_idx = 0;
 
-    let particles = parse_particles(input);
-
-    for (idx, particles) in particles.into_iter().enumerate() {
+    for (idx, particles) in particles.iter().enumerate() {
         let (ax, ay, az) = particles.2;
 
         let magnitude = ((ax * ax + ay * ay + az * az) as f64).sqrt();
@@ -121,8 +116,8 @@ In this example, particles 0, 1, and 2 are simultaneously destroyed at the time
 How many particles are left after all collisions are resolved?
 */
 
-fn solve_b(input: &str) -> usize {
-    let mut particles = parse_particles(input);
+pub fn solve_b(particles: &[(Particle, Particle, Particle)]) -> usize {
+    let mut particles = particles.to_vec();
     let mut alive = vec![true; particles.len()];
     let mut iterations_without_death = 0;
 
@@ -157,9 +152,8 @@ fn solve_b(input: &str) -> usize {
             if !alive[idx] { continue }
 
             let position = particles.0;
-            if occupied.contains_key(&position) {
+            if let Some(&other) = occupied.get(&position) {
                 // println!("Killed {} at {:?} - {} remaining", idx, position, alive.iter().filter(|&&x| x).count());
-                let other = occupied[&position];
                 alive[other] = false;
                 alive[idx] = false;
                 iterations_without_death = 0;
@@ -172,13 +166,51 @@ fn solve_b(input: &str) -> usize {
     alive.iter().filter(|&&x| x).count()
 }
 
+/// `size` particles, with some of them sent in twos and threes to meet at
+/// the same place on the same tick within the first 40, so that there's
+/// something to collide.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    fn vector(rng: &mut Rng, low: i64, high: i64) -> Particle {
+        (rng.range(low, high), rng.range(low, high), rng.range(low, high))
+    }
+
+    let mut particles = vec![];
+
+    while particles.len() < size {
+        let left = size - particles.len();
+        if left >= 2 && rng.chance(0.3) {
+            let (px, py, pz) = vector(rng, -1000, 1000);
+            let tick = rng.range(1, 40);
+            let members = if left >= 3 { rng.range(2, 3) } else { 2 };
+
+            for _ in 0..members {
+                let (vx, vy, vz) = vector(rng, -100, 100);
+                let (ax, ay, az) = vector(rng, -10, 10);
+                // Where the particle has to start to be at (px, py, pz) on
+                // that tick.
+                let start = |p, v, a| p - v * tick - a * tick * (tick + 1) / 2;
+                particles.push(((start(px, vx, ax), start(py, vy, ay), start(pz, vz, az)), (vx, vy, vz), (ax, ay, az)));
+            }
+        } else {
+            particles.push((vector(rng, -1500, 1500), vector(rng, -100, 100), vector(rng, -10, 10)));
+        }
+    }
+    rng.shuffle(&mut particles);
+
+    particles.iter()
+        .map(|&((px, py, pz), (vx, vy, vz), (ax, ay, az))| {
+            format!("p=<{},{},{}>, v=<{},{},{}>, a=<{},{},{}>\n", px, py, pz, vx, vy, vz, ax, ay, az)
+        })
+        .collect()
+}
+
 #[test]
 fn test_a() {
     assert_eq!(
-        solve_a(
+        solve_a(&parse(
 "p=<3,0,0>, v=<2,0,0>, a=<-1,0,0>
 p=<4,0,0>, v=<0,0,0>, a=<-2,0,0>"
-        ),
+        ).unwrap()),
         0
     );
 }
@@ -186,12 +218,32 @@ p=<4,0,0>, v=<0,0,0>, a=<-2,0,0>"
 #[test]
 fn test_b() {
     assert_eq!(
-        solve_b(
+        solve_b(&parse(
 "p=<-6,0,0>, v=<3,0,0>, a=<0,0,0>
 p=<-4,0,0>, v=<2,0,0>, a=<0,0,0>
 p=<-2,0,0>, v=<1,0,0>, a=<0,0,0>
 p=<3,0,0>, v=<-1,0,0>, a=<0,0,0>"
-            ),
+            ).unwrap()),
         1
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_generate() {
+    let mut collided = false;
+
+    for seed in 0..3 {
+        let particles = parse(&generate(&mut Rng::new(seed), 30)).unwrap();
+        assert_eq!(particles.len(), 30);
+
+        let magnitude = |&(_, _, (x, y, z)): &(Particle, Particle, Particle)| x * x + y * y + z * z;
+        let closest = solve_a(&particles);
+        assert!(particles.iter().all(|particle| magnitude(particle) >= magnitude(&particles[closest])));
+
+        let left = solve_b(&particles);
+        assert!(left <= particles.len());
+        collided |= left < particles.len();
+    }
+
+    assert!(collided);
+}
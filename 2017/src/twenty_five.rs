@@ -62,79 +62,94 @@ Recreate the Turing machine and save the computer! What is the diagnostic checks
 
 */
 
-#[macro_use]
-extern crate text_io;
-
-use std::io::Read;
-use std::fs::File;
 use std::collections::HashMap;
 
-fn main() {
-    let mut input = File::open("inputs/twenty_five.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
-
-    println!("a: {}", solve_a(&s, 6));
-    println!("b: {}", solve_b(&s));
-}
+use aoc_common::infinite::Tape;
+use aoc_common::parse::{ParseError, Parser};
 
 #[derive(Debug, PartialEq, Eq)]
-struct State {
+pub struct State {
     false_transition: Transition,
     true_transition: Transition,
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Transition {
+pub struct Transition {
     write_value: usize,
     move_left: bool,
     next_state: char,
 }
 
-#[allow(unused_imports)]
-fn parse_blueprint(input: &str, num_states: usize) -> (char, usize, HashMap<char, State>) {
-    let mut input = input.bytes();
-    let starting_state: char = read!("Begin in state {}.\n", &mut input);
-    let diagnostic_steps: usize = read!("Perform a diagnostic checksum after {} steps.\n", &mut input);
-
-    let parse_transition = |mut input: &mut std::str::Bytes| {
-        let current_value: usize = read!("  If the current value is {}:\n", input);
-        let write_value = read!("    - Write the value {}.\n", input);
-        let direction: String = read!("    - Move one slot to the {}.\n", input);
-        let next_state = read!("    - Continue with state {}.\n", input);
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blueprint {
+    starting_state: char,
+    diagnostic_steps: usize,
+    states: HashMap<char, State>,
+}
 
-        let move_left = direction == "left";
+pub fn parse(input: &str) -> Result<Blueprint, ParseError> {
+    let p = Parser::new(input);
+    let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty()).peekable();
 
-        (current_value, Transition { write_value, move_left, next_state })
-    };
+    let starting_state = state_name(&p, line(&p, &mut lines, input, "Begin in state ", ".")?)?;
+    let diagnostic_steps = p.number(line(&p, &mut lines, input, "Perform a diagnostic checksum after ", " steps.")?)?;
 
     let mut states = HashMap::new();
 
-    for _ in 0..num_states {
-        let state_name: char = read!("\nIn state {}:\n", &mut input);
-        let (false_value, false_transition) = parse_transition(&mut input);
-        assert_eq!(false_value, 0);
-
-        let (true_value, true_transition) = parse_transition(&mut input);
-        assert_eq!(true_value, 1);
+    while lines.peek().is_some() {
+        let state_name = state_name(&p, line(&p, &mut lines, input, "In state ", ":")?)?;
+        let false_transition = parse_transition(&p, &mut lines, input, 0)?;
+        let true_transition = parse_transition(&p, &mut lines, input, 1)?;
 
         let state = State { false_transition, true_transition };
         states.insert(state_name, state);
     }
 
-    (starting_state, diagnostic_steps, states)
+    Ok(Blueprint { starting_state, diagnostic_steps, states })
+}
+
+fn parse_transition<'a, I>(p: &Parser<'a>, lines: &mut I, input: &'a str, value: usize) -> Result<Transition, ParseError>
+    where I: Iterator<Item = &'a str>
+{
+    p.keyword(line(p, lines, input, "If the current value is ", ":")?, &value.to_string())?;
+
+    let write_value = p.number(line(p, lines, input, "- Write the value ", ".")?)?;
+    let move_left = match line(p, lines, input, "- Move one slot to the ", ".")? {
+        "left" => true,
+        "right" => false,
+        direction => return Err(p.error(direction, "left or right")),
+    };
+    let next_state = state_name(p, line(p, lines, input, "- Continue with state ", ".")?)?;
+
+    Ok(Transition { write_value, move_left, next_state })
+}
+
+/// Takes the next line, which should be `<prefix><value><suffix>`, e.g.
+/// `Begin in state A.`, and returns the value.
+fn line<'a, I>(p: &Parser<'a>, lines: &mut I, input: &'a str, prefix: &str, suffix: &str) -> Result<&'a str, ParseError>
+    where I: Iterator<Item = &'a str>
+{
+    let line = p.token(lines, input, &format!("{:?}", prefix))?;
+    p.suffix(p.literal(line, prefix)?, suffix)
+}
+
+fn state_name(p: &Parser, name: &str) -> Result<char, ParseError> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_uppercase() => Ok(c),
+        _ => Err(p.error(name, "a state")),
+    }
 }
 
-fn solve_a(input: &str, num_states: usize) -> usize {
-    let (starting_state, diagnostic_steps, states) = parse_blueprint(input, num_states);
+pub fn solve_a(blueprint: &Blueprint) -> usize {
+    let Blueprint { starting_state, diagnostic_steps, ref states } = *blueprint;
 
     let mut x = 0;
-    let mut tape = HashMap::new();
+    let mut tape = Tape::new(false);
     let mut current_state = &states[&starting_state];
 
     for _ in 0..diagnostic_steps {
-        let current_slot = tape.entry(x).or_insert(false);
+        let current_slot = &mut tape[x];
         let transition = if *current_slot {
             &current_state.true_transition
         } else {
@@ -146,7 +161,7 @@ fn solve_a(input: &str, num_states: usize) -> usize {
         current_state = &states[&transition.next_state];
     }
 
-    tape.values().filter(|&&v| v).count()
+    tape.iter().filter(|&&v| v).count()
 }
 
 /*
@@ -180,14 +195,16 @@ To hear about future projects, you can follow me on Twitter.
 I've highlighted the easter eggs in each puzzle, just in case you missed any. Hover your mouse over them, and the easter egg will appear.
 */
 
-fn solve_b(_: &str) -> &'static str {
+/// There's no second puzzle on the last day, just the fiftieth star for
+/// finishing the rest.
+pub fn solve_b(_: &Blueprint) -> &'static str {
     "FINISHED!"
 }
 
 #[test]
 fn test_a() {
     assert_eq!(
-        solve_a(
+        solve_a(&parse(
 "Begin in state A.
 Perform a diagnostic checksum after 6 steps.
 
@@ -210,8 +227,7 @@ In state B:
     - Write the value 1.
     - Move one slot to the right.
     - Continue with state A.
-",
-        2
+").unwrap()
         ),
         3
     );
@@ -0,0 +1,252 @@
+/*
+--- Day 24: Electromagnetic Moat ---
+
+The CPU itself is a large, black building surrounded by a bottomless pit. Enormous metal tubes extend outward from the side of the building at regular intervals and descend down into the void. There's no way to cross, but you need to get inside.
+
+No way, of course, other than building a bridge out of the magnetic components strewn about nearby.
+
+Each component has two ports, one on each end. The ports come in all different types, and only matching types can be connected. You take an inventory of the components by their port types (your puzzle input). Each port is identified by the number of pins it uses; more pins mean a stronger connection for your bridge. A 3/7 component, for example, has a type-3 port on one side, and a type-7 port on the other.
+
+Your side of the pit is metallic; a perfect surface to connect a magnetic, zero-pin port. Because of this, the first port you use must be of type 0. It doesn't matter what type of port you end with; your goal is just to make the bridge as strong as possible.
+
+The strength of a bridge is the sum of the port types in each component. For example, if your bridge is made of components 0/3, 3/7, and 7/4, your bridge has a strength of 0+3 + 3+7 + 7+4 = 24.
+
+For example, suppose you had the following components:
+
+0/2
+2/2
+2/3
+3/4
+3/5
+0/1
+10/1
+9/10
+
+With them, you could make the following valid bridges:
+
+    0/1
+    0/1--10/1
+    0/1--10/1--9/10
+    0/2
+    0/2--2/3
+    0/2--2/3--3/4
+    0/2--2/3--3/5
+    0/2--2/2
+    0/2--2/2--2/3
+    0/2--2/2--2/3--3/4
+    0/2--2/2--2/3--3/5
+
+(Note how, as shown by 10/1, order of ports within a component doesn't matter. However, you may only use each port on a component once.)
+
+Of these bridges, the strongest one is 0/1--10/1--9/10; it has a strength of 0+1 + 1+10 + 10+9 = 31.
+
+What is the strength of the strongest bridge you can make with the components you have available?
+*/
+
+use std::collections::{HashSet, HashMap};
+use std::cmp;
+use std::fmt::{self, Display};
+
+use aoc_common::parse::{ParseError, Parser};
+
+type Components = HashMap<usize, Vec<usize>>;
+
+/// Parses components like `0/2`.
+pub fn parse(input: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    let p = Parser::new(input);
+
+    p.lines(|line| {
+        let mut ports = line.split('/');
+        let a = p.number(p.token(&mut ports, line, "a port")?)?;
+        let b = p.number(p.token(&mut ports, line, "\"/\"")?)?;
+        p.end(&mut ports)?;
+
+        Ok((a, b))
+    })
+}
+
+fn setup(components: &[(usize, usize)]) -> (Components, HashSet<(usize, usize)>) {
+    let mut map = HashMap::new();
+
+    for &(a, b) in components {
+        map.entry(a).or_insert(vec![]).push(b);
+        map.entry(b).or_insert(vec![]).push(a);
+    }
+
+    (map, HashSet::new())
+}
+
+pub fn solve_a(components: &[(usize, usize)]) -> usize {
+    best_key(components, &strongest).0
+}
+
+/*
+-- Part Two ---
+
+The bridge you've built isn't long enough; you can't jump the rest of the way.
+
+In the example above, there are two longest bridges:
+
+    0/2--2/2--2/3--3/4
+    0/2--2/2--2/3--3/5
+
+Of them, the one which uses the 3/5 component is stronger; its strength is 0+2 + 2+2 + 2+3 + 3+5 = 19.
+
+What is the strength of the longest bridge you can make? If you can make multiple bridges of the longest length, pick the strongest one.
+
+*/
+
+pub fn solve_b(components: &[(usize, usize)]) -> usize {
+    best_key(components, &longest).1
+}
+
+/// A bridge as a list of components, each turned so that it joins onto the
+/// one before it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Bridge(pub Vec<(usize, usize)>);
+
+impl Bridge {
+    pub fn strength(&self) -> usize {
+        self.0.iter().map(|&(a, b)| a + b).sum()
+    }
+}
+
+impl Display for Bridge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, &(a, b)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("--")?;
+            }
+            write!(f, "{}/{}", a, b)?;
+        }
+        write!(f, " (strength {}, length {})", self.strength(), self.0.len())
+    }
+}
+
+/// Ranks bridges by strength alone.
+fn strongest(strength: usize, _length: usize) -> (usize, usize) {
+    (strength, 0)
+}
+
+/// Ranks bridges by length, then strength.
+fn longest(strength: usize, length: usize) -> (usize, usize) {
+    (length, strength)
+}
+
+/// The strongest bridge.
+pub fn explain_a(components: &[(usize, usize)]) -> Bridge {
+    best_bridge(components, &strongest)
+}
+
+/// The longest bridge, and of those the strongest.
+pub fn explain_b(components: &[(usize, usize)]) -> Bridge {
+    best_bridge(components, &longest)
+}
+
+type Key = dyn Fn(usize, usize) -> (usize, usize);
+
+/// The highest `key` of any bridge's strength and length.
+fn best_key(components: &[(usize, usize)], key: &Key) -> (usize, usize) {
+    let mut best = (0, 0);
+    walk(components, &mut |strength, length, _| {
+        best = cmp::max(best, key(strength, length));
+        false
+    });
+
+    best
+}
+
+/// The first bridge with the highest `key`, found by walking the bridges a
+/// second time once that key is known.
+fn best_bridge(components: &[(usize, usize)], key: &Key) -> Bridge {
+    let target = best_key(components, key);
+    let mut best = Bridge::default();
+    walk(components, &mut |strength, length, bridge| {
+        let found = key(strength, length) == target;
+        if found {
+            best = bridge.clone();
+        }
+        found
+    });
+
+    best
+}
+
+/// Builds every bridge depth first, handing each one to `visit` along with
+/// its strength and length, until `visit` returns true.
+fn walk(components: &[(usize, usize)], visit: &mut dyn FnMut(usize, usize, &Bridge) -> bool) {
+    fn extend(
+        left: usize,
+        strength: usize,
+        map: &Components,
+        used: &mut HashSet<(usize, usize)>,
+        bridge: &mut Bridge,
+        visit: &mut dyn FnMut(usize, usize, &Bridge) -> bool,
+    ) -> bool {
+        if visit(strength, bridge.0.len(), bridge) {
+            return true;
+        }
+
+        for &right in map.get(&left).unwrap_or(&vec![]) {
+            let pair = (cmp::min(left, right), cmp::max(left, right));
+            if used.contains(&pair) { continue }
+
+            used.insert(pair);
+            bridge.0.push((left, right));
+            let done = extend(right, strength + left + right, map, used, bridge, visit);
+            bridge.0.pop();
+            used.remove(&pair);
+
+            if done {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    let (map, mut used) = setup(components);
+    extend(0, 0, &map, &mut used, &mut Bridge::default(), visit);
+}
+
+#[test]
+fn test_a() {
+    assert_eq!(
+        solve_a(&parse(
+"0/2
+2/2
+2/3
+3/4
+3/5
+0/1
+10/1
+9/10"
+        ).unwrap()),
+        31
+    );
+}
+
+#[test]
+fn test_b() {
+    assert_eq!(
+        solve_b(&parse(
+"0/2
+2/2
+2/3
+3/4
+3/5
+0/1
+10/1
+9/10"
+        ).unwrap()),
+        19
+    );
+}
+
+#[test]
+fn test_explain() {
+    let components = parse("0/2\n2/2\n2/3\n3/4\n3/5\n0/1\n10/1\n9/10").unwrap();
+
+    assert_eq!(explain_a(&components).to_string(), "0/1--1/10--10/9 (strength 31, length 3)");
+    assert_eq!(explain_b(&components).to_string(), "0/2--2/2--2/3--3/5 (strength 19, length 4)");
+}
@@ -89,128 +89,93 @@ How many pixels stay on after 5 iterations?
 
 */
 
-#[macro_use]
-extern crate text_io;
-
-use std::io::Read;
-use std::fs::File;
 use std::collections::HashMap;
-use std::mem;
-
-fn main() {
-    let mut input = File::open("inputs/twenty_one.txt").unwrap();
-    let mut s = String::new();
 
-    input.read_to_string(&mut s).unwrap();
+use aoc_common::grid::Grid;
+use aoc_common::parse::{char_at, ParseError, Parser};
 
-    println!("a: {}", solve(&s, 5));
-    println!("b: {}", solve(&s, 18));
-}
+/// Every orientation of every pattern, mapped to what it enhances into.
+pub type Rules = HashMap<Grid<char>, Grid<char>>;
 
-fn parse_rules(input: &str) -> HashMap<Vec<Vec<char>>, Vec<Vec<char>>> {
+pub fn parse(input: &str) -> Result<Rules, ParseError> {
+    let p = Parser::new(input);
     let mut map = HashMap::new();
 
-    for line in input.lines() {
-        let before: String;
-        let after: String;
-
+    for line in input.lines().filter(|line| !line.is_empty()) {
         // .#./..#/### => #..#/..../..../#..#
-        scan!(line.bytes() => "{} => {}", before, after);
+        let mut split = line.split(" => ");
+        let before = p.token(&mut split, line, "a pattern")?;
+        let after = p.token(&mut split, line, "\" => \"")?;
+        p.end(&mut split)?;
 
-        let mut before = parse_grid(&before);
-        let after = parse_grid(&after);
-        let size = before.len();
+        let mut before = parse_pattern(&p, before, &[2, 3])?;
+        let size = before.width();
+        let after = parse_pattern(&p, after, &[size + 1])?;
 
-        // Rotate clockwise 4 times
+        // Each of the 4 rotations, and each of those flipped
         for _ in 0..4 {
-            let mut rotation: Vec<Vec<_>> = before.clone();
-
-            // Rotations
-            for i in 0..size {
-                // Take top from the left
-                rotation[0][i] = before[i][0];
-            }
-
-            for i in 0..size {
-                // Take left from bottom
-                rotation[size - i - 1][0] = before[size - 1][size - i - 1];
-                // Take bottom from right
-                rotation[size - 1][size - i - 1] = before[i][size - 1];
-                // Take right from top
-                rotation[i][size - 1] = before[0][i];
-            }
-
-            let mut vertical_flip = rotation.clone();
-            let mut horizontal_flip = rotation.clone();
-            // Flips for all rotations (not worried about duplicates)
-            for i in 0..size {
-                // Take top from bottom
-                vertical_flip[0][i] = rotation[size - 1][i];
-                // Take bottom from top
-                vertical_flip[size - 1][i] = rotation[0][i];
-                // Take left from right
-                horizontal_flip[i][0] = rotation[i][size - 1];
-                // Take right from left
-                horizontal_flip[i][size - 1] = rotation[i][0];
-            }
-
-            // Insert into the map
-            map.insert(rotation.clone(), after.clone());
-            map.insert(vertical_flip, after.clone());
-            map.insert(horizontal_flip, after.clone());
-
+            map.insert(before.flipped(), after.clone());
+            let rotation = before.rotated();
+            map.insert(before, after.clone());
             before = rotation;
         }
     }
 
-    map
+    Ok(map)
 }
 
-fn parse_grid(raw: &str) -> Vec<Vec<char>> {
-    raw.split('/').map(|row| row.chars().collect()).collect()
+/// Parses a square pattern like `.#./..#/###`, as long as it's one of
+/// `sizes` across.
+fn parse_pattern(p: &Parser, raw: &str, sizes: &[usize]) -> Result<Grid<char>, ParseError> {
+    let rows = raw.split('/').map(|row| row.chars().collect::<Vec<_>>()).collect::<Vec<_>>();
+    let size = rows.len();
+    let expected = || {
+        let sizes = sizes.iter().map(|n| format!("{0}x{0}", n)).collect::<Vec<_>>();
+        format!("a {} pattern", sizes.join(" or "))
+    };
+
+    if !sizes.contains(&size) || rows.iter().any(|row| row.len() != size) {
+        return Err(p.error(raw, expected()));
+    }
+
+    if let Some(i) = raw.find(|c| c != '.' && c != '#' && c != '/') {
+        return Err(p.error(char_at(raw, i), "\".\" or \"#\""));
+    }
+
+    Ok(Grid::from_rows(rows, '.'))
 }
 
-fn solve(input: &str, iterations: usize) -> usize {
-    let rules = parse_rules(input);
-    let mut grid = parse_grid(".#./..#/###");
+pub fn solve(rules: &Rules, iterations: usize) -> usize {
+    let start = [".#.", "..#", "###"].iter().map(|row| row.chars().collect()).collect();
+    let mut grid = Grid::from_rows(start, '.');
 
     for _ in 0..iterations {
-        let size = grid.len();
+        let size = grid.width();
         // Important: Try rule 2 before rule 3.
-        let split_size = if size % 2 == 0 { 2 } else { 3 };
+        let split_size = if size.is_multiple_of(2) { 2 } else { 3 };
         let split_count = size / split_size;
 
         let new_size = split_count * (split_size + 1);
-        let old_grid = mem::replace(&mut grid, vec![vec!['x'; new_size]; new_size]);
+        let mut enhanced = Grid::new(new_size, new_size, 'x');
 
         for sq_y in 0..split_count {
             for sq_x in 0..split_count {
-
-                let mut current_block = vec![vec!['x'; split_size]; split_size];
-                for y in 0..split_size {
-                    for x in 0..split_size {
-                        let real_y = (sq_y * split_size) + y;
-                        let real_x = (sq_x * split_size) + x;
-
-                        current_block[y][x] = old_grid[real_y][real_x];
-                    }
-                }
+                let current_block = grid.window((sq_x * split_size, sq_y * split_size), split_size, split_size);
 
                 let new_block = &rules[&current_block];
-                for y in 0..split_size + 1 {
-                    for x in 0..split_size + 1 {
-                        let real_y = (sq_y * (split_size + 1)) + y;
-                        let real_x = (sq_x * (split_size + 1)) + x;
+                for (x, y) in new_block.points() {
+                    let real_y = (sq_y * (split_size + 1)) + y;
+                    let real_x = (sq_x * (split_size + 1)) + x;
 
-                        grid[real_y][real_x] = new_block[y][x];
-                    }
+                    enhanced[(real_x, real_y)] = new_block[(x, y)];
                 }
             }
         }
+
+        grid = enhanced;
     }
 
     grid.iter()
-        .flat_map(|row| row.iter())
         .filter(|&&c| c == '#')
         .count()
 }
@@ -224,9 +189,9 @@ How many pixels stay on after 18 iterations?
 #[test]
 fn test_a() {
     assert_eq!(
-        solve(
+        solve(&parse(
 "../.# => ##./#../...
-.#./..#/### => #..#/..../..../#..#",
+.#./..#/### => #..#/..../..../#..#").unwrap(),
         2),
         12
     );
@@ -0,0 +1,340 @@
+/*
+--- Day 23: Coprocessor Conflagration ---
+
+You decide to head directly to the CPU and fix the printer from there. As you get close, you find an experimental coprocessor doing so much work that the local programs are afraid it will halt and catch fire. This would cause serious issues for the rest of the computer, so you head in and see what you can do.
+
+The code it's running seems to be a variant of the kind you saw recently on that tablet. The general functionality seems very similar, but some of the instructions are different:
+
+    set X Y sets register X to the value of Y.
+    sub X Y decreases register X by the value of Y.
+    mul X Y sets register X to the result of multiplying the value contained in register X by the value of Y.
+    jnz X Y jumps with an offset of the value of Y, but only if the value of X is not zero. (An offset of 2 skips the next instruction, an offset of -1 jumps to the previous instruction, and so on.)
+
+    Only the instructions listed above are used. The eight registers here, named a through h, all start at 0.
+
+The coprocessor is currently set to some kind of debug mode, which allows for testing, but prevents it from doing any meaningful work.
+
+If you run the program (your puzzle input), how many times is the mul instruction invoked?
+*/
+
+use std::fmt::{self, Display};
+
+use aoc_common::Failure;
+use aoc_common::cancel::{Cancelled, Token};
+use aoc_common::generate::Rng;
+use aoc_common::parse::ParseError;
+use aoc_common::vm::{self, Assembler, Flow, Operand, Program, State};
+
+use self::Instruction::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Set(usize, Operand),
+    Sub(usize, Operand),
+    Mul(usize, Operand),
+    Jnz(Operand, Operand),
+}
+
+impl vm::Instruction for Instruction {
+    fn execute(self, state: &mut State) -> Flow {
+        match self {
+            // set X Y sets register X to the value of Y.
+            Set(x, y) => state.registers[x] = state.get(y),
+            // sub X Y decreases register X by the value of Y.
+            Sub(x, y) => state.registers[x] -= state.get(y),
+            // mul X Y sets register X to the result of multiplying the value contained in register X by the value of Y.
+            Mul(x, y) => state.registers[x] *= state.get(y),
+            // jnz X Y jumps with an offset of the value of Y, but only if the value of X is not zero. (An offset of 2 skips the next instruction, an offset of -1 jumps to the previous instruction, and so on.)
+            Jnz(x, y) => if state.get(x) != 0 {
+                return Flow::Jump(state.get(y))
+            },
+        }
+
+        Flow::Next
+    }
+}
+
+pub fn parse(input: &str) -> Result<Program<Instruction>, ParseError> {
+    Assembler::letters(input, 8).assemble(|line| {
+        let op = line.word("an instruction")?;
+
+        Ok(match op {
+            "set" => Set(line.register()?, line.operand()?),
+            "sub" => Sub(line.register()?, line.operand()?),
+            "mul" => Mul(line.register()?, line.operand()?),
+            "jnz" => Jnz(line.operand()?, line.operand()?),
+            _ => return Err(line.error(op, "set, sub, mul or jnz")),
+        })
+    })
+}
+
+pub fn solve(program: &Program<Instruction>) -> usize {
+    run(program, 0, &Token::none()).expect("cancelled without a budget").1
+}
+
+/// Runs the program with register `a` starting at `a`, returning the
+/// registers it finishes with and how many multiplies it did.
+pub fn run(program: &Program<Instruction>, a: i64, token: &Token) -> Result<(Vec<i64>, usize), Cancelled> {
+    let mut machine = program.machine();
+    machine.state.registers[0] = a;
+    let mut multiplies = 0;
+
+    while let Some(&instruction) = machine.current() {
+        token.check()?;
+
+        if let Mul(..) = instruction {
+            multiplies += 1;
+        }
+        machine.step();
+    }
+
+    Ok((machine.state.registers, multiplies))
+}
+
+/// The numbers that make one input's program differ from another's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Constants {
+    /// What `b` starts at.
+    pub seed: i64,
+    /// With `a` set, `b` is then scaled and offset...
+    pub scale: i64,
+    pub offset: i64,
+    /// ...and `h` counts the composite numbers from `b` to `b + span`, going
+    /// up in steps of `step`.
+    pub span: i64,
+    pub step: i64,
+}
+
+/// A program that isn't shaped like the one in the puzzle, so that only
+/// running it can say what it does: the first instruction that's out of
+/// place, counting from 0, and what was expected there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsupported {
+    pub instruction: usize,
+    pub expected: &'static str,
+}
+
+impl Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unsupported shape, expected {} at instruction {}", self.expected, self.instruction)
+    }
+}
+
+impl From<Unsupported> for Failure {
+    fn from(unsupported: Unsupported) -> Failure {
+        Failure::no_answer(unsupported.to_string())
+    }
+}
+
+impl Constants {
+    /// Picks the constants out of the program, which has to be shaped like
+    /// the one in the puzzle.
+    pub fn find(program: &[Instruction]) -> Result<Constants, Unsupported> {
+        use self::Operand::Value;
+
+        let unsupported = |instruction, expected| Err(Unsupported { instruction, expected });
+
+        if program.len() < 32 {
+            return unsupported(program.len(), "32 instructions");
+        } else if program.len() > 32 {
+            return unsupported(32, "the end of the program");
+        }
+
+        let (b, c) = (1, 2);
+        let seed = match program[0] { Set(x, Value(v)) if x == b => v, _ => return unsupported(0, "set b to a number") };
+        let scale = match program[4] { Mul(x, Value(v)) if x == b => v, _ => return unsupported(4, "mul b by a number") };
+        let offset = match program[5] { Sub(x, Value(v)) if x == b => -v, _ => return unsupported(5, "sub a number from b") };
+        let span = match program[7] { Sub(x, Value(v)) if x == c => -v, _ => return unsupported(7, "sub a number from c") };
+        let step = match program[30] { Sub(x, Value(v)) if x == b => -v, _ => return unsupported(30, "sub a number from b") };
+
+        // The inner loops only stop when they count up to b, from 2.
+        if seed < 3 {
+            return unsupported(0, "b to start above 2");
+        }
+        // And the outer one only when b lands exactly on c.
+        let lands = if step == 0 { span == 0 } else { span % step == 0 && span / step >= 0 };
+        if !lands {
+            return unsupported(30, "a step that goes from b to c evenly");
+        }
+
+        Ok(Constants { seed, scale, offset, span, step })
+    }
+}
+
+/// How many multiplies the program does with `a` left at 0: the inner loops
+/// run once each for every `d` and `e` from 2 up to `b`.
+pub fn counted(constants: &Constants) -> usize {
+    let b = constants.seed as usize;
+
+    (b - 2) * (b - 2)
+}
+
+/// A program like the puzzle's, with small enough constants that running it
+/// with `a` set finishes, checking `size` numbers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let step = rng.range(1, 10);
+
+    format!("set b {seed}
+set c b
+jnz a 2
+jnz 1 5
+mul b {scale}
+sub b -{offset}
+set c b
+sub c -{span}
+set f 1
+set d 2
+set e 2
+set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8
+sub d -1
+set g d
+sub g b
+jnz g -13
+jnz f 2
+sub h -1
+set g b
+sub g c
+jnz g 2
+jnz 1 3
+sub b -{step}
+jnz 1 -23
+", seed = rng.range(3, 20), scale = rng.range(1, 3), offset = rng.range(0, 50),
+       span = step * (size.max(1) as i64 - 1), step = step)
+}
+
+/*
+    --- Part Two ---
+
+Now, it's time to fix the problem.
+
+The debug mode switch is wired directly to register a. You flip the switch, which makes register a now start at 1 when the program is executed.
+
+Immediately, the coprocessor begins to overheat. Whoever wrote this program obviously didn't choose a very efficient implementation. You'll need to optimize the program if it has any hope of completing before Santa needs that printer working.
+
+The coprocessor's ultimate goal is to determine the final value left in register h once the program completes. Technically, if it had that... it wouldn't even need to run the program.
+
+After setting register a to 1, if the program were to run to completion, what value would be left in register h?
+
+*/
+
+/// Part two, running the program with `a` set to 1, which is far too slow to
+/// finish.
+pub fn interpreted(program: &Program<Instruction>, token: &Token) -> Result<i64, Cancelled> {
+    Ok(run(program, 1, token)?.0[7])
+}
+
+/// Part two, worked out from the constants of a program shaped like the
+/// puzzle's, as running it is far too slow.
+pub fn solve_b(program: &Program<Instruction>) -> Result<i64, Unsupported> {
+    Ok(optimized(&Constants::find(program)?))
+}
+
+/// The program hand translated into Rust, which is far too slow to finish.
+pub fn translated(constants: &Constants, token: &Token) -> Result<i64, Cancelled> {
+    let mut b = constants.seed;
+    let mut f;
+    let mut g;
+    let mut h = 0;
+
+    b *= constants.scale;
+    b -= -constants.offset;
+    let c = b - -constants.span;
+    loop {
+        f = 1;
+        for d in 2..b+1 {
+            for e in 2..b+1 {
+                token.check()?;
+                g = d;
+                g *= e;
+                g -= b;
+                if g == 0 {
+                    f = 0;
+                }
+            }
+        }
+        if f == 0 {
+            h -= -1;
+        }
+        g = b;
+        g -= c;
+        if g == 0 {
+            return Ok(h);
+        }
+        trace!("{:?}", (b, c, f, g, h));
+        b -= -constants.step;
+    }
+}
+
+pub fn optimized(constants: &Constants) -> i64 {
+    let mut h = 0;
+
+    let mut b = constants.seed * constants.scale + constants.offset;
+    let c = b + constants.span;
+
+    loop {
+        // Check if prime!
+        let f = (2..b+1).any(|d| {
+            let x = b / d;
+            b % x == 0 && x < b && x > 1
+        });
+
+        if f {
+            h += 1;
+        }
+
+        if b == c {
+            return h
+        }
+        b += constants.step;
+    }
+}
+
+#[test]
+fn test_generate() {
+    for seed in 0..10 {
+        let program = parse(&generate(&mut Rng::new(seed), 10)).unwrap();
+        let constants = Constants::find(&program).unwrap();
+
+        assert_eq!(solve(&program), counted(&constants));
+        assert_eq!(interpreted(&program, &Token::none()), Ok(optimized(&constants)));
+        assert_eq!(translated(&constants, &Token::none()), Ok(optimized(&constants)));
+    }
+}
+
+#[test]
+fn test_shape() {
+    let program = generate(&mut Rng::new(0), 10);
+    let error = |program: &str| solve_b(&parse(program).unwrap()).unwrap_err();
+    let patched = |patches: &[(usize, &str)]| {
+        let mut lines = program.lines().collect::<Vec<_>>();
+        for &(i, instruction) in patches {
+            lines[i] = instruction;
+        }
+        error(&lines.join("\n"))
+    };
+    let at = |instruction, expected| Unsupported { instruction, expected };
+
+    assert_eq!(patched(&[(7, "sub c b")]), at(7, "sub a number from c"));
+    assert_eq!(patched(&[(0, "set b 2")]), at(0, "b to start above 2"));
+    assert_eq!(patched(&[(7, "sub c -7"), (30, "sub b -2")]), at(30, "a step that goes from b to c evenly"));
+    assert_eq!(patched(&[(7, "sub c 6"), (30, "sub b -2")]), at(30, "a step that goes from b to c evenly"));
+    assert_eq!(error(program.trim_end().rsplit_once('\n').unwrap().0), at(31, "32 instructions"));
+    assert_eq!(error(&(program.clone() + "jnz 1 1\n")), at(32, "the end of the program"));
+}
+
+#[test]
+fn test_any_program() {
+    let program = parse("set a 3\nmul a a\nsub a 1\nmul b a\njnz a -2").unwrap();
+
+    assert_eq!(solve(&program), 10);
+    assert_eq!(interpreted(&program, &Token::none()), Ok(0));
+    assert_eq!(solve_b(&program), Err(Unsupported { instruction: 5, expected: "32 instructions" }));
+}
@@ -96,72 +96,47 @@ After a total of 10000 bursts of activity, 5587 bursts will have caused an infec
 Given your actual map, after 10000 bursts of activity, how many bursts cause a node to become infected? (Do not count nodes that begin infected.)
 */
 
-use std::io::Read;
-use std::fs::File;
-use std::collections::HashMap;
-
-const INFECTED: usize = 2;
-
-fn main() {
-    let mut input = File::open("inputs/twenty_two.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
-
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+use aoc_common::grid::Grid;
+use aoc_common::heading::Heading;
+use aoc_common::infinite::InfiniteGrid;
+use aoc_common::parse::ParseError;
+
+const INFECTED: u8 = 2;
+
+/// The infected nodes, centred on the middle of the map.
+pub fn parse(input: &str) -> Result<InfiniteGrid<u8>, ParseError> {
+    let map = Grid::parse(input, "\".\" or \"#\"", |c| match c {
+        '#' => Some(INFECTED),
+        '.' => Some(0),
+        _ => None,
+    })?;
+    let start = (-(map.width() as isize / 2), -(map.height() as isize / 2));
+
+    Ok(InfiniteGrid::from_grid(map, start, 0))
 }
 
-fn parse_input(input: &str) -> HashMap<(isize, isize), usize> {
-    let mut map = HashMap::new();
-
-    for (y, line) in input.lines().enumerate() {
-        let width = line.len() as isize;
-        let offset = width / 2;
-
-        for (x, c) in line.chars().enumerate() {
-            let coord = (x as isize - offset, y as isize - offset);
-            let infected = if c == '#' { INFECTED } else { 0 };
-            map.insert(coord, infected);
-        }
-    }
-
-    map
-}
-
-fn solve_a(input: &str) -> usize {
-    let mut grid = parse_input(input);
-    let mut x = 0;
-    let mut y = 0;
-    let mut direction = 0;
+pub fn solve_a(grid: &InfiniteGrid<u8>) -> usize {
+    let mut grid = grid.clone();
+    let mut position = (0, 0);
+    let mut heading = Heading::North;
     let mut infections = 0;
 
     for _ in 0..10_000 {
-        let current_node = grid.entry((x, y)).or_insert(0);
+        let current_node = &mut grid[position];
 
         // If the current node is infected, it turns to its right. Otherwise, it turns to its left. (Turning is done in-place; the current node does not change.)
         // If the current node is clean, it becomes infected. Otherwise, it becomes cleaned. (This is done after the node is considered for the purposes of changing direction.)
         if *current_node == INFECTED {
-            direction = (direction + 1) % 4;
+            heading = heading.right();
             *current_node = 0;
         } else {
-            direction = (direction - 1 + 4) % 4;
+            heading = heading.left();
             infections += 1;
             *current_node = INFECTED;
         }
 
         // The virus carrier moves forward one node in the direction it is facing.
-        match direction {
-            // up
-            0 => y -= 1,
-            // right
-            1 => x += 1,
-            // down
-            2 => y += 1,
-            // left
-            3 => x -= 1,
-            _ => unreachable!()
-        }
+        position = heading.advance(position, 1);
     }
 
     infections
@@ -264,26 +239,25 @@ Of the first 100 bursts, 26 will result in infection. Unfortunately, another fea
 Given your actual map, after 10000000 bursts of activity, how many bursts cause a node to become infected? (Do not count nodes that begin infected.)
 */
 
-fn solve_b(input: &str) -> usize {
-    let mut grid = parse_input(input);
-    let mut x = 0;
-    let mut y = 0;
-    let mut direction = 0;
+pub fn solve_b(grid: &InfiniteGrid<u8>) -> usize {
+    let mut grid = grid.clone();
+    let mut position = (0, 0);
+    let mut heading = Heading::North;
     let mut infections = 0;
 
     for _ in 0..10_000_000 {
-        let current_node = grid.entry((x, y)).or_insert(0);
+        let current_node = &mut grid[position];
 
         // Decide which way to turn based on the current node:
         match *current_node {
             //     If it is clean, it turns left.
-            0 => direction = (direction - 1 + 4) % 4,
+            0 => heading = heading.left(),
             //     If it is weakened, it does not turn, and will continue moving in the same direction.
             1 => infections += 1,
             //     If it is infected, it turns right.
-            2 => direction = (direction + 1) % 4,
+            2 => heading = heading.right(),
             //     If it is flagged, it reverses direction, and will go back the way it came.
-            3 => direction = (direction + 2) % 4,
+            3 => heading = heading.reverse(),
             _ => unreachable!(),
         }
 
@@ -295,17 +269,7 @@ fn solve_b(input: &str) -> usize {
         *current_node = (*current_node + 1) % 4;
 
         // The virus carrier moves forward one node in the direction it is facing.
-        match direction {
-            // up
-            0 => y -= 1,
-            // right
-            1 => x += 1,
-            // down
-            2 => y += 1,
-            // left
-            3 => x -= 1,
-            _ => unreachable!()
-        }
+        position = heading.advance(position, 1);
     }
 
     infections
@@ -314,10 +278,10 @@ fn solve_b(input: &str) -> usize {
 #[test]
 fn test_a() {
     assert_eq!(
-        solve_a(
+        solve_a(&parse(
 "..#
 #..
-..."),
+...").unwrap()),
         5587
     );
 }
@@ -325,10 +289,10 @@ fn test_a() {
 #[test]
 fn test_b() {
     assert_eq!(
-        solve_b(
+        solve_b(&parse(
 "..#
 #..
-..."),
+...").unwrap()),
         2511944
     );
 }
\ No newline at end of file
@@ -20,27 +20,22 @@ In this example, the spreadsheet's checksum would be 8 + 4 + 6 = 18.
 What is the checksum for the spreadsheet in your puzzle input?
 */
 
-use std::io::Read;
-use std::fs::File;
 use std::cmp;
 
-fn main() {
-    let mut input = File::open("inputs/two.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::parse::{ParseError, Parser};
 
-    input.read_to_string(&mut s).unwrap();
+pub fn parse(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let p = Parser::new(input);
 
-    println!("a: {}", solve_a(&s));
-    println!("a: {}", solve_b(&s));
+    p.lines(|line| line.split_whitespace().map(|s| p.number(s)).collect())
 }
 
-fn solve_a(input: &str) -> u32 {
-    input.lines().fold(0, |checksum, line| {
-        let numbers = line.split(&['\t', ' '][..]).map(|s| s.parse::<u32>().unwrap());
-        let mut min = std::u32::MAX;
-        let mut max = std::u32::MIN;
+pub fn solve_a(rows: &[Vec<u32>]) -> u32 {
+    rows.iter().fold(0, |checksum, numbers| {
+        let mut min = u32::MAX;
+        let mut max = u32::MIN;
 
-        for number in numbers {
+        for &number in numbers {
             min = cmp::min(min, number);
             max = cmp::max(max, number);
         }
@@ -72,10 +67,9 @@ In this example, the sum of the results would be 4 + 3 + 2 = 9.
 
 What is the sum of each row's result in your puzzle input?
 */
-fn solve_b(input: &str) -> u32 {
-    input.lines().fold(0, |checksum, line| {
-        let numbers = line.split(&['\t', ' '][..]).map(|s| s.parse::<u32>().unwrap()).collect::<Vec<_>>();
 
+pub fn solve_b(rows: &[Vec<u32>]) -> u32 {
+    rows.iter().fold(0, |checksum, numbers| {
         for (i, &a) in numbers.iter().enumerate() {
             for &b in &numbers[i + 1..] {
                 if a % b == 0 {
@@ -96,7 +90,7 @@ fn test_a() {
 7 5 3
 2 4 6 8";
 
-    assert_eq!(solve_a(input), 18);
+    assert_eq!(solve_a(&parse(input).unwrap()), 18);
 }
 
 #[test]
@@ -105,5 +99,5 @@ fn test_b() {
 9 4 7 3
 3 8 6 5";
 
-    assert_eq!(solve_b(input), 9);
+    assert_eq!(solve_b(&parse(input).unwrap()), 9);
 }
\ No newline at end of file
@@ -0,0 +1,307 @@
+/*
+--- Day 8: Handheld Halting ---
+
+Your flight to the major airline hub reaches cruising altitude without incident. While you consider checking the in-flight menu for one of those drinks that come with a little umbrella, you are interrupted by the kid sitting next to you.
+
+Their handheld game console won't turn on! They ask if you can take a look.
+
+You narrow the problem down to a strange infinite loop in the boot code (your puzzle input) of the device. You should be able to fix it, but first you need to be able to run the code in isolation.
+
+The boot code is represented as a text file with one instruction per line of text. Each instruction consists of an operation (acc, jmp, or nop) and an argument (a signed number like +4 or -20).
+
+    acc increases or decreases a single global value called the accumulator by the value given in the argument. For example, acc +7 would increase the accumulator by 7. The accumulator starts at 0. After an acc instruction, the instruction immediately below it is executed next.
+    jmp jumps to a new instruction relative to itself. The next instruction to execute is found using the argument as an offset from the jmp instruction; for example, jmp +2 would skip the next instruction, jmp +1 would continue to the instruction immediately below it, and jmp -20 would cause the instruction 20 lines above to be executed next.
+    nop stands for No OPeration - it does nothing. The instruction immediately below it is executed next.
+
+For example, consider the following program:
+
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+
+These instructions are visited in this order:
+
+nop +0  | 1
+acc +1  | 2, 8(!)
+jmp +4  | 3
+acc +3  | 6
+jmp -3  | 7
+acc -99 |
+acc +1  | 4
+jmp -4  | 5
+acc +6  |
+
+First, the nop +0 does nothing. Then, the accumulator is increased from 0 to 1 (acc +1) and jmp +4 sets the next instruction to the other acc +1 near the bottom. After it increases the accumulator from 1 to 2, jmp -4 executes, setting the next instruction to the only acc +3. It sets the accumulator to 5, and jmp -3 causes the program to continue back at the first acc +1.
+
+This is an infinite loop: with this sequence of jumps, the program will run forever. The moment the program tries to run any instruction a second time, you know it will never terminate.
+
+Immediately before the program would run an instruction a second time, the value in the accumulator is 5.
+
+Run your copy of the boot code. Immediately before any instruction is executed a second time, what value is in the accumulator?
+*/
+
+use std::fmt::{self, Display};
+
+use aoc_common::Failure;
+use aoc_common::generate::Rng;
+use aoc_common::parse::ParseError;
+use aoc_common::vm::{self, Assembler, Flow, Machine, Program, State, Stop};
+
+use self::Instruction::*;
+
+/// The accumulator, the only register.
+const ACC: usize = 0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Acc(i64),
+    Jump(i64),
+    Noop(i64),
+}
+
+impl vm::Instruction for Instruction {
+    fn execute(self, state: &mut State) -> Flow {
+        match self {
+            Acc(change) => state.registers[ACC] += change,
+            Jump(offset) => return Flow::Jump(offset),
+            Noop(_) => {},
+        }
+
+        Flow::Next
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Acc(n) => write!(f, "acc {:+}", n),
+            Jump(n) => write!(f, "jmp {:+}", n),
+            Noop(n) => write!(f, "nop {:+}", n),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Program<Instruction>, ParseError> {
+    Assembler::new(input, &["acc"]).assemble(|line| {
+        let op = line.word("an operation")?;
+
+        Ok(match op {
+            "acc" => Acc(line.number()?),
+            "jmp" => Jump(line.number()?),
+            "nop" => Noop(line.number()?),
+            _ => return Err(line.error(op, "\"acc\", \"jmp\" or \"nop\"")),
+        })
+    })
+}
+
+/// Whether the program terminates rather than looping, and the accumulator
+/// when it stops.
+fn run_machine(instructions: &[Instruction]) -> (bool, i64) {
+    let mut machine = Machine::new(instructions, 1);
+    let stop = machine.run_until_loop();
+
+    (stop == Stop::Exited, machine.state.registers[ACC])
+}
+
+pub fn solve_a(instructions: &[Instruction]) -> i64 {
+    let (_, result) = run_machine(instructions);
+    result
+}
+
+/*
+--- Part Two ---
+
+After some careful analysis, you believe that exactly one instruction is corrupted.
+
+Somewhere in the program, either a jmp is supposed to be a nop, or a nop is supposed to be a jmp. (No acc instructions were harmed in the corruption of this boot code.)
+
+The program is supposed to terminate by attempting to execute an instruction immediately after the last instruction in the file. By changing exactly one jmp or nop, you can repair the boot code and make it terminate correctly.
+
+For example, consider the same program from above:
+
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+
+If you change the first instruction from nop +0 to jmp +0, it would create a single-instruction infinite loop, never leaving that instruction. If you change almost any of the jmp instructions, the program will still eventually find another jmp instruction and loop forever.
+
+However, if you change the second-to-last instruction (from jmp -4 to nop -4), the program terminates! The instructions are visited in this order:
+
+nop +0  | 1
+acc +1  | 2
+jmp +4  | 3
+acc +3  |
+jmp -3  |
+acc -99 |
+acc +1  | 4
+nop -4  | 5
+acc +6  | 6
+
+After the last instruction (acc +6), the program terminates by attempting to run the instruction below the last instruction in the file. With this change, after the program terminates, the accumulator contains the value 8 (acc +1, acc +1, acc +6).
+
+Fix the program so that it terminates normally by changing exactly one jmp (to nop) or nop (to jmp). What is the value of the accumulator after the program terminates?
+*/
+
+/// The one instruction that, swapped between `jmp` and `nop`, lets the
+/// program terminate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    pub index: usize,
+    pub from: Instruction,
+    pub to: Instruction,
+    /// The accumulator once the patched program terminates.
+    pub acc: i64,
+}
+
+impl Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "changing instruction {} from {} to {} terminates with the accumulator at {}",
+               self.index, self.from, self.to, self.acc)
+    }
+}
+
+pub fn solve_b(instructions: &[Instruction]) -> Result<i64, Failure> {
+    explain_b(instructions)
+        .map(|patch| patch.acc)
+        .ok_or_else(|| Failure::no_answer("no single jmp or nop swapped lets the program terminate"))
+}
+
+/// The first patch that lets the program terminate, if there is one.
+pub fn explain_b(instructions: &[Instruction]) -> Option<Patch> {
+    let mut instructions = instructions.to_vec();
+
+    for index in 0..instructions.len() {
+        match instructions[index] {
+            Noop(offset) => {
+                instructions[index] = Jump(offset);
+                let (terminated, acc) = run_machine(&instructions);
+                if terminated { return Some(Patch { index, from: Noop(offset), to: Jump(offset), acc }) }
+                instructions[index] = Noop(offset);
+            },
+            Jump(offset) => {
+                instructions[index] = Noop(offset);
+                let (terminated, acc) = run_machine(&instructions);
+                if terminated { return Some(Patch { index, from: Jump(offset), to: Noop(offset), acc }) }
+                instructions[index] = Jump(offset);
+            }
+            Acc(_) => continue,
+        }
+    }
+
+    None
+}
+
+/// A program of `size` instructions with exactly one corrupted `jmp` or
+/// `nop`.
+///
+/// Instructions before the corruption only ever jump forwards, so running
+/// the program always reaches it. It's either a `jmp` back into them, which
+/// should have fallen through to the code that runs off the end, or a `nop`
+/// falling through to a `jmp` back, which should have jumped to that code.
+/// Swapping any other instruction before it still leads back to it, and the
+/// instructions after it are never run until it's fixed.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(4) as i64;
+    let jump_back = rng.chance(0.5);
+    let corrupted = rng.range(1, size - if jump_back { 1 } else { 3 });
+    // Where the fixed program carries on, running straight off the end.
+    let exit = if jump_back { corrupted + 1 } else { corrupted + 3 };
+    let mut program = vec![];
+
+    for index in 0..corrupted {
+        program.push(match rng.below(3) {
+            0 => Acc(rng.range(-50, 50)),
+            1 => Jump(rng.range(1, corrupted - index)),
+            _ => Noop(rng.range(-index, corrupted - index)),
+        });
+    }
+
+    if jump_back {
+        program.push(Jump(rng.range(-corrupted, 0)));
+    } else {
+        program.push(Noop(rng.range(exit, size) - corrupted));
+        program.push(Jump(rng.range(-corrupted - 1, -1)));
+        // Only reached by wrongly patching the jump before it.
+        program.push(Jump(0));
+    }
+
+    for index in exit..size {
+        program.push(match rng.below(3) {
+            0 => Acc(rng.range(-50, 50)),
+            1 => Jump(rng.range(1, size - index)),
+            _ => Noop(rng.range(-index, size - index)),
+        });
+    }
+
+    program.iter().map(|instruction| format!("{}\n", instruction)).collect()
+}
+
+#[test]
+fn smoke_a() {
+    assert_eq!(solve_a(&parse("nop +0
+                        acc +1
+                        jmp +4
+                        acc +3
+                        jmp -3
+                        acc -99
+                        acc +1
+                        jmp -4
+                        acc +6").unwrap()), 5);
+}
+
+#[test]
+fn smoke_b() {
+    assert_eq!(solve_b(&parse("nop +0
+                        acc +1
+                        jmp +4
+                        acc +3
+                        jmp -3
+                        acc -99
+                        acc +1
+                        jmp -4
+                        acc +6").unwrap()), Ok(8));
+}
+
+#[test]
+fn test_explain_b() {
+    let patch = explain_b(&parse("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6").unwrap());
+
+    assert_eq!(patch.unwrap().to_string(), "changing instruction 7 from jmp -4 to nop -4 terminates with the accumulator at 8");
+
+    // Every swap still loops, through the two jmps or back to the start.
+    let program = parse("jmp +1\njmp -1\nacc +1\njmp -3").unwrap();
+    assert_eq!(explain_b(&program), None);
+    assert!(solve_b(&program).is_err());
+}
+
+#[test]
+fn test_generate() {
+    for seed in 0..50 {
+        let program = parse(&generate(&mut Rng::new(seed), 20)).unwrap();
+        assert_eq!(program.len(), 20);
+        assert!(!run_machine(&program).0);
+
+        let fixes = (0..program.len()).filter(|&index| {
+            let mut patched = program.clone();
+            patched[index] = match program[index] {
+                Acc(_) => return false,
+                Jump(n) => Noop(n),
+                Noop(n) => Jump(n),
+            };
+            run_machine(&patched).0
+        }).count();
+        assert_eq!(fixes, 1, "seed {}", seed);
+    }
+}
@@ -91,79 +91,71 @@ At this point, something interesting happens: the chaos stabilizes and further a
 
 Simulate your seating area by applying the seating rules repeatedly until no seats change state. How many seats end up occupied?
 */
-use std::io::Read;
-use std::fs::File;
 
-fn main() {
-    let mut input = File::open("inputs/eleven.txt").unwrap();
-    let mut s = String::new();
+use std::fmt::{self, Display};
 
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
-}
+use aoc_common::cancel::{Cancelled, Token};
+use aoc_common::grid::{Grid, NEIGHBOURS_8};
+use aoc_common::parse::ParseError;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum TileType {
+pub enum TileType {
     Floor,
     Seat(bool)
 }
 
-type PlaneState = Vec<Vec<TileType>>;
-
-fn layout(input: &str) -> PlaneState {
-    input.lines().map(|s| {
-        s.trim().chars().map(|c| {
-            match c {
-                '.' => TileType::Floor,
-                'L' => TileType::Seat(false),
-                '#' => TileType::Seat(true),
-                _ => unreachable!("Encountered unknown character {}", c),
-            }
-        }).collect()
-    }).collect()
+impl Display for TileType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TileType::Floor => write!(f, "."),
+            TileType::Seat(false) => write!(f, "L"),
+            TileType::Seat(true) => write!(f, "#"),
+        }
+    }
+}
+
+pub type PlaneState = Grid<TileType>;
+
+pub fn parse(input: &str) -> Result<PlaneState, ParseError> {
+    Grid::parse(input, "'.', 'L' or '#'", |c| match c {
+        '.' => Some(TileType::Floor),
+        'L' => Some(TileType::Seat(false)),
+        '#' => Some(TileType::Seat(true)),
+        _ => None,
+    })
+}
+
+fn occupied(state: &PlaneState) -> usize {
+    state.iter().filter(|&&seat| seat == TileType::Seat(true)).count()
 }
 
 fn simulate_a(state: &PlaneState) -> PlaneState {
     let mut next = state.clone();
 
-    for x in 0..state.len() as isize {
-        for y in 0..state[x as usize].len() as isize{
-            let occupied = vec![
-                (x - 1, y - 1),
-                (x - 1, y),
-                (x - 1, y + 1),
-                (x, y - 1),
-                (x, y + 1),
-                (x + 1, y - 1),
-                (x + 1, y),
-                (x + 1, y + 1)
-            ].into_iter().map(|(x, y)| {
-                state.get(x as usize).and_then(|row| row.get(y as usize))
-            }).filter(|state| *state == Some(&TileType::Seat(true)))
-              .count();
-
-            next[x as usize][y as usize] = match state[x as usize][y as usize] {
-                TileType::Seat(false) if occupied == 0 => TileType::Seat(true),
-                TileType::Seat(true) if occupied >= 4 => TileType::Seat(false),
-                current => current
-            };
-        }
+    for point in state.points() {
+        let occupied = state.neighbours_8(point)
+            .filter(|&neighbour| state[neighbour] == TileType::Seat(true))
+            .count();
+
+        next[point] = match state[point] {
+            TileType::Seat(false) if occupied == 0 => TileType::Seat(true),
+            TileType::Seat(true) if occupied >= 4 => TileType::Seat(false),
+            current => current
+        };
     }
 
     next
 }
 
-fn solve_a(input: &str) -> usize {
-    let mut current = layout(input);
+pub fn solve_a(layout: &PlaneState, token: &Token) -> Result<usize, Cancelled> {
+    let mut current = layout.clone();
 
     loop {
+        token.check()?;
         let next = simulate_a(&current);
 
         if next == current {
-            return current.iter().map(|row| {
-                row.iter().filter(|&&seat| seat == TileType::Seat(true)).count()
-            }).sum()
+            return Ok(occupied(&current))
         }
 
         current = next;
@@ -292,56 +284,31 @@ Given the new visibility method and the rule change for occupied seats becoming
 fn simulate_b(state: &PlaneState) -> PlaneState {
     let mut next = state.clone();
 
-    fn find_next_seat(map: &PlaneState, (mut x, mut y): (isize, isize), (dx, dy): (isize, isize)) -> Option<TileType> {
-        loop {
-            x += dx;
-            y += dy;
-
-            match map.get(x as usize).and_then(|row| row.get(y as usize)) {
-                Some(seat @ TileType::Seat(_)) => return Some(*seat),
-                None => return None,
-                Some(_) => continue,
-            }
-        }
-    }
-
-    for x in 0..state.len() as isize {
-        for y in 0..state[x as usize].len() as isize{
-            let occupied = vec![
-                (-1, -1),
-                (-1, 0),
-                (-1, 1),
-                (0, -1),
-                (0, 1),
-                (1, -1),
-                (1, 0),
-                (1, 1)
-            ].into_iter()
-            .map(|(dx, dy)| find_next_seat(state, (x, y), (dx, dy)))
-            .filter(|state| *state == Some(TileType::Seat(true)))
+    for point in state.points() {
+        let occupied = NEIGHBOURS_8.iter()
+            .filter_map(|&offset| state.ray(point, offset).map(|seen| state[seen]).find(|&tile| tile != TileType::Floor))
+            .filter(|&seat| seat == TileType::Seat(true))
             .count();
 
-            next[x as usize][y as usize] = match state[x as usize][y as usize] {
-                TileType::Seat(false) if occupied == 0 => TileType::Seat(true),
-                TileType::Seat(true) if occupied >= 5 => TileType::Seat(false),
-                current => current
-            };
-        }
+        next[point] = match state[point] {
+            TileType::Seat(false) if occupied == 0 => TileType::Seat(true),
+            TileType::Seat(true) if occupied >= 5 => TileType::Seat(false),
+            current => current
+        };
     }
 
     next
 }
 
-fn solve_b(input: &str) -> usize {
-    let mut current = layout(input);
+pub fn solve_b(layout: &PlaneState, token: &Token) -> Result<usize, Cancelled> {
+    let mut current = layout.clone();
 
     loop {
+        token.check()?;
         let next = simulate_b(&current);
 
         if next == current {
-            return current.iter().map(|row| {
-                row.iter().filter(|&&seat| seat == TileType::Seat(true)).count()
-            }).sum()
+            return Ok(occupied(&current))
         }
 
         current = next;
@@ -353,7 +320,7 @@ fn test_simulate_a() {
     println!("one");
     assert_eq!(
         simulate_a(
-            &layout(
+            &parse(
                 "L.LL.LL.LL
                 LLLLLLL.LL
                 L.L.L..L..
@@ -364,9 +331,9 @@ fn test_simulate_a() {
                 LLLLLLLLLL
                 L.LLLLLL.L
                 L.LLLLL.LL"
-            )
+            ).unwrap()
         ),
-        layout(
+        parse(
             "#.##.##.##
             #######.##
             #.#.#..#..
@@ -377,13 +344,13 @@ fn test_simulate_a() {
             ##########
             #.######.#
             #.#####.##"
-        )
+        ).unwrap()
     );
 
     println!("two");
     assert_eq!(
         simulate_a(
-            &layout(
+            &parse(
                 "#.##.##.##
                 #######.##
                 #.#.#..#..
@@ -394,9 +361,9 @@ fn test_simulate_a() {
                 ##########
                 #.######.#
                 #.#####.##"
-            )
+            ).unwrap()
         ),
-        layout(
+        parse(
             "#.LL.L#.##
             #LLLLLL.L#
             L.L.L..L..
@@ -407,13 +374,13 @@ fn test_simulate_a() {
             #LLLLLLLL#
             #.LLLLLL.L
             #.#LLLL.##"
-        )
+        ).unwrap()
     );
 
     println!("three");
     assert_eq!(
         simulate_a(
-            &layout(
+            &parse(
                 "#.LL.L#.##
                 #LLLLLL.L#
                 L.L.L..L..
@@ -424,9 +391,9 @@ fn test_simulate_a() {
                 #LLLLLLLL#
                 #.LLLLLL.L
                 #.#LLLL.##"
-            )
+            ).unwrap()
         ),
-        layout(
+        parse(
             "#.##.L#.##
             #L###LL.L#
             L.#.#..#..
@@ -437,13 +404,13 @@ fn test_simulate_a() {
             #L######L#
             #.LL###L.L
             #.#L###.##"
-        )
+        ).unwrap()
     );
 
     println!("four");
     assert_eq!(
         simulate_a(
-            &layout(
+            &parse(
                 "#.##.L#.##
                 #L###LL.L#
                 L.#.#..#..
@@ -454,9 +421,9 @@ fn test_simulate_a() {
                 #L######L#
                 #.LL###L.L
                 #.#L###.##"
-            )
+            ).unwrap()
         ),
-        layout(
+        parse(
             "#.#L.L#.##
             #LLL#LL.L#
             L.L.L..#..
@@ -467,13 +434,13 @@ fn test_simulate_a() {
             #L#LLLL#L#
             #.LLLLLL.L
             #.#L#L#.##"
-        )
+        ).unwrap()
     );
 }
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("L.LL.LL.LL
+    assert_eq!(solve_a(&parse("L.LL.LL.LL
                         LLLLLLL.LL
                         L.L.L..L..
                         LLLL.LL.LL
@@ -482,7 +449,7 @@ fn smoke_a() {
                         ..L.L.....
                         LLLLLLLLLL
                         L.LLLLLL.L
-                        L.LLLLL.LL"), 37);
+                        L.LLLLL.LL").unwrap(), &Token::none()), Ok(37));
 }
 
 #[test]
@@ -490,7 +457,7 @@ fn test_simulate_b() {
     println!("one");
     assert_eq!(
         simulate_b(
-            &layout(
+            &parse(
                 "L.LL.LL.LL
                 LLLLLLL.LL
                 L.L.L..L..
@@ -501,9 +468,9 @@ fn test_simulate_b() {
                 LLLLLLLLLL
                 L.LLLLLL.L
                 L.LLLLL.LL"
-            )
+            ).unwrap()
         ),
-        layout(
+        parse(
             "#.##.##.##
             #######.##
             #.#.#..#..
@@ -514,13 +481,13 @@ fn test_simulate_b() {
             ##########
             #.######.#
             #.#####.##"
-        )
+        ).unwrap()
     );
 
     println!("two");
     assert_eq!(
         simulate_b(
-            &layout(
+            &parse(
                 "#.##.##.##
                 #######.##
                 #.#.#..#..
@@ -531,9 +498,9 @@ fn test_simulate_b() {
                 ##########
                 #.######.#
                 #.#####.##"
-            )
+            ).unwrap()
         ),
-        layout(
+        parse(
             "#.LL.LL.L#
             #LLLLLL.LL
             L.L.L..L..
@@ -544,13 +511,13 @@ fn test_simulate_b() {
             LLLLLLLLL#
             #.LLLLLL.L
             #.LLLLL.L#"
-        )
+        ).unwrap()
     );
 
     println!("three");
     assert_eq!(
         simulate_b(
-            &layout(
+            &parse(
                 "#.LL.LL.L#
                 #LLLLLL.LL
                 L.L.L..L..
@@ -561,9 +528,9 @@ fn test_simulate_b() {
                 LLLLLLLLL#
                 #.LLLLLL.L
                 #.LLLLL.L#"
-            )
+            ).unwrap()
         ),
-        layout(
+        parse(
             "#.L#.##.L#
             #L#####.LL
             L.#.#..#..
@@ -574,13 +541,13 @@ fn test_simulate_b() {
             LLL####LL#
             #.L#####.L
             #.L####.L#"
-        )
+        ).unwrap()
     );
 
     println!("four");
     assert_eq!(
         simulate_b(
-            &layout(
+            &parse(
                 "#.L#.##.L#
                 #L#####.LL
                 L.#.#..#..
@@ -591,9 +558,9 @@ fn test_simulate_b() {
                 LLL####LL#
                 #.L#####.L
                 #.L####.L#"
-            )
+            ).unwrap()
         ),
-        layout(
+        parse(
             "#.L#.L#.L#
             #LLLLLL.LL
             L.L.L..#..
@@ -604,13 +571,13 @@ fn test_simulate_b() {
             LLLLLLLLL#
             #.LLLLL#.L
             #.L#LL#.L#"
-        )
+        ).unwrap()
     );
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("L.LL.LL.LL
+    assert_eq!(solve_b(&parse("L.LL.LL.LL
                         LLLLLLL.LL
                         L.L.L..L..
                         LLLL.LL.LL
@@ -619,5 +586,5 @@ fn smoke_b() {
                         ..L.L.....
                         LLLLLLLLLL
                         L.LLLLLL.L
-                        L.LLLLL.LL"), 26);
+                        L.LLLLL.LL").unwrap(), &Token::none()), Ok(26));
 }
\ No newline at end of file
@@ -41,19 +41,8 @@ Here are some other boarding passes:
 
 As a sanity check, look through your list of boarding passes. What is the highest seat ID on a boarding pass?
 */
-extern crate text_io;
 
-use std::io::Read;
-use std::fs::File;
-
-fn main() {
-    let mut input = File::open("inputs/five.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
-}
+use aoc_common::parse::{char_at, ParseError, Parser};
 
 fn find_seat(boarding_pass: &str) -> (usize, usize) {
     let boarding_pass = boarding_pass.trim();
@@ -81,13 +70,31 @@ fn seat_id((row, column): (usize, usize)) -> usize {
     row * 8 + column
 }
 
-fn parse_boarding_passes(input: &str) -> impl Iterator<Item = usize> + '_ {
-    input.lines().filter(|s| !s.is_empty())
-        .map(|boarding_pass| seat_id(find_seat(boarding_pass)))
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let p = Parser::new(input);
+
+    p.lines(|boarding_pass| {
+        let boarding_pass = boarding_pass.trim();
+
+        for (i, c) in boarding_pass.char_indices() {
+            match (i, c) {
+                (0..=6, 'F') | (0..=6, 'B') | (7..=9, 'L') | (7..=9, 'R') => {},
+                (0..=6, _) => return Err(p.error(char_at(boarding_pass, i), "'F' or 'B'")),
+                (7..=9, _) => return Err(p.error(char_at(boarding_pass, i), "'L' or 'R'")),
+                _ => return Err(p.error(char_at(boarding_pass, i), "the end of the line")),
+            }
+        }
+
+        if boarding_pass.len() != 10 {
+            return Err(p.error_after(boarding_pass, "a boarding pass of 10 characters"));
+        }
+
+        Ok(seat_id(find_seat(boarding_pass)))
+    })
 }
 
-fn solve_a(input: &str) -> usize {
-    parse_boarding_passes(input).max().unwrap()
+pub fn solve_a(ids: &[usize]) -> usize {
+    ids.iter().cloned().max().unwrap()
 }
 
 /*
@@ -102,8 +109,8 @@ Your seat wasn't at the very front or back, though; the seats with IDs +1 and -1
 What is the ID of your seat?
 */
 
-fn solve_b(input: &str) -> usize {
-    let mut ids = parse_boarding_passes(input).collect::<Vec<_>>();
+pub fn solve_b(ids: &[usize]) -> usize {
+    let mut ids = ids.to_vec();
 
     ids.sort();
 
@@ -128,26 +135,26 @@ fn test_find_seat() {
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("BFFFBBFRRR
+    assert_eq!(solve_a(&parse("BFFFBBFRRR
                         FFFBBBFRRR
-                        BBFFBBFRLL"), 820);
+                        BBFFBBFRLL").unwrap()), 820);
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("BBBFFFFLRR
+    assert_eq!(solve_b(&parse("BBBFFFFLRR
                         BBBFFFFRLL
                         BBBFFFFRLR
                         BBBFFFFRRL
                         BBBFFFBLLL
                         BBBFFFBLLR
-                        BBBFFFBLRL"), 903);
+                        BBBFFFBLRL").unwrap()), 903);
 
-    assert_eq!(solve_b("BBBFFFFLRR
+    assert_eq!(solve_b(&parse("BBBFFFFLRR
                         BBBFFFBLLL
                         BBBFFFFRLL
                         BBBFFFBLLR
                         BBBFFFFRLR
                         BBBFFFFRRL
-                        BBBFFFBLRL"), 903)
+                        BBBFFFBLRL").unwrap()), 903)
 }
\ No newline at end of file
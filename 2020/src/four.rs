@@ -46,44 +46,40 @@ According to the above rules, your improved system would report 2 valid passport
 
 Count the number of valid passports - those that have all required fields. Treat cid as optional. In your batch file, how many passports are valid?
 */
-#[macro_use]
-extern crate text_io;
 
-use std::io::Read;
-use std::fs::File;
+use aoc_common::parse::{ParseError, Parser};
 use std::collections::HashMap;
 
-fn main() {
-    let mut input = File::open("inputs/four.txt").unwrap();
-    let mut s = String::new();
+pub type Passport = HashMap<String, String>;
 
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
-}
+const FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
 
-fn solve_a(input: &str) -> usize {
-    input.split("\n\n").filter(|passport| {
-        let fields = passport
-            .split(&[' ', '\n'][..])
-            .filter(|s| !s.is_empty())
-            .filter_map(|keyvalue| {
-                let mut bytes = &mut keyvalue.trim().bytes();
-                let key: String = read!("{}:", bytes);
-                let value: String = read!("{}", bytes);
-
-                if key.is_empty() {
-                    panic!("{} was empty", keyvalue);
-                }
+pub fn parse(input: &str) -> Result<Vec<Passport>, ParseError> {
+    let p = Parser::new(input);
 
-                if value.is_empty() {
-                    None
-                } else {
-                    Some((key, value))
-                }
-            })
-            .collect::<HashMap<String, String>>();
+    input.split("\n\n").map(|passport| {
+        let mut fields = Passport::new();
+
+        for keyvalue in passport.split_whitespace() {
+            let mut split = keyvalue.splitn(2, ':');
+            let key = p.token(&mut split, keyvalue, "a field")?;
+            let value = p.token(&mut split, keyvalue, "\":\"")?;
+
+            if !FIELDS.contains(&key) {
+                return Err(p.error(key, "a passport field"));
+            }
+
+            if !value.is_empty() {
+                fields.insert(key.to_owned(), value.to_owned());
+            }
+        }
+
+        Ok(fields)
+    }).collect()
+}
 
+pub fn solve_a(passports: &[Passport]) -> usize {
+    passports.iter().filter(|fields| {
         let required_fields = vec![
             "byr",
             "iyr",
@@ -95,19 +91,9 @@ fn solve_a(input: &str) -> usize {
             // "cid",
         ];
 
-        let has_extra_fields = fields.keys().any(|key| {
-            if key == "cid" { return false }
-
-            !required_fields.contains(&&**key)
-        });
-
-        if has_extra_fields {
-            panic!("{} has extra", passport);
-        }
-
-        let valid = required_fields.into_iter().all(|key| fields.get(key).is_some());
+        let valid = required_fields.into_iter().all(|key| fields.contains_key(key));
         if valid {
-            println!("{} is valid:\n{:?}\n\n", passport, fields);
+            trace!("{:?} is valid", fields);
         }
 
         valid
@@ -186,48 +172,25 @@ iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
 Count the number of valid passports - those that have all required fields and valid values. Continue to treat cid as optional. In your batch file, how many passports are valid?
 */
 
-fn solve_b(input: &str) -> usize {
-    input.split("\n\n").filter(|passport| {
-        let fields = passport
-            .split(&[' ', '\n'][..])
-            .filter(|s| !s.is_empty())
-            .filter_map(|keyvalue| {
-                let mut bytes = &mut keyvalue.trim().bytes();
-                let key: String = read!("{}:", bytes);
-                let value: String = read!("{}", bytes);
-
-                if key.is_empty() {
-                    panic!("{} was empty", keyvalue);
-                }
-
-                if value.is_empty() {
-                    None
-                } else {
-                    Some((key, value))
-                }
-            })
-            .collect::<HashMap<String, String>>();
-
+pub fn solve_b(passports: &[Passport]) -> usize {
+    passports.iter().filter(|fields| {
         match (
             // byr (Birth Year) - four digits; at least 1920 and at most 2002.
-            fields.get("byr").and_then(|value| value.parse::<usize>().ok()).filter(|&value| value >= 1920 && value <= 2002),
+            fields.get("byr").and_then(|value| value.parse::<usize>().ok()).filter(|value| (1920..=2002).contains(value)),
             // iyr (Issue Year) - four digits; at least 2010 and at most 2020.
-            fields.get("iyr").and_then(|value| value.parse::<usize>().ok()).filter(|&value| value >= 2010 && value <= 2020),
+            fields.get("iyr").and_then(|value| value.parse::<usize>().ok()).filter(|value| (2010..=2020).contains(value)),
             // eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
-            fields.get("eyr").and_then(|value| value.parse::<usize>().ok()).filter(|&value| value >= 2020 && value <= 2030),
+            fields.get("eyr").and_then(|value| value.parse::<usize>().ok()).filter(|value| (2020..=2030).contains(value)),
             // hgt (Height) - a number followed by either cm or in:
             //     If cm, the number must be at least 150 and at most 193.
             //     If in, the number must be at least 59 and at most 76.
             fields.get("hgt").filter(|value| {
-                let value = value.trim();
-                if value.contains("cm") {
-                    let value: usize  = read!("{}cm", &mut value.bytes());
-                    value >= 150 && value <= 193
-                } else if value.contains("in") {
-                    let value: usize = read!("{}in", &mut value.bytes());
-                    value >= 59 && value <= 76
-                } else {
-                    false
+                let height = |unit| value.strip_suffix(unit).and_then(|n| n.parse::<usize>().ok());
+
+                match (height("cm"), height("in")) {
+                    (Some(cm), _) => (150..=193).contains(&cm),
+                    (_, Some(inches)) => (59..=76).contains(&inches),
+                    _ => false,
                 }
             }),
             // hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
@@ -237,7 +200,7 @@ fn solve_b(input: &str) -> usize {
                 value.chars().skip(1).all(|c| "0123456789abcdef".contains(c))
             }),
             // ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
-            fields.get("ecl").filter(|value| vec!["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&&***value)),
+            fields.get("ecl").filter(|value| ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&&***value)),
             // pid (Passport ID) - a nine-digit number, including leading zeroes.
             fields.get("pid").filter(|value| {
                 if value.len() != 9  { return false }
@@ -248,8 +211,8 @@ fn solve_b(input: &str) -> usize {
             /* ignore */
         ) {
             (Some(_), Some(_), Some(_), Some(_), Some(_), Some(_), Some(_)) => true,
-            result @ _=> {
-                println!("{:?} was invalid\n{:?}", fields, result);
+            result => {
+                debug!("{:?} was invalid\n{:?}", fields, result);
                 false
             }
         }
@@ -258,7 +221,7 @@ fn solve_b(input: &str) -> usize {
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+    assert_eq!(solve_a(&parse("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
                         byr:1937 iyr:2017 cid:147 hgt:183cm
 
                         iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
@@ -270,12 +233,12 @@ fn smoke_a() {
                         hgt:179cm
 
                         hcl:#cfa07d eyr:2025 pid:166559648
-                        iyr:2011 ecl:brn hgt:59in"), 2);
+                        iyr:2011 ecl:brn hgt:59in").unwrap()), 2);
 }
 
 #[test]
 fn smoke_b_invalid() {
-    assert_eq!(solve_b("eyr:1972 cid:100
+    assert_eq!(solve_b(&parse("eyr:1972 cid:100
                         hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
 
                         iyr:2019
@@ -287,12 +250,12 @@ fn smoke_b_invalid() {
 
                         hgt:59cm ecl:zzz
                         eyr:2038 hcl:74454a iyr:2023
-                        pid:3556412378 byr:2007"), 0);
+                        pid:3556412378 byr:2007").unwrap()), 0);
 }
 
 #[test]
 fn smoke_b_valid() {
-    assert_eq!(solve_b("pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+    assert_eq!(solve_b(&parse("pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
                         hcl:#623a2f
 
                         eyr:2029 ecl:blu cid:129 byr:1989
@@ -303,5 +266,5 @@ fn smoke_b_valid() {
                         pid:545766238 ecl:hzl
                         eyr:2022
 
-                        iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719"), 4);
+                        iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719").unwrap()), 4);
 }
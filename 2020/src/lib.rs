@@ -1,7 +1,99 @@
+#[macro_use]
+extern crate aoc_common;
+
+use aoc_common::{Day, Year};
+
+pub mod one;
+pub mod two;
+pub mod three;
+pub mod four;
+pub mod five;
+pub mod six;
+pub mod seven;
+pub mod eight;
+pub mod nine;
+pub mod ten;
+pub mod eleven;
+pub mod twelve;
+
+// Each day by number as well, e.g. `day10::knot_hash`.
+pub use one as day01;
+pub use two as day02;
+pub use three as day03;
+pub use four as day04;
+pub use five as day05;
+pub use six as day06;
+pub use seven as day07;
+pub use eight as day08;
+pub use nine as day09;
+pub use ten as day10;
+pub use eleven as day11;
+pub use twelve as day12;
+
+/// Every day solved for 2020.
+///
+/// Parts are all registered through closures, like the ones `aoc new`
+/// writes, whether or not a day's types need one to coerce.
+#[allow(clippy::redundant_closure)]
+pub fn year() -> Year {
+    Year::new(2020, env!("CARGO_MANIFEST_DIR"))
+        .register(Day::new(1, "one")
+            .parse(one::parse)
+            .part_a(|numbers| one::solve_a(numbers))
+            .part_b(|numbers| one::solve_b(numbers))
+            .explain_a(|numbers| one::explain_a(numbers))
+            .explain_b(|numbers| one::explain_b(numbers)))
+        .register(Day::new(2, "two")
+            .parse(two::parse)
+            .part_a(|policies| two::solve_a(policies))
+            .part_b(|policies| two::solve_b(policies)))
+        .register(Day::new(3, "three")
+            .parse(three::parse)
+            .part_a(|tree_map| three::solve_a(tree_map))
+            .part_b(|tree_map| three::solve_b(tree_map)))
+        .register(Day::new(4, "four")
+            .parse(four::parse)
+            .part_a(|passports| four::solve_a(passports))
+            .part_b(|passports| four::solve_b(passports)))
+        .register(Day::new(5, "five")
+            .parse(five::parse)
+            .part_a(|ids| five::solve_a(ids))
+            .part_b(|ids| five::solve_b(ids)))
+        .register(Day::new(6, "six")
+            .parse(six::parse)
+            .part_a(|groups| six::solve_a(groups))
+            .part_b(|groups| six::solve_b(groups)))
+        .register(Day::new(7, "seven")
+            .parse(seven::parse)
+            .part_a(|map| seven::solve_a(map))
+            .part_b(|map| seven::solve_b(map))
+            .generate(seven::generate))
+        .register(Day::new(8, "eight")
+            .parse(eight::parse)
+            .part_a(|instructions| eight::solve_a(instructions))
+            .part_b(|instructions| eight::solve_b(instructions))
+            .explain_b(|instructions| eight::explain_b(instructions))
+            .generate(eight::generate))
+        .register(Day::new(9, "nine")
+            .parse(nine::parse)
+            .part_a(|numbers| nine::solve_a(numbers, 25))
+            .part_b(|numbers| nine::solve_b(numbers, 25))
+            .explain_b(|numbers| nine::explain_b(numbers, 25)))
+        .register(Day::new(10, "ten")
+            .parse(ten::parse)
+            .part_a(|adapters| ten::solve_a(adapters))
+            .part_b(|adapters| ten::solve_b(adapters)))
+        .register(Day::new(11, "eleven")
+            .parse(eleven::parse)
+            .part_a_cancellable(|layout, token| eleven::solve_a(layout, token))
+            .part_b_cancellable(|layout, token| eleven::solve_b(layout, token)))
+        .register(Day::new(12, "twelve")
+            .parse(twelve::parse)
+            .part_a(|instructions| twelve::solve_a(instructions))
+            .part_b(|instructions| twelve::solve_b(instructions)))
+}
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
+    year_tests!(::year());
 }
@@ -49,21 +49,27 @@ In this example, after the 5-number preamble, almost every number is the sum of
 
 The first step of attacking the weakness in the XMAS data is to find the first number in the list (after the preamble) which is not the sum of two of the 25 numbers before it. What is the first number that does not have this property?
 */
-use std::io::Read;
-use std::fs::File;
+
+use aoc_common::Failure;
+use aoc_common::parse::{ParseError, Parser};
 use std::collections::VecDeque;
+use std::fmt::{self, Display};
+
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let p = Parser::new(input);
 
-fn main() {
-    let mut input = File::open("inputs/nine.txt").unwrap();
-    let mut s = String::new();
+    p.lines(|line| p.number(line.trim()))
+}
 
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s, 25));
-    println!("b: {}", solve_b(&s, 25));
+pub fn solve_a(numbers: &[usize], preamble_len: usize) -> Result<usize, Failure> {
+    first_invalid(numbers, preamble_len)
+        .ok_or_else(|| Failure::no_answer("every number is the sum of two of those before it"))
 }
 
-fn solve_a(input: &str, preamble_len: usize) -> usize {
-    let inputs = &mut input.lines().filter_map(|s| s.trim().parse::<usize>().ok());
+/// The first number that isn't the sum of two of the `preamble_len` before
+/// it, if there is one.
+fn first_invalid(numbers: &[usize], preamble_len: usize) -> Option<usize> {
+    let inputs = &mut numbers.iter().cloned();
     let mut preamble: VecDeque<_> = inputs.take(preamble_len).collect();
 
     for x in inputs {
@@ -79,14 +85,14 @@ fn solve_a(input: &str, preamble_len: usize) -> usize {
         }
 
         if !valid {
-            return x
+            return Some(x)
         }
 
         preamble.pop_front();
         preamble.push_back(x);
     }
 
-    unreachable!()
+    None
 }
 
 /*
@@ -124,9 +130,33 @@ To find the encryption weakness, add together the smallest and largest number in
 What is the encryption weakness in your XMAS-encrypted list of numbers?
 */
 
-fn solve_b(input: &str, preamble_len: usize) -> usize {
-    let inputs = input.lines().filter_map(|s| s.trim().parse::<usize>().ok()).collect::<Vec<_>>();
-    let invalid_number = solve_a(input, preamble_len);
+/// The contiguous run of numbers that sums to the invalid number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Weakness {
+    /// Where the run starts and ends in the list, inclusive.
+    pub start: usize,
+    pub end: usize,
+    pub invalid_number: usize,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Display for Weakness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "numbers {} to {} sum to the invalid number {}; the smallest is {} and the largest {}, so {} + {} = {}",
+               self.start, self.end, self.invalid_number, self.min, self.max, self.min, self.max, self.min + self.max)
+    }
+}
+
+pub fn solve_b(inputs: &[usize], preamble_len: usize) -> Result<usize, Failure> {
+    explain_b(inputs, preamble_len)
+        .map(|weakness| weakness.min + weakness.max)
+        .ok_or_else(|| Failure::no_answer("no run of numbers sums to an invalid number"))
+}
+
+/// The weakness, if there's an invalid number and a run that sums to it.
+pub fn explain_b(inputs: &[usize], preamble_len: usize) -> Option<Weakness> {
+    let invalid_number = first_invalid(inputs, preamble_len)?;
 
     for (start, &a) in inputs.iter().enumerate() {
         let mut sum = a;
@@ -135,24 +165,24 @@ fn solve_b(input: &str, preamble_len: usize) -> usize {
             sum += b;
 
             if offset > 0 && sum == invalid_number {
-                let end = start + offset;
+                let end = start + offset + 1;
                 let values = &inputs[start..=end];
-                let min = values.iter().min().unwrap();
-                let max = values.iter().max().unwrap();
+                let min = *values.iter().min().unwrap();
+                let max = *values.iter().max().unwrap();
 
-                return min + max
+                return Some(Weakness { start, end, invalid_number, min, max })
             } else if sum > invalid_number {
                 break
             }
         }
     }
 
-    unreachable!()
+    None
 }
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("35
+    assert_eq!(solve_a(&parse("35
                         20
                         15
                         25
@@ -171,8 +201,8 @@ fn smoke_a() {
                         299
                         277
                         309
-                        576", 5), 127);
-    assert_eq!(solve_a("1
+                        576").unwrap(), 5), Ok(127));
+    assert_eq!(solve_a(&parse("1
                         2
                         3
                         4
@@ -197,8 +227,8 @@ fn smoke_a() {
                         23
                         24
                         25
-                        100", 25), 100);
-    assert_eq!(solve_a("1
+                        100").unwrap(), 25), Ok(100));
+    assert_eq!(solve_a(&parse("1
                         2
                         3
                         4
@@ -224,12 +254,12 @@ fn smoke_a() {
                         24
                         25
                         50
-                        100", 25), 50);
+                        100").unwrap(), 25), Ok(50));
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("35
+    assert_eq!(solve_b(&parse("35
                         20
                         15
                         25
@@ -248,5 +278,26 @@ fn smoke_b() {
                         299
                         277
                         309
-                        576", 5), 62);
+                        576").unwrap(), 5), Ok(62));
+}
+
+#[test]
+fn test_explain_b() {
+    let numbers = parse("35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576").unwrap();
+
+    assert_eq!(explain_b(&numbers, 5), Some(Weakness { start: 2, end: 5, invalid_number: 127, min: 15, max: 47 }));
+
+    // 3 is invalid, but no run of two or more sums to it.
+    assert_eq!(explain_b(&parse("1\n1\n3\n5").unwrap(), 2), None);
+    assert!(solve_b(&parse("1\n1\n3\n5").unwrap(), 2).is_err());
+    assert!(solve_a(&parse("1\n2\n3\n5").unwrap(), 2).is_err());
+}
+
+#[test]
+fn test_b_range_includes_last_number() {
+    // The run is 1, 2, 7, so the largest number is the last one in it.
+    let numbers = parse("1\n2\n3\n10\n1\n2\n7").unwrap();
+
+    assert_eq!(explain_b(&numbers, 2), Some(Weakness { start: 4, end: 6, invalid_number: 10, min: 1, max: 7 }));
+    assert_eq!(solve_b(&numbers, 2), Ok(8));
 }
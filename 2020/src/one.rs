@@ -27,30 +27,51 @@ In this list, the two entries that sum to 2020 are 1721 and 299. Multiplying the
 Of course, your expense report is much larger. Find the two entries that sum to 2020; what do you get if you multiply them together?
 */
 
-use std::io::Read;
-use std::fs::File;
+use std::fmt::{self, Display};
 
-fn main() {
-    let mut input = File::open("inputs/one.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::Failure;
+use aoc_common::parse::{ParseError, Parser};
 
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+/// The entries that sum to 2020, which the answer is the product of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entries(pub Vec<u32>);
+
+impl Entries {
+    pub fn product(&self) -> u32 {
+        self.0.iter().product()
+    }
+}
+
+impl Display for Entries {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self.0.iter().map(u32::to_string).collect::<Vec<_>>();
+
+        write!(f, "{} = {}, {} = {}", entries.join(" + "), self.0.iter().sum::<u32>(), entries.join(" * "), self.product())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    let p = Parser::new(input);
+
+    p.lines(|line| p.number(line.trim()))
 }
 
-fn solve_a(input: &str) -> u32 {
-    let numbers: Vec<u32> = input.lines().flat_map(|s| s.trim().parse().ok()).collect();
+pub fn solve_a(numbers: &[u32]) -> Result<u32, Failure> {
+    explain_a(numbers)
+        .map(|entries| entries.product())
+        .ok_or_else(|| Failure::no_answer("no two entries sum to 2020"))
+}
 
-    for (idx, a) in numbers.iter().enumerate() {
-        for b in numbers.iter().skip(idx) {
+pub fn explain_a(numbers: &[u32]) -> Option<Entries> {
+    for (idx, &a) in numbers.iter().enumerate() {
+        for &b in numbers.iter().skip(idx) {
             if a + b == 2020 {
-                return a * b;
+                return Some(Entries(vec![a, b]));
             }
         }
     }
 
-    unreachable!()
+    None
 }
 
 /*
@@ -63,38 +84,53 @@ Using the above example again, the three entries that sum to 2020 are 979, 366,
 In your expense report, what is the product of the three entries that sum to 2020?
 */
 
-fn solve_b(input: &str) -> u32 {
-    let numbers: Vec<u32> = input.lines().flat_map(|s| s.trim().parse().ok()).collect();
+pub fn solve_b(numbers: &[u32]) -> Result<u32, Failure> {
+    explain_b(numbers)
+        .map(|entries| entries.product())
+        .ok_or_else(|| Failure::no_answer("no three entries sum to 2020"))
+}
 
-    for (idx, a) in numbers.iter().enumerate() {
-        for (idx, b) in numbers.iter().skip(idx).enumerate() {
-            for c in numbers.iter().skip(idx) {
+pub fn explain_b(numbers: &[u32]) -> Option<Entries> {
+    for (idx, &a) in numbers.iter().enumerate() {
+        for (idx, &b) in numbers.iter().skip(idx).enumerate() {
+            for &c in numbers.iter().skip(idx) {
                 if a + b + c == 2020 {
-                    return a * b * c;
+                    return Some(Entries(vec![a, b, c]));
                 }
             }
         }
     }
 
-    unreachable!()
+    None
 }
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("1721
+    assert_eq!(solve_a(&parse("1721
                         979
                         366
                         299
                         675
-                        1456"), 514579);
+                        1456").unwrap()), Ok(514579));
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("1721
+    assert_eq!(solve_b(&parse("1721
                         979
                         366
                         299
                         675
-                        1456"), 241861950);
+                        1456").unwrap()), Ok(241861950));
+}
+
+#[test]
+fn test_explain() {
+    let numbers = parse("1721\n979\n366\n299\n675\n1456").unwrap();
+
+    assert_eq!(explain_a(&numbers).unwrap().to_string(), "1721 + 299 = 2020, 1721 * 299 = 514579");
+    assert_eq!(explain_b(&numbers), Some(Entries(vec![979, 366, 675])));
+
+    assert_eq!(explain_a(&[1, 2, 3]), None);
+    assert!(solve_b(&[1, 2, 3]).is_err());
 }
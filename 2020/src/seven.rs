@@ -32,64 +32,54 @@ So, in this example, the number of bag colors that can eventually contain at lea
 
 How many bag colors can eventually contain at least one shiny gold bag? (The list of rules is quite long; make sure you get all of it.)
 */
-#[macro_use]
-extern crate text_io;
 
-use std::io::Read;
-use std::fs::File;
+use aoc_common::generate::Rng;
+use aoc_common::parse::{ParseError, Parser};
 use std::collections::HashMap;
 
-fn main() {
-    let mut input = File::open("inputs/seven.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
-}
-
-type BagMap = HashMap<String, Vec<(usize, String)>>;
-
-fn parse_rules(input: &str) -> BagMap {
-    input.lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            // println!("Checking {}", line.trim());
-            let mut bytes = &mut line.trim().bytes();
-            let adjective: String;
-            let colour: String;
-
-            scan!(bytes => "{} {} bags contain ", adjective, colour);
-
-            let name = format!("{} {}", adjective, colour);
-
-            let remainder: String = read!("{}\n", bytes);
-
-            let inside = remainder.split(',')
-                .filter(|s| !s.contains("no other"))
-                .map(|s| {
-                    let mut bytes = &mut s.trim().bytes();
-                    let count: usize = read!("{} ", bytes);
-                    let adjective: String = read!("{} ", bytes);
-                    let colour: String = read!("{} ", bytes);
-
-                    (count, format!("{} {}", adjective, colour))
-                })
-                .collect::<Vec<_>>();
-
-            (name, inside)
-        }).collect()
+pub type BagMap = HashMap<String, Vec<(usize, String)>>;
+
+pub fn parse(input: &str) -> Result<BagMap, ParseError> {
+    let p = Parser::new(input);
+
+    let rules = p.lines(|line| {
+        // light red bags contain 1 bright white bag, 2 muted yellow bags.
+        let line = line.trim();
+        let mut split = line.splitn(2, " bags contain ");
+        let name = p.token(&mut split, line, "a bag colour")?;
+        let remainder = p.suffix(p.token(&mut split, line, "\" bags contain \"")?, ".")?;
+
+        let mut inside = vec![];
+        if remainder != "no other bags" {
+            for bag in remainder.split(", ") {
+                let mut words = bag.split(' ');
+                let count = p.number(p.token(&mut words, bag, "a count")?)?;
+                let adjective = p.token(&mut words, bag, "an adjective")?;
+                let colour = p.token(&mut words, bag, "a colour")?;
+                let noun = p.token(&mut words, bag, "\"bags\"")?;
+                if noun != "bag" && noun != "bags" {
+                    return Err(p.error(noun, "\"bags\""));
+                }
+                p.end(&mut words)?;
+
+                inside.push((count, format!("{} {}", adjective, colour)));
+            }
+        }
+
+        Ok((name.to_owned(), inside))
+    })?;
+
+    Ok(rules.into_iter().collect())
 }
 
-fn solve_a(input: &str) -> usize {
+pub fn solve_a(map: &BagMap) -> usize {
     fn has_shiny_gold(map: &BagMap, starting_key: &str) -> bool {
         map[starting_key].iter().any(|(_, bag_type)| {
             bag_type == "shiny gold" || has_shiny_gold(map, bag_type)
         })
     }
 
-    let map = parse_rules(input);
-    map.keys().filter(|key| has_shiny_gold(&map, key)).count()
+    map.keys().filter(|key| has_shiny_gold(map, key)).count()
 }
 
 /*
@@ -123,21 +113,90 @@ In this example, a single shiny gold bag must contain 126 other bags.
 How many individual bags are required inside your single shiny gold bag?
 */
 
-fn solve_b(input: &str) -> usize {
+pub fn solve_b(map: &BagMap) -> usize {
     fn count(map: &BagMap, starting_key: &str) -> usize {
         map[starting_key].iter().fold(0, |total, (n, bag_type)| {
             total + n + n * count(map, bag_type)
         })
     }
 
-    let map = parse_rules(input);
+    count(map, "shiny gold")
+}
+
+const ADJECTIVES: [&str; 16] = [
+    "light", "dark", "bright", "muted", "shiny", "faded", "dotted", "vibrant",
+    "pale", "dim", "clear", "drab", "wavy", "plaid", "posh", "striped",
+];
+
+const COLOURS: [&str; 20] = [
+    "red", "orange", "yellow", "white", "gold", "olive", "plum", "blue", "black", "green",
+    "violet", "teal", "tan", "crimson", "indigo", "lime", "maroon", "silver", "beige", "coral",
+];
+
+/// How many levels of bags inside bags there are, which keeps the counts
+/// from growing without bound like the real rules.
+const DEPTH: usize = 7;
+
+/// Rules for `size` bag colours, one of them shiny gold.
+///
+/// Each bag is put on one of a few levels, shiny gold in the middle, and
+/// only holds bags from the levels below it, so the rules never loop.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let per_round = ADJECTIVES.len() * COLOURS.len();
+    let mut names = (0..)
+        .map(|i| {
+            let adjective = ADJECTIVES[i % ADJECTIVES.len()];
+            let colour = COLOURS[i / ADJECTIVES.len() % COLOURS.len()];
+            match i / per_round {
+                0 => format!("{} {}", adjective, colour),
+                round => format!("{} {}{}", adjective, colour, round),
+            }
+        })
+        .filter(|name| name != "shiny gold")
+        .take(size.max(1) - 1)
+        .collect::<Vec<_>>();
+    names.push("shiny gold".to_string());
+    rng.shuffle(&mut names);
+
+    let levels = names.iter()
+        .map(|name| if name == "shiny gold" { DEPTH / 2 } else { rng.below(DEPTH) })
+        .collect::<Vec<_>>();
+    let mut by_level = vec![vec![]; DEPTH];
+    for (bag, &level) in levels.iter().enumerate() {
+        by_level[level].push(bag);
+    }
+
+    let mut rules = String::new();
+    for (name, &level) in names.iter().zip(&levels) {
+        let below = by_level[level + 1..].iter().filter(|bags| !bags.is_empty()).collect::<Vec<_>>();
+        let mut inside = vec![];
+        if !below.is_empty() {
+            for _ in 0..rng.below(5) {
+                let level = *rng.pick(&below);
+                let bag = *rng.pick(level);
+                if !inside.contains(&bag) {
+                    inside.push(bag);
+                }
+            }
+        }
+
+        let contents = inside.iter()
+            .map(|&bag| match rng.range(1, 5) {
+                1 => format!("1 {} bag", names[bag]),
+                count => format!("{} {} bags", count, names[bag]),
+            })
+            .collect::<Vec<_>>();
+        let contents = if contents.is_empty() { "no other bags".to_string() } else { contents.join(", ") };
+
+        rules += &format!("{} bags contain {}.\n", name, contents);
+    }
 
-    count(&map, "shiny gold")
+    rules
 }
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("light red bags contain 1 bright white bag, 2 muted yellow bags.
+    assert_eq!(solve_a(&parse("light red bags contain 1 bright white bag, 2 muted yellow bags.
                         dark orange bags contain 3 bright white bags, 4 muted yellow bags.
                         bright white bags contain 1 shiny gold bag.
                         muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
@@ -145,12 +204,12 @@ fn smoke_a() {
                         dark olive bags contain 3 faded blue bags, 4 dotted black bags.
                         vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
                         faded blue bags contain no other bags.
-                        dotted black bags contain no other bags."), 4);
+                        dotted black bags contain no other bags.").unwrap()), 4);
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("light red bags contain 1 bright white bag, 2 muted yellow bags.
+    assert_eq!(solve_b(&parse("light red bags contain 1 bright white bag, 2 muted yellow bags.
                         dark orange bags contain 3 bright white bags, 4 muted yellow bags.
                         bright white bags contain 1 shiny gold bag.
                         muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
@@ -158,16 +217,41 @@ fn smoke_b() {
                         dark olive bags contain 3 faded blue bags, 4 dotted black bags.
                         vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
                         faded blue bags contain no other bags.
-                        dotted black bags contain no other bags."), 32);
+                        dotted black bags contain no other bags.").unwrap()), 32);
 }
 
 #[test]
 fn smoke_b_2() {
-    assert_eq!(solve_b("shiny gold bags contain 2 dark red bags.
+    assert_eq!(solve_b(&parse("shiny gold bags contain 2 dark red bags.
                         dark red bags contain 2 dark orange bags.
                         dark orange bags contain 2 dark yellow bags.
                         dark yellow bags contain 2 dark green bags.
                         dark green bags contain 2 dark blue bags.
                         dark blue bags contain 2 dark violet bags.
-                        dark violet bags contain no other bags."), 126);
+                        dark violet bags contain no other bags.").unwrap()), 126);
+}
+
+#[test]
+fn test_generate() {
+    use std::collections::HashSet;
+
+    for seed in 0..20 {
+        let map = parse(&generate(&mut Rng::new(seed), 100)).unwrap();
+        assert_eq!(map.len(), 100);
+
+        // Every bag that can end up holding shiny gold, worked outwards.
+        let mut holders = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (bag, inside) in &map {
+                if !holders.contains(bag) && inside.iter().any(|(_, b)| b == "shiny gold" || holders.contains(b)) {
+                    holders.insert(bag.clone());
+                    changed = true;
+                }
+            }
+        }
+        assert_eq!(solve_a(&map), holders.len(), "seed {}", seed);
+        solve_b(&map);
+    }
 }
@@ -43,24 +43,26 @@ In this example, the sum of these counts is 3 + 3 + 3 + 1 + 1 = 11.
 
 For each group, count the number of questions to which anyone answered "yes". What is the sum of those counts?
 */
-extern crate text_io;
 
-use std::io::Read;
-use std::fs::File;
+use aoc_common::parse::{char_at, ParseError, Parser};
 use std::collections::{HashSet, HashMap};
 
-fn main() {
-    let mut input = File::open("inputs/six.txt").unwrap();
-    let mut s = String::new();
+pub fn parse(input: &str) -> Result<Vec<Vec<String>>, ParseError> {
+    let p = Parser::new(input);
 
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+    input.split("\n\n").map(|group| {
+        group.split_whitespace().map(|voter| {
+            match voter.char_indices().find(|&(_, c)| !c.is_ascii_lowercase()) {
+                Some((i, _)) => Err(p.error(char_at(voter, i), "a question from 'a' to 'z'")),
+                None => Ok(voter.to_owned()),
+            }
+        }).collect()
+    }).collect()
 }
 
-fn solve_a(input: &str) -> usize {
-    input.split("\n\n").map(|group| {
-        let set: HashSet<char> = group.chars().filter(|c| !c.is_whitespace()).collect();
+pub fn solve_a(groups: &[Vec<String>]) -> usize {
+    groups.iter().map(|group| {
+        let set: HashSet<char> = group.iter().flat_map(|voter| voter.chars()).collect();
         set.len()
     }).sum()
 }
@@ -103,13 +105,13 @@ In this example, the sum of these counts is 3 + 0 + 1 + 1 + 1 = 6.
 For each group, count the number of questions to which everyone answered "yes". What is the sum of those counts?
 */
 
-fn solve_b(input: &str) -> usize {
-    input.split("\n\n").map(|group| {
+pub fn solve_b(groups: &[Vec<String>]) -> usize {
+    groups.iter().map(|group| {
         let mut voters = 0;
         let mut map = HashMap::<char, usize>::new();
 
-        for voter in group.lines().filter(|line| !line.is_empty()) {
-            let mut chars: Vec<_> = voter.chars().filter(|c| !c.is_whitespace()).collect();
+        for voter in group {
+            let mut chars: Vec<_> = voter.chars().collect();
             chars.sort();
             chars.dedup();
 
@@ -126,7 +128,7 @@ fn solve_b(input: &str) -> usize {
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("abc
+    assert_eq!(solve_a(&parse("abc
 
                         a
                         b
@@ -140,12 +142,12 @@ fn smoke_a() {
                         a
                         a
 
-                        b"), 11);
+                        b").unwrap()), 11);
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("abc
+    assert_eq!(solve_b(&parse("abc
 
                         a
                         b
@@ -159,5 +161,5 @@ fn smoke_b() {
                         a
                         a
 
-                        b"), 6);
+                        b").unwrap()), 6);
 }
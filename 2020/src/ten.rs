@@ -84,21 +84,18 @@ In this larger example, in a chain that uses all of the adapters, there are 22 d
 
 Find a chain that uses all of your adapters to connect the charging outlet to your device's built-in adapter and count the joltage differences between the charging outlet, the adapters, and your device. What is the number of 1-jolt differences multiplied by the number of 3-jolt differences?
 */
-use std::io::Read;
-use std::fs::File;
+
+use aoc_common::parse::{ParseError, Parser};
 use std::collections::HashMap;
 
-fn main() {
-    let mut input = File::open("inputs/ten.txt").unwrap();
-    let mut s = String::new();
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let p = Parser::new(input);
 
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+    p.lines(|line| p.number(line.trim()))
 }
 
-fn get_counts(input: &str) -> [usize; 4] {
-    let mut adapters: Vec<usize> = input.lines().filter_map(|line| line.trim().parse().ok()).collect();
+fn get_counts(adapters: &[usize]) -> [usize; 4] {
+    let mut adapters = adapters.to_vec();
 
     adapters.sort();
 
@@ -120,8 +117,8 @@ fn get_counts(input: &str) -> [usize; 4] {
     counts
 }
 
-fn solve_a(input: &str) -> usize {
-    let [_, ones, _, threes] = get_counts(input);
+pub fn solve_a(adapters: &[usize]) -> usize {
+    let [_, ones, _, threes] = get_counts(adapters);
 
     ones * threes
 }
@@ -183,8 +180,8 @@ You glance back down at your bag and try to remember why you brought so many ada
 What is the total number of distinct ways you can arrange the adapters to connect the charging outlet to your device?
 */
 
-fn solve_b(input: &str) -> isize {
-    let mut adapters: Vec<isize> = input.lines().filter_map(|line| line.trim().parse().ok()).collect();
+pub fn solve_b(adapters: &[usize]) -> isize {
+    let mut adapters: Vec<isize> = adapters.iter().map(|&adapter| adapter as isize).collect();
 
     adapters.sort();
 
@@ -224,7 +221,7 @@ fn solve_b(input: &str) -> isize {
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("16
+    assert_eq!(solve_a(&parse("16
                         10
                         15
                         5
@@ -234,9 +231,9 @@ fn smoke_a() {
                         19
                         6
                         12
-                        4"), 35);
+                        4").unwrap()), 35);
 
-    assert_eq!(solve_a("28
+    assert_eq!(solve_a(&parse("28
                         33
                         18
                         42
@@ -266,12 +263,12 @@ fn smoke_a() {
                         2
                         34
                         10
-                        3"), 220);
+                        3").unwrap()), 220);
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("16
+    assert_eq!(solve_b(&parse("16
                         10
                         15
                         5
@@ -281,9 +278,9 @@ fn smoke_b() {
                         19
                         6
                         12
-                        4"), 8);
+                        4").unwrap()), 8);
 
-    assert_eq!(solve_b("28
+    assert_eq!(solve_b(&parse("28
                         33
                         18
                         42
@@ -313,5 +310,5 @@ fn smoke_b() {
                         2
                         34
                         10
-                        3"), 19208);
+                        3").unwrap()), 19208);
 }
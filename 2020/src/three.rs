@@ -56,41 +56,37 @@ In this example, traversing the map using this slope would cause you to encounte
 Starting at the top-left corner of your map and following a slope of right 3 and down 1, how many trees would you encounter?
 
 */
-use std::io::Read;
-use std::fs::File;
 
-fn main() {
-    let mut input = File::open("inputs/three.txt").unwrap();
-    let mut s = String::new();
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
 
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+/// Where the trees are, repeating off to the right.
+pub fn parse(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse(input, "'.' or '#'", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
 }
 
-fn solve(input: &str, dx: usize, dy: usize) -> usize {
-    let tree_map: Vec<Vec<bool>> = input.lines().map(|line| {
-        line.trim().chars().map(|c| c == '#').collect()
-    }).collect();
-
+fn solve(tree_map: &Grid<bool>, dx: isize, dy: isize) -> usize {
     let (mut x, mut y) = (0, 0);
-    let (rows, columns) = (tree_map.len(), tree_map[0].len());
     let mut trees_hit = 0;
 
     loop {
         x += dx;
         y += dy;
 
-        if y >= rows { return trees_hit }
+        if y >= tree_map.height() as isize { return trees_hit }
 
-        if tree_map[y][x % columns] {
+        if *tree_map.wrapping((x, y)) {
             trees_hit += 1;
         }
     }
 }
 
-fn solve_a(input: &str) -> usize {
-    solve(input, 3, 1)
+pub fn solve_a(tree_map: &Grid<bool>) -> usize {
+    solve(tree_map, 3, 1)
 }
 
 /*
@@ -111,17 +107,17 @@ In the above example, these slopes would find 2, 7, 3, 4, and 2 tree(s) respecti
 What do you get if you multiply together the number of trees encountered on each of the listed slopes?
 */
 
-fn solve_b(input: &str) -> usize {
-    solve(input, 1, 1) *
-    solve(input, 3, 1) *
-    solve(input, 5, 1) *
-    solve(input, 7, 1) *
-    solve(input, 1, 2)
+pub fn solve_b(tree_map: &Grid<bool>) -> usize {
+    solve(tree_map, 1, 1) *
+    solve(tree_map, 3, 1) *
+    solve(tree_map, 5, 1) *
+    solve(tree_map, 7, 1) *
+    solve(tree_map, 1, 2)
 }
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("..##.......
+    assert_eq!(solve_a(&parse("..##.......
                         #...#...#..
                         .#....#..#.
                         ..#.#...#.#
@@ -131,12 +127,12 @@ fn smoke_a() {
                         .#........#
                         #.##...#...
                         #...##....#
-                        .#..#...#.#"), 7);
+                        .#..#...#.#").unwrap()), 7);
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("..##.......
+    assert_eq!(solve_b(&parse("..##.......
                         #...#...#..
                         .#....#..#.
                         ..#.#...#.#
@@ -146,5 +142,5 @@ fn smoke_b() {
                         .#........#
                         #.##...#...
                         #...##....#
-                        .#..#...#.#"), 336);
+                        .#..#...#.#").unwrap()), 336);
 }
@@ -37,182 +37,92 @@ At the end of these instructions, the ship's Manhattan distance (sum of the abso
 
 Figure out where the navigation instructions lead. What is the Manhattan distance between that location and the ship's starting position?
 */
-#[macro_use]
-extern crate text_io;
 
-use std::io::Read;
-use std::fs::File;
-
-use Instruction::*;
-use Direction::*;
-
-fn main() {
-    let mut input = File::open("inputs/twelve.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
-}
+use aoc_common::grid::Offset;
+use aoc_common::heading::{self, Heading};
+use aoc_common::parse::{char_at, ParseError, Parser};
+use self::Instruction::*;
 
 #[derive(Debug, Clone, Copy)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     MoveForward(isize),
-    Move(Direction, isize),
+    Move(Heading, isize),
     Turn(isize),
 }
 
 #[derive(Debug)]
 struct Ship {
-    direction: Direction,
-    x: isize,
-    y: isize,
-    waypoint_x: isize,
-    waypoint_y: isize,
+    heading: Heading,
+    position: Offset,
+    /// Relative to the ship.
+    waypoint: Offset,
 }
 
 impl Ship {
     fn new() -> Self {
         Self {
-            direction: East,
-            x: 0,
-            y: 0,
-            waypoint_x: -10,
-            waypoint_y: -1
+            heading: Heading::East,
+            position: (0, 0),
+            waypoint: (10, -1),
         }
     }
 
     fn handle(&mut self, instruction: Instruction) {
         match instruction {
-            MoveForward(steps) => {
-                match self.direction {
-                    North => self.y -= steps,
-                    South => self.y += steps,
-                    East => self.x -= steps,
-                    West => self.x += steps,
-                }
-            }
-            Move(direction, steps) => {
-                match direction {
-                    North => self.y -= steps,
-                    South => self.y += steps,
-                    East => self.x -= steps,
-                    West => self.x += steps,
-                }
-            },
-            Turn(degrees) => {
-                let mut degrees = 360 + degrees;
-
-                while degrees > 0 {
-                    self.direction = match self.direction {
-                        North => East,
-                        South => West,
-                        East => South,
-                        West => North,
-                    };
-
-                    degrees -= 90;
-                }
-            }
+            MoveForward(steps) => self.position = self.heading.advance(self.position, steps),
+            Move(heading, steps) => self.position = heading.advance(self.position, steps),
+            Turn(degrees) => self.heading = self.heading.turn(degrees),
         }
     }
 
     fn follow_waypoint(&mut self, instruction: Instruction) {
         match instruction {
             MoveForward(count) => {
-                for _ in 0..count {
-                    self.x += self.waypoint_x;
-                    self.y += self.waypoint_y;
-                }
-            }
-            Move(direction, steps) => {
-                match direction {
-                    North => self.waypoint_y -= steps,
-                    South => self.waypoint_y += steps,
-                    East => self.waypoint_x -= steps,
-                    West => self.waypoint_x += steps,
-                }
-            },
-            Turn(degrees) => {
-                let mut degrees = 360 + degrees;
-
-                while degrees > 0 {
-                    let (before_x, before_y): (isize, isize) = (self.waypoint_x, self.waypoint_y);
-
-                    self.direction = match self.direction {
-                        North => {
-                            self.waypoint_y = -before_x;
-                            self.waypoint_x = before_y;
-
-                            East
-                        },
-                        South => {
-                            self.waypoint_y = -before_x;
-                            self.waypoint_x = before_y;
-
-                            West
-                        },
-                        East => {
-                            self.waypoint_y = -before_x;
-                            self.waypoint_x = before_y;
-
-                            South
-                        },
-                        West => {
-                            self.waypoint_y = -before_x;
-                            self.waypoint_x = before_y;
-
-                            North
-                        },
-                    };
-
-                    degrees -= 90;
-                }
+                let (x, y) = self.position;
+                let (dx, dy) = self.waypoint;
+                self.position = (x + dx * count, y + dy * count);
             }
+            Move(heading, steps) => self.waypoint = heading.advance(self.waypoint, steps),
+            Turn(degrees) => self.waypoint = heading::rotate(self.waypoint, degrees),
         }
     }
+
+    fn distance(&self) -> usize {
+        (self.position.0.abs() + self.position.1.abs()) as usize
+    }
 }
 
-fn parse_instructions(input: &str) -> Vec<Instruction> {
-    input.lines().map(|line| {
-        let instruction_type: char = line.trim().chars().next().unwrap();
-        let mut bytes = &mut line.trim().bytes().skip(1);
-        let value: isize = read!("{}", bytes);
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let p = Parser::new(input);
 
-        match instruction_type {
-            'F' => MoveForward(value),
+    p.lines(|line| {
+        let line = line.trim();
+        let mut chars = line.chars();
+        let instruction_type = chars.next().unwrap_or_default();
+        let value: isize = p.number(chars.as_str())?;
 
-            'N' => Move(North, value),
-            'E' => Move(East, value),
-            'S' => Move(South, value),
-            'W' => Move(West, value),
+        Ok(match instruction_type {
+            'F' => MoveForward(value),
 
-            'L' => Turn(-1 * value),
+            'L' | 'R' if value % 90 != 0 => return Err(p.error(chars.as_str(), "a multiple of 90 degrees")),
+            'L' => Turn(-value),
             'R' => Turn(value),
 
-            _ => unreachable!("unhandled character: {}", line),
-        }
-    }).collect()
-}
+            'N' | 'E' | 'S' | 'W' => Move(line[..1].parse().expect("a compass point"), value),
 
-fn solve_a(input: &str) -> usize {
-    let instructions = parse_instructions(input);
+            _ => return Err(p.error(char_at(line, 0), "one of N, S, E, W, L, R or F")),
+        })
+    })
+}
 
+pub fn solve_a(instructions: &[Instruction]) -> usize {
     let mut ship = Ship::new();
 
-    for instruction in instructions {
+    for &instruction in instructions {
         ship.handle(instruction);
     }
 
-    (ship.x.abs() + ship.y.abs()) as usize
+    ship.distance()
 }
 
 /*
@@ -245,32 +155,30 @@ After these operations, the ship's Manhattan distance from its starting position
 Figure out where the navigation instructions actually lead. What is the Manhattan distance between that location and the ship's starting position?
 */
 
-fn solve_b(input: &str) -> usize {
-    let instructions = parse_instructions(input);
-
+pub fn solve_b(instructions: &[Instruction]) -> usize {
     let mut ship = Ship::new();
 
-    for instruction in instructions {
+    for &instruction in instructions {
         ship.follow_waypoint(instruction);
     }
 
-    (ship.x.abs() + ship.y.abs()) as usize
+    ship.distance()
 }
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("F10
+    assert_eq!(solve_a(&parse("F10
                         N3
                         F7
                         R90
-                        F11"), 25);
+                        F11").unwrap()), 25);
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("F10
+    assert_eq!(solve_b(&parse("F10
                         N3
                         F7
                         R90
-                        F11"), 286);
+                        F11").unwrap()), 286);
 }
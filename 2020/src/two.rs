@@ -21,32 +21,48 @@ In the above example, 2 passwords are valid. The middle password, cdefg, is not;
 
 How many passwords are valid according to their policies?
 */
-#[macro_use]
-extern crate text_io;
 
-use std::io::Read;
-use std::fs::File;
+use aoc_common::parse::{char_at, ParseError, Parser};
 
-fn main() {
-    let mut input = File::open("inputs/two.txt").unwrap();
-    let mut s = String::new();
-
-    input.read_to_string(&mut s).unwrap();
-    println!("a: {}", solve_a(&s));
-    println!("b: {}", solve_b(&s));
+pub struct Policy {
+    min: usize,
+    max: usize,
+    allowed_char: char,
+    password: String,
 }
 
-fn solve_a(input: &str) -> usize {
-    input.lines().filter(|line| {
-        let mut bytes = &mut line.trim().bytes();
-        let min: usize = read!("{}-", bytes);
-        let max: usize = read!("{} ", bytes);
-        let allowed_char: char = read!("{}:", bytes);
-        let password: String = read!("{}", bytes);
+pub fn parse(input: &str) -> Result<Vec<Policy>, ParseError> {
+    let p = Parser::new(input);
+
+    p.lines(|line| {
+        // 1-3 a: abcde
+        let line = line.trim();
+        let mut split = line.split(' ');
+        let range = p.token(&mut split, line, "a range")?;
+        let mut bounds = range.splitn(2, '-');
+        let min = p.number(p.token(&mut bounds, range, "a minimum")?)?;
+        let max = p.number(p.token(&mut bounds, range, "\"-\"")?)?;
+
+        let allowed = p.token(&mut split, line, "a letter")?;
+        let rest = p.suffix(allowed, ":")?;
+        let mut chars = rest.chars();
+        let allowed_char = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(p.error(char_at(allowed, 0), "a single letter")),
+        };
+
+        let password = p.token(&mut split, line, "a password")?;
+        p.end(&mut split)?;
+
+        Ok(Policy { min, max, allowed_char, password: password.to_owned() })
+    })
+}
 
-        let actual = password.chars().filter(|&c| c == allowed_char).count();
+pub fn solve_a(policies: &[Policy]) -> usize {
+    policies.iter().filter(|policy| {
+        let actual = policy.password.chars().filter(|&c| c == policy.allowed_char).count();
 
-        actual >= min && actual <= max
+        actual >= policy.min && actual <= policy.max
     }).count()
 }
 
@@ -68,36 +84,27 @@ Given the same example list from above:
 How many passwords are valid according to the new interpretation of the policies?
 */
 
-fn solve_b(input: &str) -> usize {
-    input.lines().filter(|line| {
-        let mut bytes = &mut line.trim().bytes();
-        let min: usize = read!("{}-", bytes);
-        let max: usize = read!("{} ", bytes);
-        let allowed_char: char = read!("{}:", bytes);
-        let password: String = read!("{}", bytes);
-
-        let first = password.as_bytes().get(min - 1).filter(|&&c| c == allowed_char as u8);
-        let last = password.as_bytes().get(max - 1).filter(|&&c| c == allowed_char as u8);
-
-        match (first, last) {
-            (Some(_), Some(_)) => false,
-            (None, None) => false,
-            _ => true
-        }
+pub fn solve_b(policies: &[Policy]) -> usize {
+    policies.iter().filter(|policy| {
+        let password = policy.password.as_bytes();
+        let first = password.get(policy.min.wrapping_sub(1)).filter(|&&c| c == policy.allowed_char as u8);
+        let last = password.get(policy.max.wrapping_sub(1)).filter(|&&c| c == policy.allowed_char as u8);
+
+        first.is_some() != last.is_some()
     }).count()
 }
 
 #[test]
 fn smoke_a() {
-    assert_eq!(solve_a("1-3 a: abcde
+    assert_eq!(solve_a(&parse("1-3 a: abcde
                         1-3 b: cdefg
-                        2-9 c: ccccccccc"), 2);
+                        2-9 c: ccccccccc").unwrap()), 2);
 }
 
 #[test]
 fn smoke_b() {
-    assert_eq!(solve_b("1-3 a: abcde
+    assert_eq!(solve_b(&parse("1-3 a: abcde
                         1-3 b: cdefg
                         2-9 c: ccccccccc
-                        2-5 c: xcxx"), 2);
+                        2-5 c: xcxx").unwrap()), 2);
 }
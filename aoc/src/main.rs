@@ -0,0 +1,10 @@
+extern crate advent_of_code_2017;
+extern crate advent_of_code_2020;
+extern crate aoc_common;
+
+fn main() {
+    aoc_common::runner::main(vec![
+        advent_of_code_2017::year(),
+        advent_of_code_2020::year(),
+    ]);
+}
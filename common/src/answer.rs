@@ -0,0 +1,53 @@
+use std::fmt::{self, Display};
+
+/// What a part solves to. Puzzles only ever ask for a whole number or a
+/// short string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! from_number {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    }
+}
+
+from_number!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(s: &'a str) -> Answer {
+        Answer::Text(s.to_owned())
+    }
+}
+
+#[test]
+fn test_from() {
+    assert_eq!(Answer::from(12u32), Answer::Number(12));
+    assert_eq!(Answer::from(-3isize), Answer::Number(-3));
+    assert_eq!(Answer::from(u64::MAX), Answer::Number(u64::MAX as i128));
+    assert_eq!(Answer::from("abc"), Answer::Text("abc".to_string()));
+    assert_eq!(Answer::from(-3isize).to_string(), "-3");
+}
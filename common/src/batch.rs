@@ -0,0 +1,207 @@
+//! Running a day over many inputs at once, such as everyone's on a team, to
+//! catch solutions that only hold for the input they were written against.
+//!
+//! Inputs are picked out by path: a file, a directory of them, or a pattern
+//! such as `inputs/2020/eight/*.txt` whose last part has `*` and `?` in it.
+//! A whole year's inputs live in a directory per day, named like the day
+//! (`eight`) or numbered (`8`).
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use answer::Answer;
+use cancel::Token;
+use generate::catch;
+use report::human_duration;
+use solution::{Failure, Part, Solution};
+
+/// How one part went on one input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Solved(Answer, Duration),
+    TimedOut(Duration),
+    Failed(String),
+}
+
+impl Cell {
+    pub fn is_solved(&self) -> bool {
+        matches!(*self, Cell::Solved(..))
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Cell::Solved(ref answer, duration) => write!(f, "{} ({})", answer, human_duration(duration)),
+            Cell::TimedOut(after) => write!(f, "TIMED OUT after {}", human_duration(after)),
+            Cell::Failed(ref e) => write!(f, "FAILED {}", e),
+        }
+    }
+}
+
+/// Every part of a day, run on one input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub input: PathBuf,
+    /// Each part the day has, or why the input couldn't be read or parsed.
+    pub parts: Result<Vec<(Part, Cell)>, String>,
+}
+
+impl Row {
+    pub fn is_solved(&self) -> bool {
+        match self.parts {
+            Ok(ref parts) => parts.iter().all(|(_, cell)| cell.is_solved()),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Reads and solves one input, catching panics so that one bad input
+/// doesn't stop the rest.
+pub fn run(solution: &dyn Solution, path: &Path, budget: Duration) -> Row {
+    let parts = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|input| solve(solution, &input, budget));
+
+    Row { input: path.to_owned(), parts }
+}
+
+fn solve(solution: &dyn Solution, input: &str, budget: Duration) -> Result<Vec<(Part, Cell)>, String> {
+    let parsed = match catch(|| solution.parse(input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(e)) => return Err(e.to_string()),
+        Err(e) => return Err(e),
+    };
+    let mut cells = vec![];
+
+    for &part in &[Part::A, Part::B] {
+        if solution.is_placeholder(part) {
+            continue;
+        }
+
+        let token = Token::within(budget);
+        let start = Instant::now();
+        let cell = match catch(|| solution.solve_within(part, &parsed, &token)) {
+            Ok(Some(Ok(answer))) => Cell::Solved(answer, start.elapsed()),
+            Ok(Some(Err(Failure::Cancelled))) => Cell::TimedOut(start.elapsed()),
+            Ok(Some(Err(failure))) => Cell::Failed(failure.to_string()),
+            Ok(None) => continue,
+            Err(e) => Cell::Failed(e),
+        };
+        cells.push((part, cell));
+    }
+
+    Ok(cells)
+}
+
+/// The input files `path` stands for, in order.
+pub fn expand(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_owned()]);
+    }
+
+    let (dir, pattern) = match path.file_name().and_then(|name| name.to_str()) {
+        _ if path.is_dir() => (path, "*"),
+        Some(name) if name.contains(['*', '?']) => (path.parent().unwrap_or_else(|| Path::new("")), name),
+        _ => return Err(io::Error::new(ErrorKind::NotFound, format!("no inputs at {}", path.display()))),
+    };
+    let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let matched = path.file_name().and_then(|name| name.to_str()).is_some_and(|name| matches(pattern, name));
+        if matched && path.is_file() {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Where a day's inputs are under a directory holding a whole year's.
+pub fn day_dir(root: &Path, solution: &dyn Solution) -> Option<PathBuf> {
+    [solution.name().to_string(), solution.day().to_string()].iter()
+        .map(|name| root.join(name))
+        .find(|dir| dir.is_dir())
+}
+
+/// Whether `name` matches `pattern`, where `*` stands for any run of
+/// characters and `?` for any one.
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    // The furthest `*` so far, and where in the name it started matching.
+    let (mut p, mut n, mut star) = (0, 0, None);
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[test]
+fn test_matches() {
+    assert!(matches("*.txt", "alice.txt"));
+    assert!(matches("*", ""));
+    assert!(matches("a?c*.t*t", "abcdef.txt"));
+    assert!(!matches("*.txt", "alice.txt.bak"));
+    assert!(!matches("?", ""));
+    assert!(!matches("bob", "bobby"));
+}
+
+#[test]
+fn test_batch() {
+    use std::env;
+    use solution::Day;
+
+    let dir = env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+    fs::create_dir_all(dir.join("one")).unwrap();
+    fs::write(dir.join("one/alice.txt"), "12").unwrap();
+    fs::write(dir.join("one/bob.txt"), "120").unwrap();
+    fs::write(dir.join("one/notes.md"), "").unwrap();
+
+    let day = Day::new(1, "one")
+        .part_a(|input: &str| input.len())
+        .part_b(|input: &str| -> usize { assert!(!input.ends_with('0'), "assumed no zeroes"); 0 });
+    let inputs = day_dir(&dir, &day).unwrap();
+    assert_eq!(inputs, dir.join("one"));
+
+    let paths = expand(&inputs.join("*.txt")).unwrap();
+    assert_eq!(paths, [dir.join("one/alice.txt"), dir.join("one/bob.txt")]);
+    assert_eq!(expand(&inputs).unwrap().len(), 3);
+    assert!(expand(&dir.join("two")).is_err());
+
+    let rows = paths.iter().map(|path| run(&day, path, Duration::from_secs(1))).collect::<Vec<_>>();
+    assert!(rows[0].is_solved());
+    assert!(!rows[1].is_solved());
+    assert_eq!(rows[1].parts.as_ref().unwrap()[1], (Part::B, Cell::Failed("panicked: assumed no zeroes".to_string())));
+
+    let row = run(&day.placeholder(Part::B), &paths[1], Duration::from_secs(1));
+    assert!(row.is_solved());
+    assert_eq!(row.parts.unwrap().iter().map(|&(part, _)| part).collect::<Vec<_>>(), [Part::A]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
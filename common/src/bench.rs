@@ -0,0 +1,238 @@
+//! Repeated timings of each stage of a day, and baselines to compare them
+//! against.
+//!
+//! Baselines live in each year's `bench.tsv`, one
+//! `<day>\t<stage>\t<median ns>\t<spread ns>` line per stage. They are
+//! machine specific so aren't checked in.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use report::human_duration;
+use solution::{Part, Year};
+
+const HEADER: &str = "# day\tstage\tmedian_ns\tspread_ns";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Stage::Parse => f.pad("parse"),
+            Stage::Part(Part::A) => f.pad("a"),
+            Stage::Part(Part::B) => f.pad("b"),
+        }
+    }
+}
+
+impl Stage {
+    fn parse(name: &str) -> Option<Stage> {
+        match name {
+            "parse" => Some(Stage::Parse),
+            "a" => Some(Stage::Part(Part::A)),
+            "b" => Some(Stage::Part(Part::B)),
+            _ => None,
+        }
+    }
+}
+
+/// The median of a set of timings, and their median absolute deviation
+/// from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub median: Duration,
+    pub spread: Duration,
+    pub runs: usize,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "no samples to summarise");
+
+        let median = median(&mut samples);
+        let mut deviations = samples.iter()
+            .map(|&sample| sample.abs_diff(median))
+            .collect::<Vec<_>>();
+        let spread = self::median(&mut deviations);
+
+        Stats { median, spread, runs: samples.len() }
+    }
+
+    /// How this compares with `baseline`, given how far either way counts
+    /// as a real change rather than noise.
+    pub fn compare(&self, baseline: &Stats, threshold: f64) -> Change {
+        let ratio = self.median.as_nanos() as f64 / baseline.median.as_nanos().max(1) as f64 - 1.0;
+        let noise = self.spread.max(baseline.spread);
+        let difference = self.median.abs_diff(baseline.median);
+
+        if ratio.abs() < threshold || difference <= noise * 2 {
+            Change::Unchanged(ratio)
+        } else if ratio > 0.0 {
+            Change::Regressed(ratio)
+        } else {
+            Change::Improved(ratio)
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ± {}", human_duration(self.median), human_duration(self.spread))
+    }
+}
+
+fn median(samples: &mut [Duration]) -> Duration {
+    samples.sort();
+
+    let mid = samples.len() / 2;
+    if samples.len().is_multiple_of(2) {
+        (samples[mid - 1] + samples[mid]) / 2
+    } else {
+        samples[mid]
+    }
+}
+
+/// The relative change in median, as a fraction of the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    Unchanged(f64),
+    Improved(f64),
+    Regressed(f64),
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Change::Unchanged(ratio) => write!(f, "{:+.1}%", ratio * 100.0),
+            Change::Improved(ratio) => write!(f, "{:+.1}% improved", ratio * 100.0),
+            Change::Regressed(ratio) => write!(f, "{:+.1}% REGRESSED", ratio * 100.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    path: PathBuf,
+    stats: BTreeMap<(u8, Stage), Stats>,
+}
+
+impl Baseline {
+    pub fn path(year: &Year) -> PathBuf {
+        Path::new(year.dir).join("bench.tsv")
+    }
+
+    /// Reads the baseline at `path`; a missing file is an empty baseline.
+    pub fn load<P: Into<PathBuf>>(path: P) -> io::Result<Baseline> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(ref e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut stats = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = parse_line(line).ok_or_else(|| {
+                let message = format!("{}:{}: malformed baseline entry {:?}", path.display(), i + 1, line);
+                io::Error::new(ErrorKind::InvalidData, message)
+            })?;
+            stats.insert(key, value);
+        }
+
+        Ok(Baseline { path, stats })
+    }
+
+    pub fn get(&self, day: u8, stage: Stage) -> Option<&Stats> {
+        self.stats.get(&(day, stage))
+    }
+
+    pub fn insert(&mut self, day: u8, stage: Stage, stats: Stats) {
+        self.stats.insert((day, stage), stats);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+
+        for (&(day, stage), stats) in &self.stats {
+            text.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                day, stage, stats.median.as_nanos(), stats.spread.as_nanos()
+            ));
+        }
+
+        fs::write(&self.path, text)
+    }
+}
+
+fn parse_line(line: &str) -> Option<((u8, Stage), Stats)> {
+    let mut fields = line.split('\t');
+
+    let day = fields.next()?.parse().ok()?;
+    let stage = Stage::parse(fields.next()?)?;
+    let median = Duration::from_nanos(fields.next()?.parse().ok()?);
+    let spread = Duration::from_nanos(fields.next()?.parse().ok()?);
+
+    Some(((day, stage), Stats { median, spread, runs: 0 }))
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+
+    let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3), ms(2), ms(100)]);
+    assert_eq!(stats.median, ms(3));
+    assert_eq!(stats.spread, ms(2));
+    assert_eq!(stats.runs, 5);
+
+    assert_eq!(Stats::from_samples(vec![ms(4), ms(2)]).median, ms(3));
+}
+
+#[test]
+fn test_compare() {
+    let stats = |median, spread| Stats {
+        median: Duration::from_millis(median),
+        spread: Duration::from_millis(spread),
+        runs: 10,
+    };
+
+    let baseline = stats(100, 2);
+    assert!(matches!(stats(105, 1).compare(&baseline, 0.1), Change::Unchanged(_)));
+    assert_eq!(stats(150, 1).compare(&baseline, 0.1), Change::Regressed(0.5));
+    assert_eq!(stats(50, 1).compare(&baseline, 0.1), Change::Improved(-0.5));
+    // Too noisy to call either way.
+    assert_eq!(stats(150, 30).compare(&baseline, 0.1), Change::Unchanged(0.5));
+}
+
+#[test]
+fn test_round_trip() {
+    use std::env;
+
+    let path = env::temp_dir().join(format!("aoc-bench-{}.tsv", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut baseline = Baseline::load(&path).unwrap();
+    let stats = Stats::from_samples(vec![Duration::from_micros(10), Duration::from_micros(12)]);
+    baseline.insert(15, Stage::Parse, stats);
+    baseline.insert(15, Stage::Part(Part::B), stats);
+    baseline.save().unwrap();
+
+    let baseline = Baseline::load(&path).unwrap();
+    assert_eq!(baseline.get(15, Stage::Parse).map(|s| s.median), Some(stats.median));
+    assert_eq!(baseline.get(15, Stage::Part(Part::B)).map(|s| s.spread), Some(stats.spread));
+    assert!(baseline.get(15, Stage::Part(Part::A)).is_none());
+
+    fs::remove_file(&path).unwrap();
+}
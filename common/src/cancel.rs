@@ -0,0 +1,87 @@
+//! Cooperative cancellation, so that a part which runs over its time budget
+//! is stopped rather than hanging the rest of a batch.
+//!
+//! The runner hands each part a [`Token`] that expires once the part's
+//! budget is spent. Loops that can spin for a long time, or forever on bad
+//! input, call [`Token::check`] as they go and give up with [`Cancelled`]
+//! when it fails. Parts that never check simply run to completion.
+
+use std::cell::Cell;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+/// How many checks go by between looks at the clock, which keeps
+/// [`Token::check`] cheap enough for the innermost loops.
+const CLOCK_EVERY: u32 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+#[derive(Debug)]
+pub struct Token {
+    deadline: Option<Instant>,
+    cancelled: Cell<bool>,
+    countdown: Cell<u32>,
+}
+
+impl Token {
+    /// A token that is never cancelled.
+    pub fn none() -> Token {
+        Token { deadline: None, cancelled: Cell::new(false), countdown: Cell::new(0) }
+    }
+
+    /// A token that is cancelled once `budget` has passed.
+    pub fn within(budget: Duration) -> Token {
+        Token { deadline: Instant::now().checked_add(budget), ..Token::none() }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    /// Whether the token has been cancelled or its deadline has passed.
+    pub fn is_cancelled(&self) -> bool {
+        if !self.cancelled.get() && self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.cancelled.set(true);
+        }
+
+        self.cancelled.get()
+    }
+
+    /// Fails once the token is cancelled, only looking at the clock every
+    /// so often.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        let countdown = self.countdown.get();
+
+        if countdown > 0 {
+            self.countdown.set(countdown - 1);
+            return if self.cancelled.get() { Err(Cancelled) } else { Ok(()) };
+        }
+
+        self.countdown.set(CLOCK_EVERY);
+        if self.is_cancelled() { Err(Cancelled) } else { Ok(()) }
+    }
+}
+
+#[test]
+fn test_token() {
+    let token = Token::none();
+    assert!((0..5000).all(|_| token.check().is_ok()));
+    token.cancel();
+    assert_eq!(token.check(), Err(Cancelled));
+
+    let token = Token::within(Duration::from_millis(20));
+    assert!(token.check().is_ok());
+    std::thread::sleep(Duration::from_millis(30));
+    assert!(token.is_cancelled());
+
+    let token = Token::within(Duration::from_millis(0));
+    let checks = (0..).position(|_| token.check().is_err()).unwrap();
+    assert!(checks <= CLOCK_EVERY as usize);
+}
@@ -0,0 +1,190 @@
+//! Differential testing of days that solve a part more than one way.
+//!
+//! A day registers its other implementations of a part as alternatives,
+//! such as a straight translation of a puzzle's program next to the
+//! optimised version that is actually run. [`compare`] runs all of them on
+//! the same input and reports the first part they disagree on. Inputs come
+//! from the day's examples, its real input and its generator, if it has one.
+
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use answer::Answer;
+use cancel::Token;
+use examples::Examples;
+use generate::{catch, Rng};
+use solution::{Failure, Part, Solution, Year};
+
+/// The name the registered implementation of a part goes by.
+pub const MAIN: &str = "main";
+
+/// What one implementation made of an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    TimedOut,
+    Failed(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Outcome::Solved(ref answer) => write!(f, "{}", answer),
+            Outcome::TimedOut => write!(f, "timed out"),
+            Outcome::Failed(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// The implementations of a part that didn't all give the same answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub part: Part,
+    /// What each implementation gave, the registered one first.
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "part {} disagrees:", self.part)?;
+        for (i, (name, outcome)) in self.outcomes.iter().enumerate() {
+            write!(f, "{} {} {}", if i > 0 { "," } else { "" }, name, outcome)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a part went on one input: whether every implementation agreed, or
+/// some of them ran out of time so there was nothing to compare.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Comparison {
+    Agreed,
+    Inconclusive,
+    Diverged(Divergence),
+}
+
+/// Runs every implementation of each of `parts` that has alternatives on
+/// `input`, stopping at the first part they disagree on.
+///
+/// Running out of `budget` is only slow rather than wrong, so a part where
+/// fewer than two implementations finish is inconclusive. A panic is a
+/// disagreement like any other.
+pub fn compare(solution: &dyn Solution, input: &str, parts: &[Part], budget: Duration) -> Result<Comparison, String> {
+    let parsed = match catch(|| solution.parse(input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(e)) => return Err(e.diagnostic(input)),
+        Err(e) => return Err(e),
+    };
+    let mut comparison = Comparison::Agreed;
+
+    for &part in parts {
+        let alternatives = solution.alternatives(part);
+        if alternatives.is_empty() {
+            continue;
+        }
+
+        let run = |name: &str| {
+            let token = Token::within(budget);
+            let solved = catch(|| match name {
+                MAIN => solution.solve_within(part, &parsed, &token),
+                name => solution.solve_alternative(part, name, &parsed, &token),
+            });
+
+            match solved {
+                Ok(Some(Ok(answer))) => Outcome::Solved(answer),
+                Ok(Some(Err(Failure::Cancelled))) => Outcome::TimedOut,
+                Ok(Some(Err(failure))) => Outcome::Failed(failure.to_string()),
+                Ok(None) => Outcome::Failed("not registered".to_string()),
+                Err(e) => Outcome::Failed(e),
+            }
+        };
+        let outcomes = Some(MAIN).into_iter().chain(alternatives)
+            .map(|name| (name, run(name)))
+            .collect::<Vec<_>>();
+
+        let finished = outcomes.iter()
+            .map(|(_, outcome)| outcome)
+            .filter(|&outcome| *outcome != Outcome::TimedOut)
+            .collect::<Vec<_>>();
+        if finished.iter().any(|&outcome| outcome != finished[0]) {
+            return Ok(Comparison::Diverged(Divergence { part, outcomes }));
+        }
+        if finished.len() < 2 {
+            comparison = Comparison::Inconclusive;
+        }
+    }
+
+    Ok(comparison)
+}
+
+/// Compares every day with alternatives on its examples and a few small
+/// generated inputs, listing where they disagreed.
+pub fn check(year: &Year) -> Vec<String> {
+    let examples = Examples::load(Examples::path(year)).unwrap_or_else(|e| panic!("examples.tsv: {}", e));
+    let budget = Duration::from_secs(10);
+    let mut failures = vec![];
+
+    for solution in year.days() {
+        if solution.alternatives(Part::A).is_empty() && solution.alternatives(Part::B).is_empty() {
+            continue;
+        }
+
+        let mut inputs = examples.day(solution.day())
+            .map(|example| (format!("{:?}", example.input), example.input.clone(), vec![example.part]))
+            .collect::<Vec<_>>();
+        for &size in &[1, 10] {
+            for seed in 0..3 {
+                if let Some(input) = solution.generate(&mut Rng::new(seed), size) {
+                    inputs.push((format!("size {} seed {}", size, seed), input, vec![Part::A, Part::B]));
+                }
+            }
+        }
+
+        for (name, input, parts) in inputs {
+            match compare(solution, &input, &parts, budget) {
+                Ok(Comparison::Diverged(divergence)) => {
+                    failures.push(format!("{} {:>2} {}: {}", year.year, solution.day(), name, divergence))
+                }
+                Err(e) => failures.push(format!("{} {:>2} {}: {}", year.year, solution.day(), name, e)),
+                Ok(_) => {}
+            }
+        }
+    }
+
+    failures
+}
+
+#[test]
+fn test_compare() {
+    use solution::Day;
+
+    let day = Day::new(1, "one")
+        .part_a(|input: &str| input.len())
+        .alternative_a("chars", |input: &str| input.chars().count())
+        .alternative_a_cancellable("forever", |_: &str, token: &Token| -> Result<usize, ::cancel::Cancelled> {
+            loop {
+                token.check()?;
+            }
+        });
+    let budget = Duration::from_millis(10);
+
+    assert_eq!(compare(&day, "abc", &[Part::A, Part::B], budget), Ok(Comparison::Agreed));
+    assert_eq!(compare(&day, "día", &[Part::A], budget), Ok(Comparison::Diverged(Divergence {
+        part: Part::A,
+        outcomes: vec![
+            ("main", Outcome::Solved(Answer::Number(4))),
+            ("chars", Outcome::Solved(Answer::Number(3))),
+            ("forever", Outcome::TimedOut),
+        ],
+    })));
+
+    let day = Day::new(1, "one")
+        .part_a(|input: &str| input.len())
+        .alternative_a("panics", |input: &str| -> usize { panic!("{}", input) });
+    match compare(&day, "abc", &[Part::A], budget) {
+        Ok(Comparison::Diverged(divergence)) => {
+            assert_eq!(divergence.to_string(), "part a disagrees: main 3, panics panicked: abc")
+        }
+        comparison => panic!("{:?}", comparison),
+    }
+}
@@ -0,0 +1,556 @@
+//! Worked examples from the puzzle text at the top of each day's source.
+//!
+//! `extract` turns the text's indented examples into candidates, pairing each
+//! input with whatever answers the text around it states, such as the `5` in
+//! `the accumulator is 5`. A candidate only becomes a fixture in the year's
+//! `examples.tsv` once the solution agrees with one of those answers, so the
+//! file never holds a guess. Examples the text only gives inline can be added
+//! to the file by hand.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use generate::catch;
+use solution::{Part, Solution, Year};
+
+const HEADER: &str = "# day\tpart\tinput\tanswer";
+
+/// What separates an example from its answer in a list such as
+/// `1122 produces a sum of 3`.
+const SEPARATORS: [&str; 5] = [", ", " produces ", " is ", " becomes ", " results in "];
+
+/// Words that come just before an answer the text states, as in `the
+/// largest value is 1` or `a total of 7`.
+const STATING: [&str; 13] = ["is", "are", "was", "were", "be", "of", "has", "have", "gives", "produces", "becomes", "answer", "="];
+
+const NUMBERS: [&str; 13] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+impl Example {
+    /// Runs the solution on the example, giving what it got instead if that
+    /// was wrong.
+    pub fn check(&self, solution: &dyn Solution) -> Result<(), String> {
+        match solution.solve(self.part, &self.input) {
+            Ok(Some(Ok(ref answer))) if answer.to_string() == self.answer => Ok(()),
+            Ok(Some(Ok(answer))) => Err(answer.to_string()),
+            Ok(Some(Err(failure))) => Err(failure.to_string()),
+            Ok(None) => Err(format!("no part {}", self.part)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// An example input from the puzzle text, with every answer the text might
+/// be giving for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub part: Part,
+    pub input: String,
+    pub answers: Vec<String>,
+    pub kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// One line of a list of examples.
+    Listed,
+    /// A paragraph, or run of paragraphs, of the text.
+    Block,
+    /// A block from part one, tried against part two's text.
+    Reused,
+}
+
+impl Candidate {
+    /// Runs the solution on the candidate, keeping it as an example if the
+    /// answer is one the text gives. Candidates are often not inputs at all,
+    /// so parse errors, panics and finding no answer just mean no, as does a
+    /// placeholder part.
+    pub fn confirm(&self, solution: &dyn Solution) -> Option<Example> {
+        if solution.is_placeholder(self.part) {
+            return None;
+        }
+
+        let result = catch(|| solution.solve(self.part, &self.input));
+        let answer = result.ok()?.ok()??.ok()?.to_string();
+        if !self.answers.contains(&answer) {
+            return None;
+        }
+
+        Some(Example { day: solution.day(), part: self.part, input: self.input.clone(), answer })
+    }
+}
+
+/// The source file holding a day's puzzle text.
+pub fn source_path(year: &Year, solution: &dyn Solution) -> PathBuf {
+    Path::new(year.dir).join("src").join(format!("{}.rs", solution.name()))
+}
+
+/// The examples in a day's puzzle text that the solution agrees with.
+///
+/// Text often walks through the states between an example and its answer,
+/// which lead to the same answer, so only the first block giving each answer
+/// is kept. Part one's blocks are only tried for part two when it has no
+/// example of its own, as its prose is full of small numbers.
+pub fn find(solution: &dyn Solution, source: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let mut answered = BTreeSet::new();
+
+    for candidate in extract(source) {
+        if candidate.kind == Kind::Reused && examples.iter().any(|e| e.part == Part::B) {
+            continue;
+        }
+
+        if let Some(example) = candidate.confirm(solution) {
+            if candidate.kind != Kind::Listed && !answered.insert((example.part, example.answer.clone())) {
+                continue;
+            }
+            if examples.iter().all(|e| (e.part, &e.input) != (example.part, &example.input)) {
+                examples.push(example);
+            }
+        }
+    }
+
+    examples
+}
+
+/// Finds the candidate examples in a day's source.
+///
+/// The puzzle text has lost its formatting, so any paragraph could be an
+/// example: each one is a candidate whose answer is stated in the prose
+/// after it, as is each run of paragraphs with no sentences in it. Indented
+/// paragraphs are lists, and where every line reads `<input>, <answer>` each
+/// line is a candidate of its own. Part two often reuses part one's example,
+/// so those paragraphs are tried against part two's prose too, but only for
+/// answers it states outright, as in `the answer is 8`.
+pub fn extract(source: &str) -> Vec<Candidate> {
+    let mut candidates = vec![];
+    let mut blocks_a = vec![];
+
+    for (part, text) in puzzle_text(source) {
+        let paragraphs = paragraphs(text);
+
+        for (i, lines) in paragraphs.iter().enumerate() {
+            let items = lines.iter()
+                .map(|line| Some(line).filter(|line| indented(line)).and_then(|line| list_item(line)))
+                .collect::<Option<Vec<_>>>();
+
+            if let Some(items) = items {
+                for (input, rest) in items {
+                    if let Some(answer) = stated_answer(rest) {
+                        candidates.push(Candidate { part, input: input.to_owned(), answers: vec![answer], kind: Kind::Listed });
+                    }
+                }
+                continue;
+            }
+
+            let run = paragraphs[i..].iter().take_while(|lines| !prose(lines)).count();
+            let mut ends = vec![i + 1];
+            if run > 1 && (i == 0 || prose(&paragraphs[i - 1])) {
+                ends.insert(0, i + run);
+            }
+
+            for end in ends {
+                let input = paragraphs[i..end].iter().map(|lines| lines.join("\n")).collect::<Vec<_>>().join("\n\n");
+                if part == Part::A {
+                    blocks_a.push(input.clone());
+                }
+                candidates.push(Candidate { part, input, answers: stated_in(&paragraphs[end..], false), kind: Kind::Block });
+            }
+        }
+
+        if part == Part::B {
+            let answers = stated_in(&paragraphs, true);
+            for input in blocks_a.drain(..) {
+                candidates.push(Candidate { part, input, answers: answers.clone(), kind: Kind::Reused });
+            }
+        }
+    }
+
+    candidates
+}
+
+/// The `/* */` blocks holding the text of each part.
+fn puzzle_text(source: &str) -> Vec<(Part, &str)> {
+    let mut texts = vec![];
+    let mut rest = source;
+
+    while let Some(start) = rest.find("/*") {
+        let after = &rest[start + 2..];
+        let end = match after.find("*/") {
+            Some(end) => end,
+            None => break,
+        };
+        let text = &after[..end];
+        rest = &after[end + 2..];
+
+        if text.contains("Part Two ---") {
+            texts.push((Part::B, text));
+        } else if text.contains("--- Day ") {
+            texts.push((Part::A, text));
+        }
+    }
+
+    texts
+}
+
+/// Splits puzzle text into runs of non-blank lines, leaving out the
+/// `--- Day 1 ---` style headings.
+fn paragraphs(text: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs = vec![];
+    let mut lines = vec![];
+
+    for line in text.lines().map(str::trim_end).chain(Some("")) {
+        if !line.is_empty() {
+            lines.push(line);
+        } else if !lines.is_empty() {
+            paragraphs.push(lines.split_off(0));
+        }
+    }
+
+    paragraphs.retain(|lines| !(lines.len() == 1 && lines[0].starts_with("--") && lines[0].ends_with(" ---")));
+    paragraphs
+}
+
+fn indented(line: &str) -> bool {
+    line.starts_with("    ") || line.starts_with('\t')
+}
+
+/// Whether a paragraph has a sentence in it, so reads like prose rather
+/// than an example.
+fn prose(lines: &[&str]) -> bool {
+    lines.iter().any(|line| {
+        let line = line.trim();
+        line.ends_with(|c| ".:?!".contains(c)) && line.split_whitespace().count() > 3
+    })
+}
+
+/// Splits `<input><separator><rest>` at the first separator.
+fn list_item(line: &str) -> Option<(&str, &str)> {
+    SEPARATORS.iter()
+        .filter_map(|separator| line.find(separator).map(|i| (i, separator.len())))
+        .min()
+        .map(|(i, len)| (line[..i].trim(), &line[i + len..]))
+        .filter(|&(input, _)| !input.is_empty())
+}
+
+/// The answer a list item gives: whatever follows its last `=`, a count of
+/// one or zero for a valid or invalid line, or otherwise its first number.
+fn stated_answer(rest: &str) -> Option<String> {
+    let rest = rest.trim();
+
+    if let Some(i) = rest.rfind('=') {
+        return words(&rest[i + 1..]).next().map(str::to_owned);
+    }
+    if rest.starts_with("not ") || rest.starts_with("invalid") {
+        return Some("0".to_owned());
+    }
+    if rest.starts_with("valid") || rest.starts_with("a valid") {
+        return Some("1".to_owned());
+    }
+
+    words(rest).find(|word| word.parse::<i64>().is_ok()).map(str::to_owned)
+}
+
+/// The answers `paragraphs` state: the word just after one of [`STATING`],
+/// or just before it if that isn't a number, and unless `strict`, the last
+/// number written in digits in each sentence, leaving out asides in
+/// brackets. Small numbers that are spelled out are given as digits too.
+fn stated_in(paragraphs: &[Vec<&str>], strict: bool) -> Vec<String> {
+    let mut answers = vec![];
+    let mut add = |word: &str| {
+        let number = NUMBERS.iter().position(|n| n.eq_ignore_ascii_case(word)).map(|n| n.to_string());
+        for answer in Some(word.to_owned()).into_iter().chain(number) {
+            if !answer.is_empty() && !answers.contains(&answer) {
+                answers.push(answer);
+            }
+        }
+    };
+
+    for lines in paragraphs {
+        for sentence in sentences(&lines.join(" ")) {
+            let tokens = sentence.split_whitespace().collect::<Vec<_>>();
+            let is_number = |word: &str| word.parse::<i64>().is_ok() || NUMBERS.iter().any(|n| n.eq_ignore_ascii_case(word));
+
+            let last_number = tokens.iter().rev().map(|token| clean(token)).find(|word| word.parse::<i64>().is_ok());
+            if let Some(last) = last_number.filter(|_| !strict) {
+                add(last);
+            }
+
+            for (i, &token) in tokens.iter().enumerate() {
+                if token != "=" && !STATING.iter().any(|s| s.eq_ignore_ascii_case(clean(token))) {
+                    continue;
+                }
+                if let Some(next) = tokens.get(i + 1) {
+                    add(clean(next));
+                }
+                if let Some(before) = i.checked_sub(1).map(|i| clean(tokens[i])).filter(|&word| !is_number(word)) {
+                    add(before);
+                }
+            }
+        }
+    }
+
+    answers
+}
+
+/// Splits text into sentences, leaving out anything in brackets.
+fn sentences(text: &str) -> Vec<String> {
+    let mut sentences = vec![String::new()];
+    let mut depth = 0usize;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
+            '.' | '?' | '!' | ';' if chars.peek().is_none_or(|c| c.is_whitespace()) => sentences.push(String::new()),
+            c => sentences.last_mut().expect("never empty").push(c),
+        }
+    }
+
+    sentences
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split_whitespace().map(clean).filter(|word| !word.is_empty())
+}
+
+/// A word without the punctuation around it.
+fn clean(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '-').trim_end_matches('-')
+}
+
+/// The examples saved for a year.
+#[derive(Debug, Clone, Default)]
+pub struct Examples {
+    path: PathBuf,
+    examples: Vec<Example>,
+}
+
+impl Examples {
+    pub fn path(year: &Year) -> PathBuf {
+        Path::new(year.dir).join("examples.tsv")
+    }
+
+    /// Reads the examples at `path`; a missing file has none.
+    pub fn load<P: Into<PathBuf>>(path: P) -> io::Result<Examples> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(ref e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut examples = vec![];
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            examples.push(parse_line(line).ok_or_else(|| {
+                let message = format!("{}:{}: malformed example {:?}", path.display(), i + 1, line);
+                io::Error::new(ErrorKind::InvalidData, message)
+            })?);
+        }
+
+        Ok(Examples { path, examples })
+    }
+
+    pub fn day(&self, day: u8) -> impl Iterator<Item = &Example> {
+        self.examples.iter().filter(move |example| example.day == day)
+    }
+
+    /// Adds an example unless that day and part already has one for the same
+    /// input.
+    pub fn insert(&mut self, example: Example) -> bool {
+        let exists = self.examples.iter().any(|e| {
+            (e.day, e.part, &e.input) == (example.day, example.part, &example.input)
+        });
+
+        if !exists {
+            self.examples.push(example);
+        }
+        !exists
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut examples = self.examples.iter().collect::<Vec<_>>();
+        examples.sort_by_key(|example| (example.day, example.part));
+
+        let mut text = String::from(HEADER);
+        text.push('\n');
+
+        for example in examples {
+            text.push_str(&format!("{}\t{}\t{}\t{}\n", example.day, example.part, escape(&example.input), example.answer));
+        }
+
+        fs::write(&self.path, text)
+    }
+}
+
+/// Runs every saved example for `year`, describing each one that fails.
+pub fn check(year: &Year) -> io::Result<Vec<String>> {
+    check_with(year, &|input| input.to_string())
+}
+
+/// Runs every saved example for `year` after `edit`ing its input, such as
+/// to save it with different line endings.
+pub fn check_with(year: &Year, edit: &dyn Fn(&str) -> String) -> io::Result<Vec<String>> {
+    let examples = Examples::load(Examples::path(year))?;
+    let mut failures = vec![];
+
+    for solution in year.days() {
+        for example in examples.day(solution.day()) {
+            let example = Example { input: edit(&example.input), ..example.clone() };
+            if let Err(got) = example.check(solution) {
+                failures.push(format!("{} {:>2} {}  {:?}: expected {}, got {}",
+                    year.year, example.day, example.part, example.input, example.answer, got));
+            }
+        }
+    }
+
+    Ok(failures)
+}
+
+fn escape(input: &str) -> String {
+    input.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut chars = field.chars();
+
+    while let Some(c) = chars.next() {
+        text.push(match c {
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '\\' => '\\',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+
+    Some(text)
+}
+
+fn parse_line(line: &str) -> Option<Example> {
+    let mut fields = line.splitn(4, '\t');
+
+    let day = fields.next()?.parse().ok()?;
+    let part = match fields.next()? {
+        "a" => Part::A,
+        "b" => Part::B,
+        _ => return None,
+    };
+    let input = unescape(fields.next()?)?;
+    let answer = fields.next()?.to_owned();
+
+    Some(Example { day, part, input, answer })
+}
+
+#[test]
+fn test_extract() {
+    use parse::Parser;
+    use solution::Day;
+
+    let source = "/*
+--- Day 3: Toboggan Trajectory ---
+
+Here are some streams and the number of groups they contain:
+
+    {}, 1 group.
+    {{},{}}, also 3 groups.
+    <>, empty garbage.
+
+For example, in this map:
+
+..#
+#..
+
+.#.
+
+There are three trees.
+*/
+
+fn solve() {}
+
+/*
+--- Part Two ---
+
+The same map has 6 open squares in total.
+*/";
+
+    let listed = extract(source).into_iter().filter(|candidate| candidate.kind == Kind::Listed).collect::<Vec<_>>();
+    assert_eq!(listed.iter().map(|c| (&*c.input, &*c.answers)).collect::<Vec<_>>(), vec![
+        ("{}", &["1".to_owned()][..]),
+        ("{{},{}}", &["3".to_owned()][..]),
+    ]);
+
+    let trees = "..#\n#..\n\n.#.";
+    assert!(extract(source).contains(&Candidate {
+        part: Part::A,
+        input: trees.to_owned(),
+        answers: vec!["three".to_owned(), "3".to_owned(), "There".to_owned()],
+        kind: Kind::Block,
+    }));
+
+    let day = Day::new(3, "three")
+        .parse(|input: &str| {
+            let p = Parser::new(input);
+            match input.find(|c| !".#\n".contains(c)) {
+                Some(i) => Err(p.error(&input[i..], "a map")),
+                None => Ok(input.to_owned()),
+            }
+        })
+        .part_a(|map: &String| map.matches('#').count())
+        .part_b(|map: &String| map.matches('.').count());
+
+    let example = |part, answer: &str| Example { day: 3, part, input: trees.to_owned(), answer: answer.to_owned() };
+    assert_eq!(find(&day, source), vec![example(Part::A, "3"), example(Part::B, "6")]);
+
+    // Only answers the text states count, not every number in it.
+    let rules = vec![vec!["It now takes five or more visible seats (not 4) for one to empty, and the", "result is 26 seats."]];
+    assert_eq!(stated_in(&rules, false), ["26", "result"]);
+    assert_eq!(stated_in(&[vec!["Program 1 sends 1, 2, 1."]], true), Vec::<String>::new());
+
+    assert_eq!(list_item("aa bb aa is not valid - aa appears twice."), Some(("aa bb aa", "not valid - aa appears twice.")));
+    assert_eq!(stated_answer("not valid - aa appears twice."), Some("0".to_owned()));
+    assert_eq!(stated_answer("a sum of 3 (1 + 2) because"), Some("3".to_owned()));
+}
+
+#[test]
+fn test_round_trip() {
+    use std::env;
+
+    let path = env::temp_dir().join(format!("aoc-examples-{}.tsv", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut examples = Examples::load(&path).unwrap();
+    let example = Example { day: 19, part: Part::A, input: "  |\n \\+-\tA".to_owned(), answer: "A".to_owned() };
+    assert!(examples.insert(example.clone()));
+    assert!(!examples.insert(Example { answer: "B".to_owned(), ..example.clone() }));
+    examples.save().unwrap();
+
+    let examples = Examples::load(&path).unwrap();
+    assert_eq!(examples.day(19).collect::<Vec<_>>(), vec![&example]);
+    assert_eq!(examples.day(1).count(), 0);
+
+    fs::write(&path, "19\ta\tbad\\x\tA\n").unwrap();
+    assert_eq!(Examples::load(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    fs::remove_file(&path).unwrap();
+}
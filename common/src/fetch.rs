@@ -0,0 +1,393 @@
+//! Talking to the puzzle site with a session token, for inputs and answers.
+//!
+//! The session is the `session` cookie of a logged in browser, taken from
+//! `$AOC_SESSION`. Requests go to `$AOC_BASE_URL`, or the Advent of Code
+//! site itself if that's unset, so that everything can be pointed at a stub
+//! server. Plain `http://` is spoken directly; `https://` goes through
+//! `curl`, which keeps the crate free of a TLS stack. The session is handed
+//! to `curl` as a config on its stdin rather than as an argument, where any
+//! other user could read it.
+//!
+//! Fetched inputs are cached as the year's `inputs/<name>.txt`, and a day
+//! with a cached input is never fetched again. An empty one, such as the
+//! placeholder `aoc new` leaves, doesn't count. Days don't need to be
+//! registered to be fetched, so an input can be had before the day is
+//! started.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use solution::Year;
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!("aoc/", env!("CARGO_PKG_VERSION"), " (input fetcher)");
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// There's no session to fetch with.
+    NoSession,
+    /// The base URL isn't one we know how to talk to.
+    BadUrl(String),
+    /// The server answered, but not with a success.
+    Status { url: String, status: u16, body: String },
+    /// The request couldn't be made, or the cache couldn't be written.
+    Io { context: String, error: io::Error },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FetchError::NoSession => {
+                write!(f, "no session to fetch with, set {} to the session cookie", SESSION_VAR)
+            }
+            FetchError::BadUrl(ref url) => write!(f, "can't fetch from {}, only http:// and https:// are supported", url),
+            FetchError::Status { ref url, status, ref body } => {
+                write!(f, "{} answered {}", url, status)?;
+                match body.lines().map(str::trim).find(|line| !line.is_empty()) {
+                    Some(line) => write!(f, ": {}", line),
+                    None => Ok(()),
+                }
+            }
+            FetchError::Io { ref context, ref error } => write!(f, "{}: {}", context, error),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Talks to the puzzle site, or whatever stands in for it.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new<S: Into<String>, T: Into<String>>(base_url: S, session: T) -> Client {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Client { base_url, session: session.into().trim().to_string() }
+    }
+
+    /// A client for `$AOC_BASE_URL`, falling back to the real site, with the
+    /// session in `$AOC_SESSION`.
+    pub fn from_env() -> Result<Client, FetchError> {
+        let session = env::var(SESSION_VAR).ok()
+            .filter(|session| !session.trim().is_empty())
+            .ok_or(FetchError::NoSession)?;
+        let base_url = env::var(BASE_URL_VAR).ok()
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+
+        Ok(Client::new(base_url, session))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// A day's puzzle input.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// Sends an answer for one level of a day, returning the page that
+    /// answers it.
+    pub fn answer(&self, year: u16, day: u8, level: u8, answer: &str) -> Result<String, FetchError> {
+        self.post(&format!("/{}/day/{}/answer", year, day), &[("level", &level.to_string()), ("answer", answer)])
+    }
+
+    /// The body of a successful GET of `path`, relative to the base URL.
+    pub fn get(&self, path: &str) -> Result<String, FetchError> {
+        self.request(path, None)
+    }
+
+    /// The body of a successful POST of `form` to `path`.
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, FetchError> {
+        let form = form.iter()
+            .map(|&(name, value)| format!("{}={}", url_encode(name), url_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        self.request(path, Some(&form))
+    }
+
+    /// A GET, or a POST if there's a form to send.
+    fn request(&self, path: &str, form: Option<&str>) -> Result<String, FetchError> {
+        let url = format!("{}{}", self.base_url, path);
+
+        let (status, body) = if url.starts_with("http://") {
+            self.plain(&url, form)?
+        } else if url.starts_with("https://") {
+            self.curl(&url, form)?
+        } else {
+            return Err(FetchError::BadUrl(self.base_url.clone()));
+        };
+
+        if status / 100 == 2 {
+            Ok(body)
+        } else {
+            Err(FetchError::Status { url, status, body })
+        }
+    }
+
+    /// An HTTP/1.1 request over a fresh connection.
+    fn plain(&self, url: &str, form: Option<&str>) -> Result<(u16, String), FetchError> {
+        let io = |error| FetchError::Io { context: format!("could not fetch {}", url), error };
+
+        let rest = &url["http://".len()..];
+        let (host, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, "/"),
+        };
+        let address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+
+        let mut stream = TcpStream::connect(&*address).map_err(io)?;
+        stream.set_read_timeout(Some(TIMEOUT)).map_err(io)?;
+        let mut request = format!("{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n",
+                                  if form.is_some() { "POST" } else { "GET" }, path, host, USER_AGENT, self.session);
+        if let Some(form) = form {
+            request += &format!("Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n", form.len());
+        }
+        request += "\r\n";
+        request += form.unwrap_or("");
+        stream.write_all(request.as_bytes()).map_err(io)?;
+
+        let mut response = vec![];
+        stream.read_to_end(&mut response).map_err(io)?;
+
+        parse_response(&response).ok_or_else(|| {
+            io(io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP response"))
+        })
+    }
+
+    /// A request through `curl`, which prints the status after the body.
+    fn curl(&self, url: &str, form: Option<&str>) -> Result<(u16, String), FetchError> {
+        let io = |error| FetchError::Io { context: format!("could not run curl for {}", url), error };
+
+        let mut command = Command::new("curl");
+        command.args(["--silent", "--show-error", "--location", "--max-time", "30"])
+            .args(["--user-agent", USER_AGENT])
+            .args(["--config", "-"]);
+        if let Some(form) = form {
+            command.args(["--data-raw", form]);
+        }
+        let mut child = command.args(["--write-out", "\n%{http_code}", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(io)?;
+        child.stdin.take().expect("stdin is piped").write_all(curl_config(&self.session).as_bytes()).map_err(io)?;
+        let output = child.wait_with_output().map_err(io)?;
+
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(io(io::Error::other(message)));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = match stdout.rfind('\n') {
+            Some(i) => (&stdout[..i], stdout[i + 1..].trim()),
+            None => ("", stdout.trim()),
+        };
+
+        match status.parse() {
+            Ok(status) => Ok((status, body.to_string())),
+            Err(_) => Err(io(io::Error::new(io::ErrorKind::InvalidData, "curl printed no status"))),
+        }
+    }
+}
+
+/// A `curl` config sending the session cookie, quoted so that nothing in it
+/// is taken for another option.
+fn curl_config(session: &str) -> String {
+    format!("cookie = \"session={}\"\n", session.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Percent-encodes everything but the unreserved characters.
+fn url_encode(text: &str) -> String {
+    text.bytes().map(|byte| match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+        _ => format!("%{:02X}", byte),
+    }).collect()
+}
+
+/// The status and body of a raw HTTP/1.1 response.
+fn parse_response(response: &[u8]) -> Option<(u16, String)> {
+    let split = response.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let head = String::from_utf8_lossy(&response[..split]);
+    let body = &response[split + 4..];
+
+    let mut lines = head.lines();
+    let status = lines.next()?.split(' ').nth(1)?.parse().ok()?;
+    let chunked = lines.any(|line| {
+        let line = line.to_ascii_lowercase();
+        line.starts_with("transfer-encoding:") && line.contains("chunked")
+    });
+
+    let body = if chunked { dechunk(body)? } else { body.to_vec() };
+    Some((status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+/// Joins the chunks of a `Transfer-Encoding: chunked` body.
+fn dechunk(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut out = vec![];
+
+    loop {
+        let line_end = body.windows(2).position(|w| w == b"\r\n")?;
+        let size = String::from_utf8_lossy(&body[..line_end]);
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        body = &body[line_end + 2..];
+
+        if size == 0 {
+            return Some(out);
+        }
+
+        out.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+/// Where the input of the day called `name` is cached.
+pub fn cache_path(year: &Year, name: &str) -> PathBuf {
+    Path::new(year.dir).join("inputs").join(format!("{}.txt", name))
+}
+
+/// Makes sure a day's input is cached, fetching it only if it isn't yet or
+/// what's cached is empty. Returns the cached path and whether it had to be
+/// fetched.
+pub fn fetch(client: &Client, year: &Year, day: u8, name: &str) -> Result<(PathBuf, bool), FetchError> {
+    let path = cache_path(year, name);
+    if fs::read_to_string(&path).is_ok_and(|input| !input.trim().is_empty()) {
+        return Ok((path, false));
+    }
+
+    let input = client.input(year.year, day)?;
+
+    let io = |error| FetchError::Io { context: format!("could not cache {}", path.display()), error };
+    let partial = path.with_extension("txt.partial");
+    fs::create_dir_all(path.parent().unwrap()).map_err(io)?;
+    fs::write(&partial, input).map_err(io)?;
+    fs::rename(&partial, &path).map_err(io)?;
+
+    Ok((path, true))
+}
+
+/// Serves `responses` in order, one per connection, sending each request
+/// it saw back down the channel.
+#[cfg(test)]
+pub fn stub_server(responses: Vec<&'static str>) -> (String, ::std::sync::mpsc::Receiver<String>) {
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    // Whether the headers have been read, and as much body as they promise.
+    fn complete(request: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(request);
+        text.find("\r\n\r\n").is_some_and(|end| {
+            let length = text.lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .map_or(0, |length| length.parse().unwrap());
+            text.len() >= end + 4 + length
+        })
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, requests) = mpsc::channel();
+
+    thread::spawn(move || {
+        for (stream, response) in listener.incoming().zip(responses) {
+            let mut stream = stream.unwrap();
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+            while !complete(&request) {
+                let n = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..n]);
+            }
+
+            stream.write_all(response.as_bytes()).unwrap();
+            sender.send(String::from_utf8(request).unwrap()).unwrap();
+        }
+    });
+
+    (url, requests)
+}
+
+#[cfg(test)]
+fn scratch_year(name: &str) -> Year {
+    let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let dir: &'static str = Box::leak(dir.to_str().unwrap().to_string().into_boxed_str());
+    Year::new(2017, dir)
+}
+
+#[test]
+fn test_fetch_caches() {
+    let (url, requests) = stub_server(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\n3,4,1,5\n",
+    ]);
+    let client = Client::new(url + "/", "abc123\n");
+    let year = scratch_year("caches");
+
+    // Left empty by `aoc new`, so it still needs fetching.
+    fs::create_dir_all(Path::new(year.dir).join("inputs")).unwrap();
+    fs::write(cache_path(&year, "ten"), "").unwrap();
+
+    let (path, fetched) = fetch(&client, &year, 10, "ten").unwrap();
+    assert!(fetched);
+    assert_eq!(path, Path::new(year.dir).join("inputs/ten.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "3,4,1,5\n");
+
+    let request = requests.recv().unwrap();
+    assert!(request.starts_with("GET /2017/day/10/input HTTP/1.1\r\n"));
+    assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+
+    // The stub would refuse a second connection, so this has to come
+    // from the cache.
+    assert_eq!(fetch(&client, &year, 10, "ten").unwrap(), (path, false));
+
+    fs::remove_dir_all(year.dir).unwrap();
+}
+
+#[test]
+fn test_fetch_errors() {
+    let (url, _requests) = stub_server(vec![
+        "HTTP/1.1 404 Not Found\r\n\r\nPlease don't repeatedly request this endpoint before it unlocks!\n",
+    ]);
+    let client = Client::new(url.clone(), "abc123");
+    let year = scratch_year("errors");
+
+    let message = fetch(&client, &year, 25, "twenty_five").unwrap_err().to_string();
+    assert_eq!(message, format!("{}/2017/day/25/input answered 404: Please don't repeatedly request this endpoint before it unlocks!", url));
+    assert!(!cache_path(&year, "twenty_five").exists());
+
+    let client = Client::new("ftp://example.com", "abc123");
+    assert!(matches!(client.input(2017, 25), Err(FetchError::BadUrl(_))));
+
+    fs::remove_dir_all(year.dir).unwrap();
+}
+
+#[test]
+fn test_parse_response() {
+    let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n3,4,\r\n4\r\n1,5\n\r\n0\r\n\r\n";
+    assert_eq!(parse_response(chunked), Some((200, "3,4,1,5\n".to_string())));
+
+    assert_eq!(parse_response(b"HTTP/1.1 400 Bad Request\r\n\r\n"), Some((400, String::new())));
+    assert_eq!(parse_response(b"HTTP/1.1 200 OK\r\n"), None);
+    assert_eq!(url_encode("a b&c=d/é"), "a%20b%26c%3Dd%2F%C3%A9");
+    assert_eq!(curl_config("ab\"c\\d"), "cookie = \"session=ab\\\"c\\\\d\"\n");
+}
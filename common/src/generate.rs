@@ -0,0 +1,192 @@
+//! Random but valid puzzle inputs, for stress testing days well beyond the
+//! one real input and the examples.
+//!
+//! Days register a generator with `Day::generate`, which builds an input of
+//! a given size from an [`Rng`]. What the size counts is up to the day, such
+//! as instructions, bag rules or particles. Every input comes from a seed,
+//! so one that trips a day up can be made again with
+//! `aoc generate <year> <day> --size <n> --seed <seed>`.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use cancel::Token;
+use solution::{Failure, Part, Solution, Year};
+
+/// A small, seedable source of randomness (splitmix64), which is plenty for
+/// making up inputs.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    /// A seed that differs from run to run.
+    pub fn seed_from_time() -> u64 {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        now.as_secs() ^ u64::from(now.subsec_nanos())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "nothing below 0 to choose from");
+
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `low..=high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "empty range {}..={}", low, high);
+
+        let width = (high - low) as u64 + 1;
+        low + (self.next_u64() % width) as i64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Parses a generated input and solves each part of it, returning how long
+/// each part took, or what went wrong: a parse error, a panic, a part
+/// running past `budget` or one finding no answer.
+pub fn trial(solution: &dyn Solution, input: &str, budget: Duration) -> Result<Vec<(Part, Duration)>, String> {
+    catch(|| {
+        let parsed = solution.parse(input).map_err(|e| e.diagnostic(input))?;
+        let mut timings = vec![];
+
+        for &part in &[Part::A, Part::B] {
+            let token = Token::within(budget);
+            let start = Instant::now();
+            match solution.solve_within(part, &parsed, &token) {
+                Some(Ok(_)) => timings.push((part, start.elapsed())),
+                Some(Err(Failure::Cancelled)) => return Err(format!("part {} timed out after {:?}", part, budget)),
+                Some(Err(failure)) => return Err(format!("part {} found {}", part, failure)),
+                None => {}
+            }
+        }
+
+        Ok(timings)
+    }).and_then(|result| result)
+}
+
+thread_local! {
+    /// Whether this thread is inside `catch`, where panics are expected.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+/// Runs `f`, turning a panic into an error rather than letting it print.
+///
+/// The panic hook is wrapped the first time this is called, and from then
+/// on only stays quiet on a thread that's inside `catch`, so that panics on
+/// any other thread still print, and nothing swaps the hook back and forth.
+pub(crate) fn catch<R, F: FnOnce() -> R>(f: F) -> Result<R, String> {
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    let quiet = QUIET.with(|quiet| quiet.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.with(|q| q.set(quiet));
+
+    result.map_err(|payload| format!("panicked: {}", panic_message(&*payload)))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload.downcast_ref::<String>().map_or("<unknown>", |message| message),
+    }
+}
+
+/// Runs every generator the year has on a few small inputs, listing the
+/// seeds and sizes that some day couldn't handle.
+pub fn check(year: &Year) -> Vec<String> {
+    let mut failures = vec![];
+
+    for solution in year.days() {
+        for &size in &[1, 20] {
+            for seed in 0..2 {
+                let input = match solution.generate(&mut Rng::new(seed), size) {
+                    Some(input) => input,
+                    None => break,
+                };
+
+                if let Err(e) = trial(solution, &input, Duration::from_secs(10)) {
+                    failures.push(format!("{} {:>2} size {} seed {}: {}", year.year, solution.day(), size, seed, e));
+                }
+            }
+        }
+    }
+
+    failures
+}
+
+#[test]
+fn test_rng() {
+    let mut rng = Rng::new(7);
+    let first = (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>();
+    let mut again = Rng::new(7);
+    assert_eq!((0..5).map(|_| again.next_u64()).collect::<Vec<_>>(), first);
+
+    assert!((0..1000).all(|_| rng.below(3) < 3));
+    assert!((0..1000).all(|_| (-2..=2).contains(&rng.range(-2, 2))));
+    assert!((0..100).all(|_| !rng.chance(0.0)));
+
+    let mut items = (0..20).collect::<Vec<_>>();
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, (0..20).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_trial() {
+    use solution::Day;
+
+    let day = Day::new(1, "one")
+        .part_a(|input: &str| input.len())
+        .part_b(|input: &str| -> usize { panic!("{} is too long", input.len()) });
+
+    assert_eq!(trial(&day, "abc", Duration::from_secs(1)), Err("panicked: 3 is too long".to_string()));
+}
+
+#[test]
+fn test_catch() {
+    let nested = catch(|| catch(|| -> usize { panic!("inner") }));
+
+    assert_eq!(nested, Ok(Err("panicked: inner".to_string())));
+    assert!(!QUIET.with(Cell::get));
+}
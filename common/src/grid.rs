@@ -0,0 +1,288 @@
+//! Rectangular grids of cells, as so many puzzles are drawn on.
+//!
+//! Points are `(x, y)` pairs with `x` counting columns from the left and `y`
+//! rows from the top, and moving between them takes an [`Offset`] so that
+//! falling off an edge is an `Option` rather than a wrapped-around `usize`.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use parse::{char_at, ParseError, Parser};
+
+/// A cell's column and row.
+pub type Point = (usize, usize);
+
+/// A step between cells, `(dx, dy)`.
+pub type Offset = (isize, isize);
+
+/// Up, right, down and left.
+pub const NEIGHBOURS_4: [Offset; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Clockwise from the top left, diagonals included.
+pub const NEIGHBOURS_8: [Offset; 8] = [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+        where T: Clone
+    {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// A grid of the given rows, with any that fall short of the longest
+    /// padded out with `fill`.
+    pub fn from_rows(rows: Vec<Vec<T>>, fill: T) -> Grid<T>
+        where T: Clone
+    {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.extend(row);
+        }
+
+        Grid { width, height, cells }
+    }
+
+    /// Parses every non-empty line of `input`, trimmed, as a row of cells,
+    /// with `cell` giving `None` for characters that are not `expected`.
+    /// Every row must be as wide as the first.
+    pub fn parse<F>(input: &str, expected: &str, cell: F) -> Result<Grid<T>, ParseError>
+        where F: Fn(char) -> Option<T>
+    {
+        let p = Parser::new(input);
+        let mut width = None;
+        let mut cells = vec![];
+
+        let rows = p.lines(|line| {
+            let line = line.trim();
+            for (i, c) in line.char_indices() {
+                cells.push(cell(c).ok_or_else(|| p.error(char_at(line, i), expected))?);
+            }
+
+            let columns = line.chars().count();
+            match *width.get_or_insert(columns) {
+                width if width != columns => Err(p.error_after(line, format!("a row of {} cells", width))),
+                _ => Ok(()),
+            }
+        })?;
+
+        Ok(Grid { width: width.unwrap_or(0), height: rows.len(), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Point) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Point) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `(x, y)` on a grid that repeats forever in every
+    /// direction.
+    pub fn wrapping(&self, (x, y): Offset) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+
+        &self[(x, y)]
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} of {}", x, self.width);
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Every point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The point `offset` away from `point`, unless that's off the grid.
+    pub fn step(&self, (x, y): Point, (dx, dy): Offset) -> Option<Point> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+
+        Some((x, y))
+    }
+
+    /// The points up, right, down and left of `point` that are on the grid.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        NEIGHBOURS_4.iter().filter_map(move |&offset| self.step(point, offset))
+    }
+
+    /// The up to eight points around `point`, diagonals included.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        NEIGHBOURS_8.iter().filter_map(move |&offset| self.step(point, offset))
+    }
+
+    /// The points from `point` out to the edge of the grid, `offset` at a
+    /// time, not including `point` itself.
+    pub fn ray(&self, point: Point, offset: Offset) -> impl Iterator<Item = Point> + '_ {
+        let mut point = Some(point);
+
+        std::iter::from_fn(move || {
+            point = self.step(point?, offset);
+            point
+        })
+    }
+
+    /// A copy of the `width` by `height` block whose top left is `(x, y)`.
+    pub fn window(&self, (x, y): Point, width: usize, height: usize) -> Grid<T>
+        where T: Clone
+    {
+        let cells = (y..y + height).flat_map(|y| self.row(y)[x..x + width].iter().cloned()).collect();
+
+        Grid { width, height, cells }
+    }
+
+    /// Turned a quarter clockwise.
+    pub fn rotated(&self) -> Grid<T>
+        where T: Clone
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| (x, y)))
+            .map(|point| self[point].clone())
+            .collect();
+
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /// Mirrored left to right.
+    pub fn flipped(&self) -> Grid<T>
+        where T: Clone
+    {
+        let cells = self.rows().flat_map(|row| row.iter().rev().cloned()).collect();
+
+        Grid { width: self.width, height: self.height, cells }
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!("{:?} is off a {}x{} grid", point, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{:?} is off a {}x{} grid", point, width, height),
+        }
+    }
+}
+
+/// A line per row, drawn as the cells display themselves.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn parse(input: &str) -> Grid<char> {
+    Grid::parse(input, "'.' or '#'", |c| Some(c).filter(|&c| c == '.' || c == '#')).unwrap()
+}
+
+#[test]
+fn test_parse() {
+    let grid = parse("#..\n.#.\n");
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(1, 1)], '#');
+    assert_eq!(grid.row(0), ['#', '.', '.']);
+    assert_eq!(grid.column(0).collect::<String>(), "#.");
+    assert_eq!(grid.to_string(), "#..\n.#.");
+
+    let error = Grid::parse("#..\n.x.", "'.' or '#'", |c| Some(c).filter(|&c| c != 'x')).unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (2, 2, "x"));
+    let error = Grid::parse("#..\n.#", "'.' or '#'", Some).unwrap_err();
+    assert_eq!((error.line, error.column, &*error.expected), (2, 3, "a row of 3 cells"));
+
+    let padded = Grid::from_rows(vec![vec!['a'], vec!['b', 'c']], ' ');
+    assert_eq!(padded.to_string(), "a \nbc");
+}
+
+#[test]
+fn test_neighbours() {
+    let grid = Grid::new(3, 3, 0);
+
+    assert_eq!(grid.neighbours_4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+    assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+    assert_eq!(grid.neighbours_8((2, 0)).collect::<Vec<_>>(), [(2, 1), (1, 1), (1, 0)]);
+    assert_eq!(grid.step((0, 0), (-1, 0)), None);
+    assert_eq!(grid.ray((0, 0), (1, 1)).collect::<Vec<_>>(), [(1, 1), (2, 2)]);
+    assert_eq!(grid.ray((0, 2), (0, 1)).count(), 0);
+}
+
+#[test]
+fn test_transforms() {
+    let grid = parse(".#.\n..#\n###");
+
+    assert_eq!(*grid.wrapping((-1, 4)), '#');
+    assert_eq!(grid.rotated().to_string(), "#..\n#.#\n##.");
+    assert_eq!(grid.flipped().to_string(), ".#.\n#..\n###");
+    assert_eq!(grid.window((1, 1), 2, 2).to_string(), ".#\n##");
+    assert_eq!(grid.map(|&c| c == '#').iter().filter(|&&on| on).count(), 5);
+    assert_eq!(grid.rotated().rotated().rotated().rotated(), grid);
+}
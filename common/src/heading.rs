@@ -0,0 +1,189 @@
+//! Which way something on a grid is facing, and how it turns.
+//!
+//! Headings follow grids in having `y` grow downwards, so north is `(0, -1)`
+//! and turning right is turning clockwise. The diagonals step one both ways,
+//! so north-east is `(1, -1)`.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use grid::Offset;
+use parse::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Heading {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+use self::Heading::*;
+
+impl Heading {
+    /// Clockwise from north.
+    pub const ALL: [Heading; 8] = [North, NorthEast, East, SouthEast, South, SouthWest, West, NorthWest];
+
+    /// Clockwise from north, leaving out the diagonals.
+    pub const CARDINAL: [Heading; 4] = [North, East, South, West];
+
+    fn from_index(i: isize) -> Heading {
+        Heading::ALL[i.rem_euclid(8) as usize]
+    }
+
+    fn index(self) -> isize {
+        self as isize
+    }
+
+    /// A quarter turn anticlockwise.
+    pub fn left(self) -> Heading {
+        self.turn(-90)
+    }
+
+    /// A quarter turn clockwise.
+    pub fn right(self) -> Heading {
+        self.turn(90)
+    }
+
+    pub fn reverse(self) -> Heading {
+        self.turn(180)
+    }
+
+    /// Turned `degrees` clockwise, or anticlockwise if negative, which must
+    /// be a multiple of 45.
+    pub fn turn(self, degrees: isize) -> Heading {
+        assert!(degrees % 45 == 0, "can only turn headings by multiples of 45 degrees, not {}", degrees);
+
+        Heading::from_index(self.index() + degrees / 45)
+    }
+
+    /// Whether this is one of north, east, south or west.
+    pub fn is_cardinal(self) -> bool {
+        self.index() % 2 == 0
+    }
+
+    /// A single step this way.
+    pub fn offset(self) -> Offset {
+        match self {
+            North => (0, -1),
+            NorthEast => (1, -1),
+            East => (1, 0),
+            SouthEast => (1, 1),
+            South => (0, 1),
+            SouthWest => (-1, 1),
+            West => (-1, 0),
+            NorthWest => (-1, -1),
+        }
+    }
+
+    /// `steps` steps this way from `(x, y)`.
+    pub fn advance(self, (x, y): Offset, steps: isize) -> Offset {
+        let (dx, dy) = self.offset();
+
+        (x + dx * steps, y + dy * steps)
+    }
+}
+
+/// How many quarter turns clockwise `degrees` is.
+fn quarters(degrees: isize) -> isize {
+    assert!(degrees % 90 == 0, "can only turn by multiples of 90 degrees, not {}", degrees);
+
+    degrees / 90
+}
+
+/// `offset` turned `degrees` clockwise about the origin, or anticlockwise if
+/// negative, which must be a multiple of 90.
+pub fn rotate((x, y): Offset, degrees: isize) -> Offset {
+    match quarters(degrees).rem_euclid(4) {
+        0 => (x, y),
+        1 => (-y, x),
+        2 => (-x, -y),
+        _ => (y, -x),
+    }
+}
+
+/// A compass point, like `n`, `ne` or `northeast`, or up, right, down or
+/// left, as the first letter or the whole word, in either case. The error is
+/// located within `s`, so parsers with more context may want to move it.
+impl FromStr for Heading {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Heading, ParseError> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "n" | "north" | "u" | "up" => North,
+            "ne" | "northeast" => NorthEast,
+            "e" | "east" | "r" | "right" => East,
+            "se" | "southeast" => SouthEast,
+            "s" | "south" | "d" | "down" => South,
+            "sw" | "southwest" => SouthWest,
+            "w" | "west" | "l" | "left" => West,
+            "nw" | "northwest" => NorthWest,
+            _ => return Err(ParseError::at(s, s, "a heading, such as N, NE, U or north")),
+        })
+    }
+}
+
+impl Display for Heading {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match *self {
+            North => "N",
+            NorthEast => "NE",
+            East => "E",
+            SouthEast => "SE",
+            South => "S",
+            SouthWest => "SW",
+            West => "W",
+            NorthWest => "NW",
+        })
+    }
+}
+
+#[test]
+fn test_turns() {
+    assert_eq!(North.left(), West);
+    assert_eq!(West.right(), North);
+    assert_eq!(East.reverse(), West);
+    assert_eq!(North.turn(270), West);
+    assert_eq!(South.turn(-450), East);
+    assert_eq!(Heading::ALL.iter().map(|h| h.turn(360)).collect::<Vec<_>>(), Heading::ALL);
+    assert_eq!(North.turn(45), NorthEast);
+    assert_eq!(NorthEast.right(), SouthEast);
+    assert_eq!(SouthWest.reverse(), NorthEast);
+    assert_eq!(West.turn(-135), SouthEast);
+    assert_eq!(Heading::ALL.iter().filter(|h| h.is_cardinal()).cloned().collect::<Vec<_>>(), Heading::CARDINAL);
+    assert_eq!(NorthWest.advance((0, 0), 2), (-2, -2));
+    assert_eq!(East.advance((1, 1), 3), (4, 1));
+    assert_eq!(North.advance((0, 0), 2), (0, -2));
+
+    // 10 east and 4 north, turned right, is 4 east and 10 south.
+    assert_eq!(rotate((10, -4), 90), (4, 10));
+    assert_eq!(rotate((10, -4), -270), (4, 10));
+    assert_eq!(rotate((10, -4), 180), (-10, 4));
+    assert_eq!(rotate(East.offset(), -90), North.offset());
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!("N".parse(), Ok(North));
+    assert_eq!("r".parse(), Ok(East));
+    assert_eq!("down".parse(), Ok(South));
+    assert_eq!("W".parse(), Ok(West));
+    assert_eq!("north".parse(), Ok(North));
+
+    assert_eq!("ne".parse(), Ok(NorthEast));
+    assert_eq!("SE".parse(), Ok(SouthEast));
+    assert_eq!("southwest".parse(), Ok(SouthWest));
+    assert_eq!("Nw".parse(), Ok(NorthWest));
+    for heading in &Heading::ALL {
+        assert_eq!(heading.to_string().parse(), Ok(*heading));
+    }
+
+    let error = "nne".parse::<Heading>().unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (1, 1, "nne"));
+    assert_eq!(error.expected, "a heading, such as N, NE, U or north");
+    assert_eq!(West.to_string(), "W");
+}
@@ -0,0 +1,263 @@
+//! Hexagonal grids, in axial coordinates.
+//!
+//! A [`Hex`] is a `(q, r)` pair, the third cube coordinate `s` always being
+//! `-q - r`. The six neighbours are the same steps whichever way up the
+//! hexagons are drawn, and the [`Orientation`] only decides what the steps
+//! are called: flat-topped hexagons have `n` and `s` neighbours where
+//! pointy-topped ones have `e` and `w`.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+/// The six steps to a neighbour, clockwise from the one up and to the right.
+pub const DIRECTIONS: [Hex; 6] = [
+    Hex { q: 1, r: -1 },
+    Hex { q: 1, r: 0 },
+    Hex { q: 0, r: 1 },
+    Hex { q: -1, r: 1 },
+    Hex { q: -1, r: 0 },
+    Hex { q: 0, r: -1 },
+];
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub fn new(q: i64, r: i64) -> Hex {
+        Hex { q, r }
+    }
+
+    /// From cube coordinates, which must add up to 0.
+    pub fn from_cube((q, r, s): (i64, i64, i64)) -> Hex {
+        assert_eq!(q + r + s, 0, "({}, {}, {}) isn't a cube coordinate", q, r, s);
+
+        Hex { q, r }
+    }
+
+    pub fn cube(self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn s(self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// How many steps away the origin is.
+    pub fn length(self) -> i64 {
+        self.q.abs().max(self.r.abs()).max(self.s().abs())
+    }
+
+    pub fn distance(self, other: Hex) -> i64 {
+        (self - other).length()
+    }
+
+    /// Clockwise from the one up and to the right.
+    pub fn neighbours(self) -> [Hex; 6] {
+        DIRECTIONS.map(|direction| self + direction)
+    }
+
+    /// Every hex exactly `radius` steps away, clockwise from the one
+    /// `radius` steps along `DIRECTIONS[4]`, or just this one for a radius
+    /// of 0.
+    pub fn ring(self, radius: i64) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+
+        let mut hex = self + DIRECTIONS[4] * radius;
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        for &direction in &DIRECTIONS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += direction;
+            }
+        }
+
+        ring
+    }
+
+    /// The hexes a straight line from here to `other` passes through, both
+    /// ends included.
+    pub fn line_to(self, other: Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![self];
+        }
+
+        // Nudged off the edges between hexes so that rounding is consistent.
+        let lerp = |a: i64, b: i64, nudge: f64, t: f64| a as f64 + nudge + (b - a) as f64 * t;
+        (0..=n).map(|i| {
+            let t = i as f64 / n as f64;
+            round(lerp(self.q, other.q, 1e-6, t), lerp(self.r, other.r, 2e-6, t), lerp(self.s(), other.s(), -3e-6, t))
+        }).collect()
+    }
+
+    /// Where each step in turn leads from here, not including here.
+    pub fn path(self, steps: &[Hex]) -> Vec<Hex> {
+        steps.iter()
+            .scan(self, |hex, &step| {
+                *hex += step;
+                Some(*hex)
+            })
+            .collect()
+    }
+}
+
+/// The hex that fractional cube coordinates fall in.
+fn round(q: f64, r: f64, s: f64) -> Hex {
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+
+    // Rounding can leave them not adding up to 0, so work out whichever was
+    // rounded furthest from the other two.
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+
+    Hex::new(rq as i64, rr as i64)
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Hex) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Hex {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, n: i64) -> Hex {
+        Hex::new(self.q * n, self.r * n)
+    }
+}
+
+/// Which way up the hexagons are, and so what their neighbours are called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Flat top and bottom, with neighbours `n`, `ne`, `se`, `s`, `sw` and
+    /// `nw`.
+    Flat,
+    /// Pointed top and bottom, with neighbours `ne`, `e`, `se`, `sw`, `w`
+    /// and `nw`.
+    Pointy,
+}
+
+impl Orientation {
+    /// The names of [`DIRECTIONS`], in the same order.
+    pub fn names(self) -> [&'static str; 6] {
+        match self {
+            Orientation::Flat => ["ne", "se", "s", "sw", "nw", "n"],
+            Orientation::Pointy => ["ne", "e", "se", "sw", "w", "nw"],
+        }
+    }
+
+    /// The step called `name`.
+    pub fn direction(self, name: &str) -> Option<Hex> {
+        self.names().iter().position(|&n| n == name).map(|i| DIRECTIONS[i])
+    }
+
+    /// Parses steps either separated by commas, like `ne,s,nw`, or run
+    /// together, like `nwwswee`. On failure, gives the part of `text` that
+    /// isn't a step.
+    pub fn parse_steps(self, text: &str) -> Result<Vec<Hex>, &str> {
+        if text.contains(',') {
+            return text.split(',')
+                .map(|step| self.direction(step.trim()).ok_or(step))
+                .collect();
+        }
+
+        let mut steps = vec![];
+        let mut rest = text;
+        while !rest.is_empty() {
+            // Two letters first, so that `nw` isn't read as `n` then `w`.
+            let (step, len) = [2, 1].iter()
+                .filter_map(|&len| rest.get(..len).and_then(|name| self.direction(name)).map(|step| (step, len)))
+                .next()
+                .ok_or_else(|| &rest[..rest.chars().next().map_or(0, char::len_utf8)])?;
+
+            steps.push(step);
+            rest = &rest[len..];
+        }
+
+        Ok(steps)
+    }
+}
+
+#[test]
+fn test_distance() {
+    let hex = Hex::new(2, -3);
+    assert_eq!(hex.cube(), (2, -3, 1));
+    assert_eq!(Hex::from_cube(hex.cube()), hex);
+    assert_eq!(hex.length(), 3);
+    assert_eq!(hex.distance(Hex::new(-1, 1)), 4);
+    assert!(Hex::ORIGIN.neighbours().iter().all(|n| n.length() == 1));
+    assert_eq!(-hex + hex * 2, hex);
+}
+
+#[test]
+fn test_ring() {
+    assert_eq!(Hex::ORIGIN.ring(0), [Hex::ORIGIN]);
+    assert_eq!(Hex::new(1, 1).ring(1).len(), 6);
+
+    let ring = Hex::new(1, 1).ring(3);
+    assert_eq!(ring.len(), 18);
+    assert!(ring.iter().all(|hex| hex.distance(Hex::new(1, 1)) == 3));
+    assert!(ring.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+    assert_eq!(ring[17].distance(ring[0]), 1);
+}
+
+#[test]
+fn test_line() {
+    let line = Hex::ORIGIN.line_to(Hex::new(3, -1));
+    assert_eq!(line.len(), 4);
+    assert_eq!((line[0], line[3]), (Hex::ORIGIN, Hex::new(3, -1)));
+    assert!(line.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+
+    assert_eq!(Hex::ORIGIN.line_to(Hex::ORIGIN), [Hex::ORIGIN]);
+}
+
+#[test]
+fn test_parse_steps() {
+    let flat = Orientation::Flat;
+    let steps = flat.parse_steps("ne,ne,s,s").unwrap();
+    assert_eq!(Hex::ORIGIN.path(&steps).last().unwrap().length(), 2);
+    assert_eq!(flat.parse_steps("ne,e"), Err("e"));
+
+    // In the style of 2020 day 24, where this leads back to the start.
+    let pointy = Orientation::Pointy;
+    let steps = pointy.parse_steps("nwwswee").unwrap();
+    assert_eq!(steps.len(), 5);
+    assert_eq!(Hex::ORIGIN.path(&steps).last(), Some(&Hex::ORIGIN));
+    assert_eq!(pointy.parse_steps("esew").unwrap(), [DIRECTIONS[1], DIRECTIONS[2], DIRECTIONS[4]]);
+    assert_eq!(pointy.parse_steps("enx"), Err("n"));
+}
@@ -0,0 +1,195 @@
+//! Tapes and grids that go on forever in every direction, for puzzles that
+//! wander off wherever they like.
+//!
+//! Both are stored densely and grow in chunks to take in whatever is
+//! written, so they're only as big as the part that's been touched. Anything
+//! never written to reads as the default value.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use grid::Grid;
+
+/// The least a tape or grid grows by on any side, so that a walk along an
+/// edge doesn't grow it a cell at a time.
+const CHUNK: usize = 64;
+
+/// How much to grow a side `len` cells long by, to reach `past` cells
+/// beyond its end, or 0 if that's not past it at all.
+fn growth(len: usize, past: isize) -> usize {
+    match past {
+        past if past <= 0 => 0,
+        past => (past as usize).max(len).max(CHUNK),
+    }
+}
+
+/// A row of cells from minus infinity to infinity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tape<T> {
+    /// The position of `cells[0]`.
+    start: isize,
+    cells: Vec<T>,
+    blank: T,
+}
+
+impl<T: Clone> Tape<T> {
+    /// A tape that's `blank` all the way along.
+    pub fn new(blank: T) -> Tape<T> {
+        Tape { start: 0, cells: vec![], blank }
+    }
+
+    pub fn get(&self, i: isize) -> &T {
+        i.checked_sub(self.start)
+            .and_then(|offset| usize::try_from(offset).ok())
+            .and_then(|offset| self.cells.get(offset))
+            .unwrap_or(&self.blank)
+    }
+
+    pub fn get_mut(&mut self, i: isize) -> &mut T {
+        let len = self.cells.len();
+        let before = growth(len, self.start - i);
+        let after = growth(len, i - (self.start + len as isize) + 1);
+
+        if before > 0 {
+            self.cells.splice(0..0, vec![self.blank.clone(); before]);
+            self.start -= before as isize;
+        }
+        if after > 0 {
+            self.cells.resize(self.cells.len() + after, self.blank.clone());
+        }
+
+        &mut self.cells[(i - self.start) as usize]
+    }
+
+    /// Every cell that has room made for it so far, from the leftmost.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+}
+
+impl<T: Clone> Index<isize> for Tape<T> {
+    type Output = T;
+
+    fn index(&self, i: isize) -> &T {
+        self.get(i)
+    }
+}
+
+impl<T: Clone> IndexMut<isize> for Tape<T> {
+    fn index_mut(&mut self, i: isize) -> &mut T {
+        self.get_mut(i)
+    }
+}
+
+/// A plane of cells at `(x, y)`, where either can be negative, with `y`
+/// growing downwards as it does in a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfiniteGrid<T> {
+    /// The point at the top left of `cells`.
+    start: (isize, isize),
+    cells: Grid<T>,
+    blank: T,
+}
+
+impl<T: Clone> InfiniteGrid<T> {
+    /// A plane that's `blank` everywhere.
+    pub fn new(blank: T) -> InfiniteGrid<T> {
+        InfiniteGrid { start: (0, 0), cells: Grid::new(0, 0, blank.clone()), blank }
+    }
+
+    /// `grid` placed with its top left at `start`, and `blank` everywhere
+    /// around it.
+    pub fn from_grid(grid: Grid<T>, start: (isize, isize), blank: T) -> InfiniteGrid<T> {
+        InfiniteGrid { start, cells: grid, blank }
+    }
+
+    fn offset(&self, (x, y): (isize, isize)) -> Option<(usize, usize)> {
+        let x = usize::try_from(x.checked_sub(self.start.0)?).ok()?;
+        let y = usize::try_from(y.checked_sub(self.start.1)?).ok()?;
+
+        Some((x, y))
+    }
+
+    pub fn get(&self, point: (isize, isize)) -> &T {
+        self.offset(point).and_then(|offset| self.cells.get(offset)).unwrap_or(&self.blank)
+    }
+
+    pub fn get_mut(&mut self, (x, y): (isize, isize)) -> &mut T {
+        let (width, height) = (self.cells.width(), self.cells.height());
+        let (left, top) = self.start;
+        let grow_left = growth(width, left - x);
+        let grow_right = growth(width, x - (left + width as isize) + 1);
+        let grow_up = growth(height, top - y);
+        let grow_down = growth(height, y - (top + height as isize) + 1);
+
+        if grow_left + grow_right + grow_up + grow_down > 0 {
+            let mut cells = Grid::new(width + grow_left + grow_right, height + grow_up + grow_down, self.blank.clone());
+            for (x, y) in self.cells.points() {
+                cells[(x + grow_left, y + grow_up)] = self.cells[(x, y)].clone();
+            }
+
+            self.cells = cells;
+            self.start = (left - grow_left as isize, top - grow_up as isize);
+        }
+
+        let offset = self.offset((x, y)).expect("grown to fit");
+        &mut self.cells[offset]
+    }
+
+    /// Every cell that has room made for it so far, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+}
+
+impl<T: Clone> Index<(isize, isize)> for InfiniteGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: (isize, isize)) -> &T {
+        self.get(point)
+    }
+}
+
+impl<T: Clone> IndexMut<(isize, isize)> for InfiniteGrid<T> {
+    fn index_mut(&mut self, point: (isize, isize)) -> &mut T {
+        self.get_mut(point)
+    }
+}
+
+/// The part that has room made for it so far.
+impl<T: Display> Display for InfiniteGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.cells, f)
+    }
+}
+
+#[test]
+fn test_tape() {
+    let mut tape = Tape::new(false);
+    assert!(!tape[-5]);
+
+    tape[0] = true;
+    tape[-100] = true;
+    tape[3] = true;
+    assert!(tape[-100] && tape[0] && tape[3]);
+    assert!(!tape[-99] && !tape[1] && !tape[1000]);
+    assert_eq!(tape.iter().filter(|&&on| on).count(), 3);
+    assert!(tape.iter().count() >= 104);
+}
+
+#[test]
+fn test_infinite_grid() {
+    let start = Grid::from_rows(vec![vec!['a', 'b'], vec!['c', 'd']], '.');
+    let mut grid = InfiniteGrid::from_grid(start, (-1, -1), '.');
+    assert_eq!((grid[(-1, -1)], grid[(0, 0)], grid[(5, -7)]), ('a', 'd', '.'));
+
+    grid[(-70, 2)] = 'x';
+    grid[(3, -200)] = 'y';
+    assert_eq!((grid[(-1, -1)], grid[(0, 0)], grid[(-70, 2)], grid[(3, -200)]), ('a', 'd', 'x', 'y'));
+    assert_eq!(grid.iter().filter(|&&c| c != '.').count(), 6);
+
+    let mut grid = InfiniteGrid::new(0);
+    grid[(1, 1)] += 1;
+    assert_eq!(grid[(1, 1)], 1);
+}
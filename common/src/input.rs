@@ -0,0 +1,242 @@
+//! Finding and reading a day's puzzle input.
+//!
+//! Inputs are looked up in order from:
+//!
+//! 1. an explicit `--input <path>`, where `-` means stdin
+//! 2. `$AOC_INPUT_DIR/<year>/<name>.txt`, then `$AOC_INPUT_DIR/<name>.txt`
+//! 3. the year's own `inputs/<name>.txt`
+//!
+//! However it was saved, an input is tidied up by [`normalise`] before a day
+//! parses it, so that days never see a byte order mark, `\r\n` line endings
+//! or stray whitespace at the ends of lines.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use solution::{Solution, Year};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// How much tidying an input gets before a day parses it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Normalise {
+    /// Drop a byte order mark, turn `\r\n` into `\n`, trim whitespace from
+    /// the end of every line and drop blank lines from the end.
+    #[default]
+    Everything,
+    /// Only the byte order mark and line endings, for inputs whose spaces
+    /// are part of a picture.
+    LineEndings,
+}
+
+/// Tidies up an input the way some editor or download might have left it.
+pub fn normalise(input: &str, how: Normalise) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    match how {
+        Normalise::Everything => {
+            let mut lines = input.lines().map(str::trim_end).collect::<Vec<_>>();
+            while lines.last() == Some(&"") {
+                lines.pop();
+            }
+            lines.join("\n")
+        }
+        Normalise::LineEndings => input.replace("\r\n", "\n"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    Path(PathBuf),
+}
+
+impl Source {
+    /// Interprets a `--input` argument.
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::Path(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        let mut input = String::new();
+        let read = match *self {
+            Source::Stdin => io::stdin().read_to_string(&mut input),
+            Source::Path(ref path) => File::open(path).and_then(|mut file| file.read_to_string(&mut input)),
+        };
+
+        read.map(|_| input).map_err(|error| InputError::Unreadable { source: self.clone(), error })
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// None of the candidate paths existed.
+    Missing { year: u16, day: u8, name: &'static str, tried: Vec<PathBuf> },
+    /// The input was found but couldn't be read.
+    Unreadable { source: Source, error: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InputError::Missing { year, day, name, ref tried } => {
+                writeln!(f, "no input found for {} day {} ({}), tried:", year, day, name)?;
+                for path in tried {
+                    writeln!(f, "    {}", path.display())?;
+                }
+                write!(f, "pass --input <path> (or - for stdin), or set {}", INPUT_DIR_VAR)
+            }
+            InputError::Unreadable { source: Source::Stdin, ref error } => {
+                write!(f, "could not read stdin: {}", error)
+            }
+            InputError::Unreadable { source: Source::Path(ref path), ref error } => {
+                write!(f, "could not read {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Resolves inputs for any day, given the overrides taken from the command
+/// line and environment.
+#[derive(Debug, Clone, Default)]
+pub struct Inputs {
+    explicit: Option<Source>,
+    dir: Option<PathBuf>,
+}
+
+impl Inputs {
+    pub fn new(explicit: Option<Source>, dir: Option<PathBuf>) -> Inputs {
+        Inputs { explicit, dir }
+    }
+
+    /// Uses `explicit` if given, otherwise `$AOC_INPUT_DIR` before falling
+    /// back to each year's default.
+    pub fn from_env(explicit: Option<Source>) -> Inputs {
+        let dir = env::var_os(INPUT_DIR_VAR)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from);
+
+        Inputs::new(explicit, dir)
+    }
+
+    /// Every path that would be tried for a day, in order. Empty when the
+    /// input comes from stdin.
+    pub fn candidates(&self, year: &Year, day: &dyn Solution) -> Vec<PathBuf> {
+        let file = format!("{}.txt", day.name());
+
+        match self.explicit {
+            Some(Source::Stdin) => vec![],
+            Some(Source::Path(ref path)) => vec![path.clone()],
+            None => {
+                let mut paths = vec![];
+
+                if let Some(ref dir) = self.dir {
+                    paths.push(dir.join(year.year.to_string()).join(&file));
+                    paths.push(dir.join(&file));
+                }
+
+                paths.push(Path::new(year.dir).join("inputs").join(&file));
+                paths
+            }
+        }
+    }
+
+    pub fn load(&self, year: &Year, day: &dyn Solution) -> Result<String, InputError> {
+        if let Some(Source::Stdin) = self.explicit {
+            return Source::Stdin.read();
+        }
+
+        let tried = self.candidates(year, day);
+
+        for path in &tried {
+            let mut input = String::new();
+            match File::open(path).and_then(|mut file| file.read_to_string(&mut input)) {
+                Ok(_) => return Ok(input),
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(InputError::Unreadable { source: Source::Path(path.clone()), error }),
+            }
+        }
+
+        Err(InputError::Missing { year: year.year, day: day.day(), name: day.name(), tried })
+    }
+}
+
+#[cfg(test)]
+fn scratch_dir(name: &str) -> PathBuf {
+    use std::fs;
+
+    let dir = env::temp_dir().join(format!("aoc-input-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_normalise() {
+    let input = "\u{feff}ecl:gry pid:860033327 \r\nbyr:1937\r\n\t\r\niyr:2013\r\n\r\n";
+    assert_eq!(normalise(input, Normalise::Everything), "ecl:gry pid:860033327\nbyr:1937\n\niyr:2013");
+    assert_eq!(normalise("361527\n", Normalise::Everything), "361527");
+    assert_eq!(normalise("", Normalise::Everything), "");
+
+    let diagram = "\u{feff}     |          \r\n     |  +--+    \r\n";
+    assert_eq!(normalise(diagram, Normalise::LineEndings), "     |          \n     |  +--+    \n");
+}
+
+#[test]
+fn test_candidates() {
+    use solution::Day;
+
+    let year = Year::new(2017, "/aoc/2017");
+    let day = Day::new(10, "ten");
+
+    assert_eq!(Inputs::new(None, None).candidates(&year, &day), vec![
+        PathBuf::from("/aoc/2017/inputs/ten.txt"),
+    ]);
+
+    assert_eq!(Inputs::new(None, Some("/shared".into())).candidates(&year, &day), vec![
+        PathBuf::from("/shared/2017/ten.txt"),
+        PathBuf::from("/shared/ten.txt"),
+        PathBuf::from("/aoc/2017/inputs/ten.txt"),
+    ]);
+
+    let explicit = Inputs::new(Some(Source::from_arg("mine.txt")), Some("/shared".into()));
+    assert_eq!(explicit.candidates(&year, &day), vec![PathBuf::from("mine.txt")]);
+
+    assert!(Inputs::new(Some(Source::from_arg("-")), None).candidates(&year, &day).is_empty());
+}
+
+#[test]
+fn test_load() {
+    use std::fs;
+    use solution::Day;
+
+    let dir = scratch_dir("load");
+    let year = Year::new(2017, "/nonexistent");
+    let day = Day::new(10, "ten");
+
+    fs::write(dir.join("ten.txt"), "3,4,1,5").unwrap();
+    let inputs = Inputs::new(None, Some(dir.clone()));
+    assert_eq!(inputs.load(&year, &day).unwrap(), "3,4,1,5");
+
+    fs::create_dir(dir.join("2017")).unwrap();
+    fs::write(dir.join("2017").join("ten.txt"), "1,2,3").unwrap();
+    assert_eq!(inputs.load(&year, &day).unwrap(), "1,2,3");
+
+    let missing = Day::new(11, "eleven");
+    let message = inputs.load(&year, &missing).unwrap_err().to_string();
+    assert!(message.contains("2017 day 11 (eleven)"));
+    assert!(message.contains(&dir.join("eleven.txt").display().to_string()));
+    assert!(message.contains("/nonexistent/inputs/eleven.txt"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
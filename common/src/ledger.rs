@@ -0,0 +1,223 @@
+//! The answers accepted for our own inputs, so that refactors can be checked
+//! against more than the puzzle examples.
+//!
+//! Each year keeps an `answers.tsv` at its crate root, one
+//! `<day>\t<part>\t<input hash>\t<answer>` line per solved part. Tabs,
+//! newlines and backslashes in text answers are written as `\t`, `\n` and
+//! `\\`, so that each answer stays on its own line.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use answer::Answer;
+use input::{self, Normalise};
+use solution::{Part, Year};
+
+const HEADER: &str = "# day\tpart\tinput\tanswer";
+
+/// A 64-bit FNV-1a hash of an input, which is all the ledger needs to tell
+/// inputs apart.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub day: u8,
+    pub part: Part,
+    pub input: u64,
+}
+
+impl Key {
+    /// Keyed on the input tidied up `how` the day tidies it before parsing,
+    /// so that a copy with `\r\n` line endings or a byte order mark is still
+    /// the same input, but two the day would parse differently aren't.
+    pub fn new(day: u8, part: Part, input: &str, how: Normalise) -> Key {
+        Key { day, part, input: hash(&input::normalise(input, how)) }
+    }
+}
+
+/// How a fresh answer compares with the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    answers: BTreeMap<Key, String>,
+}
+
+impl Ledger {
+    pub fn path(year: &Year) -> PathBuf {
+        Path::new(year.dir).join("answers.tsv")
+    }
+
+    /// Reads the ledger at `path`; a missing file is an empty ledger.
+    pub fn load<P: Into<PathBuf>>(path: P) -> io::Result<Ledger> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(ref e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, answer) = parse_line(line).ok_or_else(|| {
+                let message = format!("{}:{}: malformed ledger entry {:?}", path.display(), i + 1, line);
+                io::Error::new(ErrorKind::InvalidData, message)
+            })?;
+            answers.insert(key, answer);
+        }
+
+        Ok(Ledger { path, answers })
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&str> {
+        self.answers.get(key).map(|answer| &**answer)
+    }
+
+    /// Records `answer` as accepted, returning whatever it replaced.
+    pub fn insert(&mut self, key: Key, answer: &Answer) -> Option<String> {
+        self.answers.insert(key, answer.to_string())
+    }
+
+    pub fn check(&self, key: &Key, answer: &Answer) -> Check {
+        match self.get(key) {
+            None => Check::Missing,
+            Some(expected) if *expected == answer.to_string() => Check::Pass,
+            Some(expected) => Check::Fail { expected: expected.to_owned() },
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+
+        for (key, answer) in &self.answers {
+            text.push_str(&format!("{}\t{}\t{:016x}\t{}\n", key.day, key.part, key.input, escape(answer)));
+        }
+
+        fs::write(&self.path, text)
+    }
+}
+
+fn parse_line(line: &str) -> Option<(Key, String)> {
+    let mut fields = line.splitn(4, '\t');
+
+    let day = fields.next()?.parse().ok()?;
+    let part = match fields.next()? {
+        "a" => Part::A,
+        "b" => Part::B,
+        _ => return None,
+    };
+    let input = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let answer = unescape(fields.next()?)?;
+
+    Some((Key { day, part, input }, answer))
+}
+
+pub(crate) fn escape(answer: &str) -> String {
+    let mut escaped = String::with_capacity(answer.len());
+    for c in answer.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Undoes [`escape`], or `None` for a backslash that isn't escaping
+/// anything.
+pub(crate) fn unescape(field: &str) -> Option<String> {
+    let mut answer = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        answer.push(match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+
+    Some(answer)
+}
+
+#[test]
+fn test_hash() {
+    assert_eq!(hash(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(hash("a"), 0xaf63_dc4c_8601_ec8c);
+    assert_ne!(hash("3,4,1,5"), hash("3,4,1,5\n"));
+}
+
+#[test]
+fn test_key() {
+    let key = Key::new(19, Part::A, "  |  \n  +-A\n", Normalise::Everything);
+
+    assert_eq!(Key::new(19, Part::A, "\u{feff}  |  \r\n  +-A\r\n\r\n", Normalise::Everything), key);
+    assert_ne!(Key::new(19, Part::A, "  |  \n  +-B\n", Normalise::Everything), key);
+
+    // Trailing spaces matter to a day that only normalises line endings.
+    let key = Key::new(19, Part::A, "  |  \n  +-A\n", Normalise::LineEndings);
+    assert_eq!(Key::new(19, Part::A, "\u{feff}  |  \r\n  +-A\r\n", Normalise::LineEndings), key);
+    assert_ne!(Key::new(19, Part::A, "  |\n  +-A\n", Normalise::LineEndings), key);
+}
+
+#[test]
+fn test_round_trip() {
+    use std::env;
+
+    let path = env::temp_dir().join(format!("aoc-ledger-{}.tsv", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut ledger = Ledger::load(&path).unwrap();
+    let a = Key::new(10, Part::A, "3,4,1,5", Normalise::Everything);
+    let b = Key::new(10, Part::B, "3,4,1,5", Normalise::Everything);
+    assert_eq!(ledger.check(&a, &Answer::Number(2)), Check::Missing);
+
+    ledger.insert(a, &Answer::Number(2));
+    ledger.insert(b, &Answer::from("4a19451b02fb05416d73aea0ec8c00c0"));
+    ledger.save().unwrap();
+
+    let ledger = Ledger::load(&path).unwrap();
+    assert_eq!(ledger.check(&a, &Answer::Number(2)), Check::Pass);
+    assert_eq!(ledger.check(&a, &Answer::Number(3)), Check::Fail { expected: "2".to_string() });
+    assert_eq!(ledger.check(&Key::new(10, Part::A, "1,2,3", Normalise::Everything), &Answer::Number(2)), Check::Missing);
+    assert_eq!(ledger.get(&b), Some("4a19451b02fb05416d73aea0ec8c00c0"));
+
+    let mut ledger = Ledger::load(&path).unwrap();
+    let drawn = Answer::from("#..#\n#\t\\.");
+    ledger.insert(a, &drawn);
+    ledger.save().unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+    assert_eq!(Ledger::load(&path).unwrap().check(&a, &drawn), Check::Pass);
+
+    fs::write(&path, "10\tc\tff\t2\n").unwrap();
+    assert_eq!(Ledger::load(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+    fs::write(&path, "10\ta\tff\tx\\y\n").unwrap();
+    assert_eq!(Ledger::load(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    fs::remove_file(&path).unwrap();
+}
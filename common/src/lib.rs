@@ -0,0 +1,73 @@
+//! Infrastructure shared by every year's crate: input loading, answers,
+//! parsing helpers, grids and the runner.
+//!
+//! A new year is a workspace member whose library exposes a `year()`
+//! building a [`Year`] from its days, registered with the `aoc` binary.
+
+mod answer;
+mod solution;
+pub mod batch;
+pub mod bench;
+pub mod cancel;
+pub mod compare;
+pub mod examples;
+pub mod fetch;
+pub mod generate;
+pub mod grid;
+pub mod heading;
+pub mod hex;
+pub mod infinite;
+pub mod input;
+pub mod ledger;
+pub mod log;
+pub mod parse;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod status;
+pub mod submit;
+pub mod vm;
+
+pub use answer::Answer;
+pub use solution::{Day, Failure, IntoAnswer, Parsed, Part, Solution, Year};
+
+/// The tests every year's crate runs over all of its days, given the
+/// expression building its [`Year`]: the saved examples, as they are and as
+/// if saved on Windows, the generators and the alternative implementations.
+///
+/// ```text
+/// #[cfg(test)]
+/// mod tests {
+///     year_tests!(::year());
+/// }
+/// ```
+#[macro_export]
+macro_rules! year_tests {
+    ($year:expr) => {
+        fn assert_passed(failures: Vec<String>) {
+            assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+        }
+
+        #[test]
+        fn examples() {
+            assert_passed($crate::examples::check(&$year).unwrap());
+        }
+
+        #[test]
+        fn examples_saved_on_windows() {
+            assert_passed($crate::examples::check_with(&$year, &|input| {
+                format!("\u{feff}{}\r\n", input.replace('\n', "\r\n"))
+            }).unwrap());
+        }
+
+        #[test]
+        fn generators() {
+            assert_passed($crate::generate::check(&$year));
+        }
+
+        #[test]
+        fn alternatives() {
+            assert_passed($crate::compare::check(&$year));
+        }
+    };
+}
@@ -0,0 +1,203 @@
+//! Levelled diagnostics for solvers, written to stderr so that they never mix
+//! with the answers.
+//!
+//! Solvers log through [`error!`], [`warn!`], [`info!`], [`debug!`] and
+//! [`trace!`], which tag each message with the day it came from, such as
+//! `2017::seven`. Only warnings and errors get through by default. `-v`,
+//! `-vv` and `-vvv` let through info, debug and trace, and `$AOC_LOG` sets
+//! levels per year or day as a comma separated list of `<level>` and
+//! `<target>=<level>`, for example `AOC_LOG=2017::seven=trace` or
+//! `AOC_LOG=debug,2020=off`.
+
+use std::env;
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+
+pub const LOG_VAR: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn parse(name: &str) -> Option<Level> {
+        match &*name.to_ascii_lowercase() {
+            "off" => Some(Level::Off),
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+
+    /// The level each extra `-v` lets through.
+    pub fn verbosity(count: usize) -> Level {
+        match count {
+            0 => Level::Warn,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Level::Off => "OFF",
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        })
+    }
+}
+
+/// Which level each target logs at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: Level,
+    targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    pub const fn new(default: Level) -> Filter {
+        Filter { default, targets: Vec::new() }
+    }
+
+    /// Reads an `$AOC_LOG` style list of directives, on top of `default`,
+    /// which a bare level replaces, quieter or not.
+    pub fn parse(spec: &str, default: Level) -> Result<Filter, String> {
+        let mut filter = Filter::new(default);
+
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let (target, level) = match directive.rfind('=') {
+                Some(i) => (Some(&directive[..i]), &directive[i + 1..]),
+                None => (None, directive),
+            };
+            let level = Level::parse(level).ok_or_else(|| format!("{:?} isn't a log level", level))?;
+
+            match target {
+                Some(target) => filter.targets.push((target.to_string(), level)),
+                None => filter.default = level,
+            }
+        }
+
+        Ok(filter)
+    }
+
+    /// The level `target` logs at, taken from the most specific directive
+    /// naming it or one of its parents.
+    pub fn level(&self, target: &str) -> Level {
+        self.targets.iter()
+            .filter(|&(prefix, _)| {
+                target == prefix || (target.starts_with(&**prefix) && target[prefix.len()..].starts_with("::"))
+            })
+            .max_by_key(|&(prefix, _)| prefix.len())
+            .map_or(self.default, |&(_, level)| level)
+    }
+
+    /// The most verbose level any target logs at.
+    fn max(&self) -> Level {
+        self.targets.iter().map(|&(_, level)| level).fold(self.default, Level::max)
+    }
+}
+
+static FILTER: RwLock<Filter> = RwLock::new(Filter::new(Level::Warn));
+static MAX: AtomicUsize = AtomicUsize::new(Level::Warn as usize);
+
+/// Sets up logging from `$AOC_LOG` and the number of `-v`s given.
+pub fn init(verbosity: usize) -> Result<(), String> {
+    let spec = env::var(LOG_VAR).unwrap_or_default();
+    let filter = Filter::parse(&spec, Level::verbosity(verbosity))
+        .map_err(|e| format!("bad {}: {}", LOG_VAR, e))?;
+
+    set_filter(filter);
+    Ok(())
+}
+
+pub fn set_filter(filter: Filter) {
+    MAX.store(filter.max() as usize, Ordering::Relaxed);
+    *FILTER.write().unwrap_or_else(|e| e.into_inner()) = filter;
+}
+
+/// The target a module logs as: its path, with the year's crate name cut
+/// down to the year.
+pub fn target(module_path: &str) -> &str {
+    module_path.strip_prefix("advent_of_code_").unwrap_or(module_path)
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    if level as usize > MAX.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    level <= FILTER.read().unwrap_or_else(|e| e.into_inner()).level(target(module_path))
+}
+
+#[doc(hidden)]
+pub fn log(level: Level, module_path: &str, args: fmt::Arguments) {
+    if enabled(level, module_path) {
+        eprintln!("[{} {}] {}", level, target(module_path), args);
+    }
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log::log($crate::log::Level::Error, module_path!(), format_args!($($arg)+)) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log::log($crate::log::Level::Warn, module_path!(), format_args!($($arg)+)) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log::log($crate::log::Level::Info, module_path!(), format_args!($($arg)+)) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log::log($crate::log::Level::Debug, module_path!(), format_args!($($arg)+)) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log::log($crate::log::Level::Trace, module_path!(), format_args!($($arg)+)) };
+}
+
+#[test]
+fn test_filter() {
+    let filter = Filter::parse("2017::seven=trace, 2020=off", Level::Warn).unwrap();
+    assert_eq!(filter.level("2017::seven"), Level::Trace);
+    assert_eq!(filter.level("2017::seven::inner"), Level::Trace);
+    assert_eq!(filter.level("2017::seventeen"), Level::Warn);
+    assert_eq!(filter.level("2020::four"), Level::Off);
+    assert_eq!(filter.max(), Level::Trace);
+
+    let filter = Filter::parse("debug,2017=info,2017::three=error", Level::Info).unwrap();
+    assert_eq!(filter.level("2020::four"), Level::Debug);
+    assert_eq!(filter.level("2017::four"), Level::Info);
+    assert_eq!(filter.level("2017::three"), Level::Error);
+
+    assert_eq!(Filter::parse("", Level::verbosity(2)).unwrap().level("2017::one"), Level::Debug);
+    assert_eq!(Filter::parse("error", Level::verbosity(2)).unwrap().level("2017::one"), Level::Error);
+    assert!(Filter::parse("2017::seven=loud", Level::Warn).is_err());
+}
+
+#[test]
+fn test_target() {
+    assert_eq!(target("advent_of_code_2017::seven"), "2017::seven");
+    assert_eq!(target("aoc_common::runner"), "aoc_common::runner");
+}
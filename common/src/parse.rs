@@ -0,0 +1,211 @@
+//! Helpers for turning puzzle input into structured data, with errors that
+//! point at the exact spot the input went wrong.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where and why an input couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the runner, as parsers don't know which day they are.
+    pub day: Option<u8>,
+    /// 1-based, as are columns, which count characters rather than bytes.
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, empty if the input ended early.
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// An error at `at`, which must be a slice of `input` for the location
+    /// to be worked out.
+    pub fn at<S: Into<String>>(input: &str, at: &str, expected: S) -> ParseError {
+        let start = input.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + at.len() <= input.len())
+            .unwrap_or(input.len());
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            day: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            found: at.to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn in_day(mut self, day: u8) -> ParseError {
+        self.day = Some(day);
+        self
+    }
+
+    /// The error with the offending line of `input` quoted beneath it and
+    /// the problem underlined.
+    pub fn diagnostic(&self, input: &str) -> String {
+        let source = input.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = self.line.to_string();
+        let underline = self.found.lines().next().map_or(0, |line| line.chars().count()).max(1);
+
+        format!(
+            "{}\n{:w$} |\n{} | {}\n{:w$} | {:c$}{}",
+            self, "", gutter, source, "", "", "^".repeat(underline),
+            w = gutter.len(), c = self.column - 1,
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+
+        write!(f, "line {}, column {}: expected {}, found ", self.line, self.column, self.expected)?;
+
+        match self.found.lines().next() {
+            None => write!(f, "the end of the line"),
+            Some(found) => write!(f, "{:?}", found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// The character starting at byte `i` of `text`, as a slice of it so that it
+/// can be pointed at by an error.
+pub fn char_at(text: &str, i: usize) -> &str {
+    let rest = &text[i..];
+    &rest[..rest.chars().next().map_or(0, char::len_utf8)]
+}
+
+/// Wraps a day's whole input so that errors can be located within it.
+///
+/// Everything handed to a `Parser` should be sliced out of that input, with
+/// `str::lines`, `split` and friends, for the errors to point anywhere
+/// useful.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Parser<'a> {
+        Parser { input }
+    }
+
+    pub fn error<S: Into<String>>(&self, at: &str, expected: S) -> ParseError {
+        ParseError::at(self.input, at, expected)
+    }
+
+    /// An error just past the end of `text`, for when it stops short.
+    pub fn error_after<S: Into<String>>(&self, text: &str, expected: S) -> ParseError {
+        self.error(&text[text.len()..], expected)
+    }
+
+    /// Parses `text` as a number, or anything else with a `FromStr`.
+    pub fn number<T: FromStr>(&self, text: &str) -> Result<T, ParseError> {
+        text.parse().map_err(|_| self.error(text, "a number"))
+    }
+
+    /// Strips `prefix` from the start of `text`, returning the rest.
+    pub fn literal<'b>(&self, text: &'b str, prefix: &str) -> Result<&'b str, ParseError> {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            return Ok(rest);
+        }
+
+        // Point at the first character that differs.
+        let same = text.char_indices()
+            .zip(prefix.chars())
+            .find(|&((_, a), b)| a != b)
+            .map_or(text.len(), |((i, _), _)| i);
+        Err(self.error(char_at(text, same), format!("{:?}", prefix)))
+    }
+
+    /// Strips `suffix` from the end of `text`, returning the rest.
+    pub fn suffix<'b>(&self, text: &'b str, suffix: &str) -> Result<&'b str, ParseError> {
+        text.strip_suffix(suffix).ok_or_else(|| self.error_after(text, format!("{:?}", suffix)))
+    }
+
+    /// Checks that `text` is exactly `word`.
+    pub fn keyword(&self, text: &str, word: &str) -> Result<(), ParseError> {
+        if text == word {
+            Ok(())
+        } else {
+            Err(self.error(text, format!("{:?}", word)))
+        }
+    }
+
+    /// Takes the next token from `tokens`, split from `line`, complaining at
+    /// the end of `line` if there isn't one.
+    pub fn token<I>(&self, tokens: &mut I, line: &'a str, expected: &str) -> Result<&'a str, ParseError>
+        where I: Iterator<Item = &'a str>
+    {
+        tokens.next().ok_or_else(|| self.error_after(line, expected))
+    }
+
+    /// Complains about anything left over in `tokens`.
+    pub fn end<I>(&self, tokens: &mut I) -> Result<(), ParseError>
+        where I: Iterator<Item = &'a str>
+    {
+        match tokens.next() {
+            Some(extra) => Err(self.error(extra, "the end of the line")),
+            None => Ok(()),
+        }
+    }
+
+    /// Parses every non-empty line with `parse`, stopping at the first error.
+    pub fn lines<T, F>(&self, parse: F) -> Result<Vec<T>, ParseError>
+        where F: FnMut(&'a str) -> Result<T, ParseError>
+    {
+        self.input.lines().filter(|line| !line.is_empty()).map(parse).collect()
+    }
+}
+
+#[test]
+fn test_location() {
+    let input = "b inc 5 if a > 1\nc dec -10 if a =< 1\n";
+    let line = input.lines().nth(1).unwrap();
+    let op = line.split(' ').nth(5).unwrap();
+
+    let error = ParseError::at(input, op, "a comparison").in_day(8);
+    assert_eq!((error.line, error.column), (2, 16));
+    assert_eq!(error.to_string(), r#"day 8, line 2, column 16: expected a comparison, found "=<""#);
+    assert_eq!(error.diagnostic(input), [
+        r#"day 8, line 2, column 16: expected a comparison, found "=<""#,
+        "  |",
+        "2 | c dec -10 if a =< 1",
+        "  |                ^^",
+    ].join("\n"));
+}
+
+#[test]
+fn test_parser() {
+    let input = "Generator A starts with 65\nGenerator B starts with x";
+    let p = Parser::new(input);
+    let lines = input.lines().collect::<Vec<_>>();
+
+    assert_eq!(p.literal(lines[0], "Generator A starts with "), Ok("65"));
+    assert_eq!(p.number::<u64>("65"), Ok(65));
+    assert_eq!(char_at("aé!", 1), "é");
+    assert_eq!(p.suffix(lines[0], "65"), Ok("Generator A starts with "));
+    assert_eq!(p.suffix(lines[0], "6").unwrap_err().column, 27);
+
+    let error = p.literal(lines[1], "Generator A").unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (2, 11, "B"));
+
+    let error = p.number::<u64>(&lines[1][24..]).unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (2, 25, "x"));
+
+    let mut words = lines[0].split(' ');
+    words.by_ref().take(4).count();
+    assert_eq!(p.token(&mut words, lines[0], "a seed"), Ok("65"));
+    let error = p.token(&mut words, lines[0], "a seed").unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found), (1, 27, ""));
+    assert!(error.to_string().ends_with("found the end of the line"));
+}
@@ -0,0 +1,153 @@
+//! Solved parts, and the formats they can be written out in.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use answer::Answer;
+use solution::Part;
+
+/// The outcome of running one part of one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    pub part: Part,
+    pub answer: Answer,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// A header per day followed by `a: <answer> (<time>)` lines.
+    #[default]
+    Human,
+    /// One JSON object per record, per line.
+    Json,
+    /// Tab separated columns, under a header row.
+    Tsv,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "human" => Some(Format::Human),
+            "json" => Some(Format::Json),
+            "tsv" => Some(Format::Tsv),
+            _ => None,
+        }
+    }
+
+    /// Printed once, before any records.
+    pub fn header(self) -> Option<&'static str> {
+        match self {
+            Format::Tsv => Some("year\tday\tname\tpart\tanswer\tduration_ns"),
+            _ => None,
+        }
+    }
+
+    /// Printed before each day's records.
+    pub fn day_header(self, year: u16, day: u8, name: &str) -> Option<String> {
+        match self {
+            Format::Human => Some(format!("{} day {} ({})", year, day, name)),
+            _ => None,
+        }
+    }
+
+    pub fn record(self, record: &Record) -> String {
+        match self {
+            Format::Human => {
+                format!("{}: {} ({})", record.part, record.answer, human_duration(record.duration))
+            }
+            Format::Json => {
+                let answer = match record.answer {
+                    Answer::Number(n) => n.to_string(),
+                    Answer::Text(ref s) => json_string(s),
+                };
+
+                format!(
+                    "{{\"year\":{},\"day\":{},\"name\":{},\"part\":\"{}\",\"answer\":{},\"duration_ns\":{}}}",
+                    record.year, record.day, json_string(record.name), record.part, answer,
+                    record.duration.as_nanos(),
+                )
+            }
+            Format::Tsv => {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    record.year, record.day, record.name, record.part,
+                    record.answer.to_string().replace(['\t', '\n'], " "),
+                    record.duration.as_nanos(),
+                )
+            }
+        }
+    }
+}
+
+pub fn human_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+fn record(answer: Answer) -> Record {
+    Record {
+        year: 2017,
+        day: 10,
+        name: "ten",
+        part: Part::B,
+        answer,
+        duration: Duration::from_micros(1500),
+    }
+}
+
+#[test]
+fn test_human() {
+    assert_eq!(Format::Human.record(&record(Answer::Number(-2))), "b: -2 (1.5ms)");
+    assert_eq!(human_duration(Duration::from_nanos(999)), "999ns");
+    assert_eq!(human_duration(Duration::from_millis(2500)), "2.50s");
+}
+
+#[test]
+fn test_json() {
+    assert_eq!(
+        Format::Json.record(&record(Answer::Number(42))),
+        r#"{"year":2017,"day":10,"name":"ten","part":"b","answer":42,"duration_ns":1500000}"#
+    );
+    assert_eq!(
+        Format::Json.record(&record(Answer::from("a\"b\n"))),
+        r#"{"year":2017,"day":10,"name":"ten","part":"b","answer":"a\"b\n","duration_ns":1500000}"#
+    );
+}
+
+#[test]
+fn test_tsv() {
+    assert_eq!(Format::Tsv.record(&record(Answer::from("4a19"))), "2017\t10\tten\tb\t4a19\t1500000");
+    assert_eq!(Format::Tsv.header().unwrap().split('\t').count(), 6);
+}
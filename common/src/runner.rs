@@ -0,0 +1,1246 @@
+//! The command line front end shared by the `aoc` binary and the per-day
+//! binaries.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+
+use batch::{self as batcher, Row};
+use bench::{Baseline, Change, Stage, Stats};
+use cancel::Token;
+use compare::{self as comparer, Comparison};
+use examples::{self, Examples};
+use fetch::{self as fetcher, Client};
+use generate::{self as generator, catch, Rng};
+use input::{Inputs, Source};
+use ledger::{Check, Key, Ledger};
+use log;
+use parse::ParseError;
+use report::{human_duration, Format, Record};
+use scaffold::{self, Scaffold};
+use status::{Calendar, Progress};
+use solution::{Failure, Part, Solution, Year};
+use submit::{self as submitter, Guesses, Verdict};
+
+const USAGE: &str = "usage:
+    aoc list
+    aoc status [<year>...]
+    aoc status <year> <day> --input <path>
+    aoc run <year> <day>... [--input <path>] [--explain]
+    aoc run <year> --all
+    aoc verify [<year> [<day>...]]
+    aoc examples <year> [<day>...] [--save]
+    aoc fetch <year> <day>...
+    aoc fetch <year> --all
+    aoc submit <year> <day> <part> [<answer>] [--input <path>]
+    aoc new <year> <day> [--example <path>] [--answer <answer>]...
+    aoc batch <year> <day> <path>...
+    aoc batch <year> --all <dir>
+    aoc bench <year> <day>... [--runs <n>] [--threshold <percent>]
+    aoc bench <year> --all
+    aoc generate <year> <day> [--size <n>] [--seed <n>]
+    aoc stress <year> <day>... [--size <n>]... [--runs <n>] [--seed <n>]
+    aoc stress <year> --all
+    aoc compare <year> <day>... [--size <n>]... [--runs <n>] [--seed <n>]
+    aoc compare <year> --all
+
+options:
+    -i, --input <path>   read the input from <path>, or stdin if it is -
+    -f, --format <name>  print answers as human (the default), json or tsv
+    --explain            show the working behind answers, for the days that
+                         can: under each answer from run, under each
+                         failure from verify, and after a wrong submission
+    -v, -vv, -vvv        log info, debug or trace messages from the solvers
+    --budget <seconds>   how long each part may run before it is stopped
+                         (default 60, or 5 for compare)
+    --save               record the answers run gives as accepted, the
+                         timings bench gives as its baseline, or the new
+                         examples examples finds
+    --example <path>     stub smoke tests with the example at <path>, or
+                         stdin if it is -
+    --answer <answer>    the example's answer for part a, then part b
+    --runs <n>           how many times bench times each day, or how many
+                         inputs of each size stress tries (default 10)
+    --size <n>           how big an input to generate; stress and compare
+                         take several (default 100, or 10, 100 and 1000
+                         for stress and 10 and 100 for compare)
+    --seed <n>           the seed to generate from (default the time)
+    --threshold <pct>    how much slower than the baseline bench tolerates
+                         before flagging a regression (default 10)
+
+status shows every day of each year's calendar and where its parts stand:
+* for an answer in answers.tsv for the day's input, + for a part that is
+solved but not yet verified, ? for a placeholder that's registered but not
+really solved, and . for one that isn't solved at all, with the timings
+from the last bench --save next to them. Nothing is run. With --input, the
+given input is checked against answers.tsv for that one day only.
+
+verify reruns days and checks their answers against those saved in the
+year's answers.tsv, keyed by day, part and a hash of the input.
+
+examples checks the year's examples.tsv and looks for more in each day's
+puzzle text, keeping those whose stated answer the solution agrees with and
+listing the parts that still have none.
+
+fetch downloads inputs from $AOC_BASE_URL (https://adventofcode.com unless
+set) with the session cookie in $AOC_SESSION, caching them as the year's
+inputs/<day>.txt; a day that is already cached is never fetched again, but
+an empty cached input is. Days can be fetched before they're registered.
+
+submit sends a part's answer, or the one run gives if there's none, to the
+same site. Every guess and its verdict is kept in the year's guesses.tsv, and
+answers that a past verdict already rules out aren't sent. A correct answer
+worked out from the input is also saved to answers.tsv.
+
+new writes a day's module, binary and empty input into the year's crate and
+registers it in the year's lib.rs.
+
+generate prints a random input for days that have a generator, and stress
+solves many of them, reporting how each part scales with the size and the
+seed of any input a day fails on, whether by a parse error, a panic or
+running out of budget.
+
+compare runs the alternative implementations some days register for a part
+next to the main one, on the day's examples, its input and generated inputs,
+and stops at the first input they give different answers for. Those that run
+out of budget aren't counted as disagreeing.
+
+batch runs a day on many inputs, such as a team's, and tabulates the
+answers and timings for each, so that a day that only works for some inputs
+stands out. Each path is an input file, a directory of them or a pattern
+like inputs/2020/eight/*.txt. With --all, each day's inputs are in <dir>
+under a directory named like the day or numbered.
+
+bench times the parse stage and each part separately, reporting the median
+and median absolute deviation, compared with the year's bench.tsv.
+
+a part that checks for cancellation is stopped once it has run for longer
+than --budget, and reported as timed out while the rest of the days still run.
+
+a part that finds no answer for its input is reported with why, and nothing
+is saved to answers.tsv or submitted for it.
+
+solvers log to stderr, only warnings and errors unless -v is given or
+$AOC_LOG sets levels per year or day, e.g. AOC_LOG=2017::seven=trace or
+AOC_LOG=debug,2020=off.
+
+inputs are otherwise read from $AOC_INPUT_DIR/<year>/<day>.txt,
+$AOC_INPUT_DIR/<day>.txt or the year's inputs/<day>.txt, in that order";
+
+const DEFAULT_BUDGET: Duration = Duration::from_secs(60);
+/// Alternatives are often straight translations that are far too slow on
+/// real inputs, so compare gives up on them sooner.
+const COMPARE_BUDGET: Duration = Duration::from_secs(5);
+
+/// Flags shared by every command, pulled out from the positional arguments.
+#[derive(Debug, Default)]
+struct Options {
+    args: Vec<String>,
+    all: bool,
+    input: Option<Source>,
+    format: Format,
+    save: bool,
+    explain: bool,
+    verbosity: usize,
+    example: Option<Source>,
+    answers: Vec<String>,
+    runs: Option<usize>,
+    sizes: Vec<usize>,
+    seed: Option<u64>,
+    budget: Option<Duration>,
+    threshold: Option<f64>,
+}
+
+impl Options {
+    fn parse<I: Iterator<Item=String>>(mut args: I) -> Result<Options, String> {
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            match &*arg {
+                "--all" => options.all = true,
+                "--save" => options.save = true,
+                "--explain" => options.explain = true,
+                "--verbose" => options.verbosity += 1,
+                flag if flag.strip_prefix('-').is_some_and(|vs| !vs.is_empty() && vs.chars().all(|c| c == 'v')) => {
+                    options.verbosity += flag.len() - 1
+                }
+                "-i" | "--input" => match args.next() {
+                    Some(path) => options.input = Some(Source::from_arg(&path)),
+                    None => return Err(format!("{} needs a path", arg)),
+                },
+                "--example" => match args.next() {
+                    Some(path) => options.example = Some(Source::from_arg(&path)),
+                    None => return Err(format!("{} needs a path", arg)),
+                },
+                "--answer" => match args.next() {
+                    Some(answer) => options.answers.push(answer),
+                    None => return Err(format!("{} needs an answer", arg)),
+                },
+                "--runs" => match args.next().and_then(|n| n.parse().ok()).filter(|&n| n > 0) {
+                    Some(runs) => options.runs = Some(runs),
+                    None => return Err(format!("{} needs a positive number", arg)),
+                },
+                "--size" => match args.next().and_then(|n| n.parse().ok()).filter(|&n| n > 0) {
+                    Some(size) => options.sizes.push(size),
+                    None => return Err(format!("{} needs a positive number", arg)),
+                },
+                "--seed" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(seed) => options.seed = Some(seed),
+                    None => return Err(format!("{} needs a number", arg)),
+                },
+                "--budget" => match args.next().and_then(|n| n.parse::<f64>().ok()).filter(|&n| n > 0.0) {
+                    Some(seconds) => options.budget = Some(Duration::from_secs_f64(seconds)),
+                    None => return Err(format!("{} needs a positive number of seconds", arg)),
+                },
+                "--threshold" => match args.next().and_then(|n| n.parse::<f64>().ok()).filter(|&n| n >= 0.0) {
+                    Some(percent) => options.threshold = Some(percent / 100.0),
+                    None => return Err(format!("{} needs a percentage", arg)),
+                },
+                "-f" | "--format" => match args.next().as_ref().and_then(|name| Format::parse(name)) {
+                    Some(format) => options.format = format,
+                    None => return Err(format!("{} needs one of human, json or tsv", arg)),
+                },
+                flag if flag.starts_with('-') && flag != "-" => {
+                    return Err(format!("unknown option {}", flag))
+                }
+                _ => options.args.push(arg),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn budget(&self) -> Duration {
+        self.budget.unwrap_or(DEFAULT_BUDGET)
+    }
+}
+
+/// A part that ran over its budget and gave up, or found no answer.
+#[derive(Debug)]
+struct Unsolved {
+    part: Part,
+    after: Duration,
+    failure: Failure,
+}
+
+impl Unsolved {
+    /// What went wrong, to follow the part it went wrong for.
+    fn reason(&self) -> String {
+        match self.failure {
+            Failure::Cancelled => format!("timed out after {}", human_duration(self.after)),
+            Failure::NoAnswer(ref why) => format!("has no answer: {}", why),
+        }
+    }
+}
+
+/// Entry point for the `aoc` binary.
+pub fn main(years: Vec<Year>) {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => exit(&format!("{}\n{}", e, USAGE)),
+    };
+    if let Err(e) = log::init(options.verbosity) {
+        exit(&e);
+    }
+    let args = options.args.iter().map(|s| &**s).collect::<Vec<_>>();
+
+    let ok = match &args[..] {
+        ["list"] => {
+            list(&years);
+            true
+        }
+        ["status", year, day] if options.input.is_some() => {
+            match find_year(&years, year) {
+                Some(year) => status(year, Some(day), &options),
+                None => false,
+            }
+        }
+        ["status", ..] if options.input.is_some() => fail("--input can only be used with a single day"),
+        ["status", keys @ ..] => {
+            let mut ok = true;
+            for key in keys {
+                ok &= find_year(&years, key).is_some();
+            }
+            ok && years.iter()
+                .filter(|year| keys.is_empty() || keys.contains(&&*year.year.to_string()))
+                .all(|year| status(year, None, &options))
+        }
+        ["run", year, days @ ..] if options.all || !days.is_empty() => {
+            match find_year(&years, year) {
+                Some(year) => run(year, days, &options),
+                None => false,
+            }
+        }
+        ["examples", year, days @ ..] => {
+            match find_year(&years, year) {
+                Some(year) => check_examples(year, days, &options),
+                None => false,
+            }
+        }
+        ["fetch", year, days @ ..] if options.all || !days.is_empty() => {
+            match find_year(&years, year) {
+                Some(year) => fetch(year, days, &options),
+                None => false,
+            }
+        }
+        ["submit", year, day, part, answer @ ..] if answer.len() <= 1 => {
+            match find_year(&years, year) {
+                Some(year) => submit(year, day, part, answer.first().cloned(), &options),
+                None => false,
+            }
+        }
+        ["new", year, day] => {
+            match find_year(&years, year) {
+                Some(year) => new_day(year, day, &options),
+                None => false,
+            }
+        }
+        ["bench", year, days @ ..] if options.all || !days.is_empty() => {
+            match find_year(&years, year) {
+                Some(year) => bench(year, days, &options),
+                None => false,
+            }
+        }
+        ["batch", year, paths @ ..] if options.all && paths.len() == 1 || paths.len() >= 2 => {
+            match find_year(&years, year) {
+                Some(year) => batch(year, paths, &options),
+                None => false,
+            }
+        }
+        ["generate", year, day] => {
+            match find_year(&years, year) {
+                Some(year) => generate(year, day, &options),
+                None => false,
+            }
+        }
+        ["stress", year, days @ ..] if options.all || !days.is_empty() => {
+            match find_year(&years, year) {
+                Some(year) => stress(year, days, &options),
+                None => false,
+            }
+        }
+        ["compare", year, days @ ..] if options.all || !days.is_empty() => {
+            match find_year(&years, year) {
+                Some(year) => compare(year, days, &options),
+                None => false,
+            }
+        }
+        ["verify"] => {
+            let mut ok = true;
+            for year in &years {
+                ok &= verify(year, &[], &options);
+            }
+            ok
+        }
+        ["verify", year, days @ ..] => {
+            match find_year(&years, year) {
+                Some(year) => verify(year, days, &options),
+                None => false,
+            }
+        }
+        _ => fail(USAGE),
+    };
+
+    if !ok {
+        process::exit(1);
+    }
+}
+
+/// Entry point for the per-day binaries, which only ever run their own day.
+pub fn main_day(year: Year, day: u8) {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(ref options) if options.all || !options.args.is_empty() => exit(USAGE),
+        Ok(options) => options,
+        Err(e) => exit(&e),
+    };
+    if let Err(e) = log::init(options.verbosity) {
+        exit(&e);
+    }
+
+    if !run(&year, &[&day.to_string()], &options) {
+        process::exit(1);
+    }
+}
+
+fn fail(message: &str) -> bool {
+    eprintln!("{}", message);
+    false
+}
+
+fn exit(message: &str) -> ! {
+    fail(message);
+    process::exit(1);
+}
+
+fn find_year<'a>(years: &'a [Year], key: &str) -> Option<&'a Year> {
+    let year = years.iter().find(|y| y.year.to_string() == key);
+
+    if year.is_none() {
+        fail(&format!("no solutions registered for {}", key));
+    }
+
+    year
+}
+
+/// The days named on the command line, or every day if there were none and
+/// `all` is set.
+fn select<'a>(year: &'a Year, days: &[&str], all: bool, options: &Options) -> Option<Vec<&'a dyn Solution>> {
+    let solutions = if all {
+        year.days().collect::<Vec<_>>()
+    } else {
+        let mut solutions = vec![];
+        for key in days {
+            match year.find(key) {
+                Some(solution) => solutions.push(solution),
+                None => {
+                    fail(&format!("{} has no day {}", year.year, key));
+                    return None;
+                }
+            }
+        }
+        solutions
+    };
+
+    if options.input.is_some() && solutions.len() != 1 {
+        fail("--input can only be used with a single day");
+        return None;
+    }
+
+    Some(solutions)
+}
+
+fn load_ledger(year: &Year) -> Option<Ledger> {
+    Ledger::load(Ledger::path(year))
+        .map_err(|e| fail(&format!("could not read the {} ledger: {}", year.year, e)))
+        .ok()
+}
+
+fn list(years: &[Year]) {
+    for year in years {
+        println!("{}", year.year);
+
+        for day in year.days() {
+            println!("  {:>2} {}", day.day(), day.name());
+        }
+    }
+}
+
+/// Prints a year's calendar, from its registry, ledger and bench baseline,
+/// with `--input` standing in for the input of `day`, if one is picked.
+fn status(year: &Year, day: Option<&str>, options: &Options) -> bool {
+    let picked = match day.map(|day| select(year, &[day], false, options)) {
+        Some(Some(solutions)) => Some(solutions[0].day()),
+        Some(None) => return false,
+        None => None,
+    };
+    let (ledger, baseline) = match (load_ledger(year), Baseline::load(Baseline::path(year))) {
+        (Some(ledger), Ok(baseline)) => (ledger, baseline),
+        (_, Err(e)) => return fail(&format!("could not read the {} baseline: {}", year.year, e)),
+        _ => return false,
+    };
+    let (inputs, given) = (Inputs::from_env(None), Inputs::from_env(options.input.clone()));
+
+    let calendar = Calendar::new(year, &ledger, &baseline, |solution| {
+        let inputs = if Some(solution.day()) == picked { &given } else { &inputs };
+        inputs.load(year, solution).ok()
+    });
+    println!("{}  {}", year.year, calendar.strip());
+    for day in &calendar.days {
+        println!("  {}", day);
+    }
+    let placeholders = calendar.count(Progress::Placeholder);
+    println!(
+        "{}: {} verified, {} unverified, {} placeholder{}, {} missing",
+        year.year, calendar.count(Progress::Verified), calendar.count(Progress::Unverified),
+        placeholders, if placeholders == 1 { "" } else { "s" }, calendar.count(Progress::Missing),
+    );
+
+    true
+}
+
+fn run(year: &Year, days: &[&str], options: &Options) -> bool {
+    let solutions = match select(year, days, options.all, options) {
+        Some(solutions) => solutions,
+        None => return false,
+    };
+    let mut ledger = None;
+    if options.save {
+        ledger = load_ledger(year);
+        if ledger.is_none() {
+            return false;
+        }
+    }
+
+    let inputs = Inputs::from_env(options.input.clone());
+    let format = options.format;
+    if options.explain && format != Format::Human {
+        return fail("--explain only goes with the human format");
+    }
+
+    if let Some(header) = format.header() {
+        println!("{}", header);
+    }
+
+    let mut ok = true;
+    for solution in solutions {
+        if let Some(header) = format.day_header(year.year, solution.day(), solution.name()) {
+            println!("{}", header);
+        }
+
+        let input = match inputs.load(year, solution) {
+            Ok(input) => input,
+            Err(e) => {
+                ok = fail(&e.to_string());
+                continue;
+            }
+        };
+
+        // A panicking day is reported like any other failure, so that the
+        // days after it still run and the ledger is still saved.
+        let records = match catch(|| solve(year, solution, &input, options.budget())) {
+            Ok(Ok(records)) => records,
+            Ok(Err(e)) => {
+                ok = fail(&e.diagnostic(&input));
+                continue;
+            }
+            Err(e) => {
+                ok = fail(&format!("{} day {} {}", year.year, solution.day(), e));
+                continue;
+            }
+        };
+
+        for record in records {
+            let record = match record {
+                Ok(record) => record,
+                Err(unsolved) => {
+                    ok = fail(&format!("{} day {} part {} {}", year.year, solution.day(), unsolved.part,
+                                       unsolved.reason()));
+                    continue;
+                }
+            };
+
+            println!("{}", format.record(&record));
+            if options.explain {
+                print_explanation(solution, &input, record.part);
+            }
+
+            if let Some(ref mut ledger) = ledger {
+                ledger.insert(Key::new(record.day, record.part, &input, solution.normalise()), &record.answer);
+            }
+        }
+
+        for part in not_solved_yet(solution) {
+            eprintln!("{} day {} part {} not solved yet", year.year, solution.day(), part);
+        }
+    }
+
+    if let Some(ledger) = ledger {
+        if let Err(e) = ledger.save() {
+            ok = fail(&format!("could not save the {} ledger: {}", year.year, e));
+        }
+    }
+
+    ok
+}
+
+/// Reruns days against the ledger, failing if any answer has changed.
+fn verify(year: &Year, days: &[&str], options: &Options) -> bool {
+    let (solutions, ledger) = match (select(year, days, days.is_empty(), options), load_ledger(year)) {
+        (Some(solutions), Some(ledger)) => (solutions, ledger),
+        _ => return false,
+    };
+    let inputs = Inputs::from_env(options.input.clone());
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for solution in solutions {
+        let input = match inputs.load(year, solution) {
+            Ok(input) => input,
+            Err(_) => {
+                println!("{} {:>2} -  no input", year.year, solution.day());
+                missing += 1;
+                continue;
+            }
+        };
+
+        let solved = catch(|| solve(year, solution, &input, options.budget()));
+        let records = match solved.and_then(|records| records.map_err(|e| e.to_string())) {
+            Ok(records) => records,
+            Err(e) => {
+                println!("{} {:>2} -  FAIL {}", year.year, solution.day(), e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for record in records {
+            let record = match record {
+                Ok(record) => record,
+                Err(unsolved) => {
+                    println!("{} {:>2} {}  FAIL {}", year.year, solution.day(), unsolved.part, unsolved.reason());
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            let key = Key::new(record.day, record.part, &input, solution.normalise());
+            let status = match ledger.check(&key, &record.answer) {
+                Check::Pass => {
+                    passed += 1;
+                    "pass".to_string()
+                }
+                Check::Fail { expected } => {
+                    failed += 1;
+                    format!("FAIL expected {}, got {}", expected, record.answer)
+                }
+                Check::Missing => {
+                    missing += 1;
+                    format!("missing, got {}", record.answer)
+                }
+            };
+
+            println!("{} {:>2} {}  {}", year.year, record.day, record.part, status);
+            if options.explain && status.starts_with("FAIL") {
+                print_explanation(solution, &input, record.part);
+            }
+        }
+
+        for part in not_solved_yet(solution) {
+            println!("{} {:>2} {}  not solved yet", year.year, solution.day(), part);
+        }
+    }
+
+    println!("{}: {} passed, {} failed, {} missing", year.year, passed, failed, missing);
+    failed == 0
+}
+
+/// Checks the saved examples and confirms new ones from the puzzle text,
+/// failing if any saved example no longer passes.
+fn check_examples(year: &Year, days: &[&str], options: &Options) -> bool {
+    let solutions = match select(year, days, days.is_empty(), options) {
+        Some(solutions) => solutions,
+        None => return false,
+    };
+    let mut saved = match Examples::load(Examples::path(year)) {
+        Ok(examples) => examples,
+        Err(e) => return fail(&format!("could not read the {} examples: {}", year.year, e)),
+    };
+
+    let (mut passed, mut failed, mut new, mut gaps) = (0, 0, 0, 0);
+    for solution in solutions {
+        let mut covered = BTreeSet::new();
+
+        for example in saved.day(solution.day()) {
+            let status = match example.check(solution) {
+                Ok(()) => {
+                    passed += 1;
+                    "pass".to_string()
+                }
+                Err(got) => {
+                    failed += 1;
+                    format!("FAIL expected {}, got {}", example.answer, got)
+                }
+            };
+
+            covered.insert(example.part);
+            println!("{} {:>2} {}  {}  {}", year.year, example.day, example.part, status, preview(&example.input));
+        }
+
+        let source = fs::read_to_string(examples::source_path(year, solution)).unwrap_or_default();
+        for example in examples::find(solution, &source) {
+            if saved.insert(example.clone()) {
+                new += 1;
+                covered.insert(example.part);
+                println!("{} {:>2} {}  new {}  {}", year.year, example.day, example.part, example.answer, preview(&example.input));
+            }
+        }
+
+        for &part in &[Part::A, Part::B] {
+            if solution.has_part(part) && !covered.contains(&part) {
+                gaps += 1;
+                println!("{} {:>2} {}  no examples", year.year, solution.day(), part);
+            }
+        }
+    }
+
+    println!("{}: {} passed, {} failed, {} new, {} without examples", year.year, passed, failed, new, gaps);
+
+    if options.save {
+        if let Err(e) = saved.save() {
+            return fail(&format!("could not save the {} examples: {}", year.year, e));
+        }
+    }
+
+    failed == 0
+}
+
+/// Prints why a part came to its answer, indented under it, if the day can
+/// say.
+fn print_explanation(solution: &dyn Solution, input: &str, part: Part) {
+    let explanation = solution.parse(input).ok().and_then(|parsed| solution.explain(part, &parsed));
+
+    for line in explanation.iter().flat_map(|explanation| explanation.lines()) {
+        println!("   {}", line);
+    }
+}
+
+/// An example input on one line, cut short if it is long.
+fn preview(input: &str) -> String {
+    let text = format!("{:?}", input);
+
+    if text.chars().count() <= 40 {
+        return text;
+    }
+    text.chars().take(36).chain("...\"".chars()).collect()
+}
+
+/// Downloads the inputs of the days that don't have one cached yet, which
+/// needn't be registered unless they're picked with `--all`.
+fn fetch(year: &Year, days: &[&str], options: &Options) -> bool {
+    let days = if options.all {
+        year.days().map(|solution| (solution.day(), solution.name())).collect::<Vec<_>>()
+    } else {
+        let mut found = vec![];
+        for key in days {
+            let day = match year.find(key) {
+                Some(solution) => Some((solution.day(), solution.name())),
+                None => key.parse().ok().or_else(|| scaffold::number(key))
+                    .and_then(|day| scaffold::name(day).map(|name| (day, name))),
+            };
+            match day {
+                Some(day) => found.push(day),
+                None => return fail(&format!("{} isn't a day of December", key)),
+            }
+        }
+        found
+    };
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => return fail(&e.to_string()),
+    };
+
+    let mut ok = true;
+    for (day, name) in days {
+        match fetcher::fetch(&client, year, day, name) {
+            Ok((path, true)) => println!("{} {:>2} fetched {}", year.year, day, path.display()),
+            Ok((path, false)) => println!("{} {:>2} cached {}", year.year, day, path.display()),
+            Err(e) => ok = fail(&format!("{} {:>2} {}", year.year, day, e)),
+        }
+    }
+
+    ok
+}
+
+/// Submits one part's answer, solving for it if none was given.
+fn submit(year: &Year, day: &str, part: &str, answer: Option<&str>, options: &Options) -> bool {
+    let solution = match select(year, &[day], false, options) {
+        Some(solutions) => solutions[0],
+        None => return false,
+    };
+    let part = match part {
+        "a" | "1" => Part::A,
+        "b" | "2" => Part::B,
+        _ => return fail(&format!("{} isn't a part, use a or b", part)),
+    };
+
+    let (answer, input) = match answer {
+        Some(answer) => (answer.to_string(), None),
+        None => {
+            let input = match Inputs::from_env(options.input.clone()).load(year, solution) {
+                Ok(input) => input,
+                Err(e) => return fail(&e.to_string()),
+            };
+            let record = match solve(year, solution, &input, options.budget()) {
+                Ok(records) => records.into_iter().find(|record| match *record {
+                    Ok(ref record) => record.part == part,
+                    Err(ref unsolved) => unsolved.part == part,
+                }),
+                Err(e) => return fail(&e.diagnostic(&input)),
+            };
+            match record {
+                Some(Ok(record)) => (record.answer.to_string(), Some(input)),
+                Some(Err(unsolved)) => {
+                    return fail(&format!("{} day {} part {} {}", year.year, solution.day(), part, unsolved.reason()))
+                }
+                None if solution.is_placeholder(part) => {
+                    return fail(&format!("{} day {} part {} is not solved yet", year.year, solution.day(), part))
+                }
+                None => return fail(&format!("{} day {} has no part {}", year.year, solution.day(), part)),
+            }
+        }
+    };
+
+    let mut guesses = match Guesses::load(Guesses::path(year)) {
+        Ok(guesses) => guesses,
+        Err(e) => return fail(&format!("could not read the {} guesses: {}", year.year, e)),
+    };
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => return fail(&e.to_string()),
+    };
+
+    let (verdict, message) = match submitter::submit(&client, &mut guesses, year.year, solution.day(), part, &answer) {
+        Ok(submitted) => submitted,
+        Err(e) => return fail(&format!("{} {:>2} {}  {}: {}", year.year, solution.day(), part, answer, e)),
+    };
+    println!("{} {:>2} {}  {}: {}", year.year, solution.day(), part, answer, verdict);
+    println!("{}", message);
+    if let (true, false, Some(ref input)) = (options.explain, verdict == Verdict::Correct, &input) {
+        print_explanation(solution, input, part);
+    }
+
+    let mut ok = verdict == Verdict::Correct;
+    if let Err(e) = guesses.save() {
+        ok = fail(&format!("could not save the {} guesses: {}", year.year, e));
+    }
+
+    if let (Verdict::Correct, Some(input)) = (verdict, input) {
+        let mut ledger = match load_ledger(year) {
+            Some(ledger) => ledger,
+            None => return false,
+        };
+        ledger.insert(Key::new(solution.day(), part, &input, solution.normalise()), &answer.as_str().into());
+        if let Err(e) = ledger.save() {
+            ok = fail(&format!("could not save the {} ledger: {}", year.year, e));
+        }
+    }
+
+    ok
+}
+
+/// Scaffolds a day that the year doesn't have yet.
+fn new_day(year: &Year, day: &str, options: &Options) -> bool {
+    let mut scaffold = match day.parse().ok().and_then(|day| Scaffold::new(year.year, day)) {
+        Some(scaffold) => scaffold,
+        None => return fail(&format!("{} isn't a day between 1 and 25", day)),
+    };
+    if year.find(day).is_some() {
+        return fail(&format!("{} already has day {}", year.year, day));
+    }
+    if options.answers.len() > 2 {
+        return fail("--answer can only be given for parts a and b");
+    }
+
+    if let Some(ref source) = options.example {
+        match source.read() {
+            Ok(example) => scaffold.example = Some(example),
+            Err(e) => return fail(&e.to_string()),
+        }
+    }
+    scaffold.answers = options.answers.clone();
+
+    match scaffold.create(Path::new(year.dir)) {
+        Ok(paths) => {
+            for path in paths {
+                println!("wrote {}", path.display());
+            }
+            true
+        }
+        Err(e) => fail(&format!("could not scaffold {} day {}: {}", year.year, day, e)),
+    }
+}
+
+/// Runs days on every input under the given paths, tabulating how each
+/// input went.
+fn batch(year: &Year, args: &[&str], options: &Options) -> bool {
+    let (days, paths) = if options.all { (&[][..], args) } else { args.split_at(1) };
+    let solutions = match select(year, days, options.all, options) {
+        Some(solutions) => solutions,
+        None => return false,
+    };
+    if options.format != Format::Human {
+        return fail("batch only has the human format");
+    }
+
+    let mut ok = true;
+    for solution in solutions {
+        let paths = if options.all {
+            match batcher::day_dir(Path::new(paths[0]), solution) {
+                Some(dir) => vec![dir],
+                None => continue,
+            }
+        } else {
+            paths.iter().map(PathBuf::from).collect()
+        };
+
+        let mut inputs = vec![];
+        for path in paths {
+            match batcher::expand(&path) {
+                Ok(found) => inputs.extend(found),
+                Err(e) => ok = fail(&format!("{}: {}", path.display(), e)),
+            }
+        }
+
+        println!("{} day {} ({})", year.year, solution.day(), solution.name());
+        let rows = inputs.iter().map(|input| batcher::run(solution, input, options.budget())).collect::<Vec<_>>();
+        print_batch(&rows);
+
+        let solved = rows.iter().filter(|row| row.is_solved()).count();
+        println!("  {}/{} inputs solved", solved, rows.len());
+        for part in not_solved_yet(solution) {
+            println!("  part {} not solved yet", part);
+        }
+        ok &= solved == rows.len();
+    }
+
+    ok
+}
+
+/// A table with a row per input and a column per part, lined up.
+fn print_batch(rows: &[Row]) {
+    let cells = rows.iter().map(|row| {
+        let mut cells = vec![row.input.display().to_string()];
+        match row.parts {
+            Ok(ref parts) => cells.extend(parts.iter().map(|(part, cell)| format!("{}: {}", part, cell))),
+            Err(ref e) => cells.push(format!("FAILED {}", e)),
+        }
+        cells
+    }).collect::<Vec<_>>();
+
+    // The last cell in a row isn't padded, so a long failure doesn't push the
+    // other rows' columns apart.
+    let mut widths = vec![];
+    for row in &cells {
+        for (i, cell) in row[..row.len() - 1].iter().enumerate() {
+            if widths.len() <= i {
+                widths.push(0);
+            }
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    for row in cells {
+        let line = row.iter().enumerate()
+            .map(|(i, cell)| format!("{:<width$}", cell, width = widths.get(i).cloned().unwrap_or(0)))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("  {}", line.trim_end());
+    }
+}
+
+/// Times each stage of each day, failing if any has regressed from the
+/// baseline.
+fn bench(year: &Year, days: &[&str], options: &Options) -> bool {
+    let solutions = match select(year, days, options.all, options) {
+        Some(solutions) => solutions,
+        None => return false,
+    };
+    let mut baseline = match Baseline::load(Baseline::path(year)) {
+        Ok(baseline) => baseline,
+        Err(e) => return fail(&format!("could not read the {} baseline: {}", year.year, e)),
+    };
+
+    let inputs = Inputs::from_env(options.input.clone());
+    let runs = options.runs.unwrap_or(10);
+    let threshold = options.threshold.unwrap_or(0.1);
+
+    let mut ok = true;
+    'days: for solution in solutions {
+        println!("{} day {} ({})", year.year, solution.day(), solution.name());
+
+        let input = match inputs.load(year, solution) {
+            Ok(input) => input,
+            Err(e) => {
+                ok = fail(&e.to_string());
+                continue;
+            }
+        };
+
+        if let Err(e) = solution.parse(&input) {
+            ok = fail(&e.diagnostic(&input));
+            continue;
+        }
+
+        let mut samples = BTreeMap::<Stage, Vec<Duration>>::new();
+        for _ in 0..runs {
+            let start = Instant::now();
+            let parsed = solution.parse(&input).expect("parsed once already");
+            samples.entry(Stage::Parse).or_default().push(start.elapsed());
+
+            for &part in &[Part::A, Part::B] {
+                if solution.is_placeholder(part) {
+                    continue;
+                }
+
+                let token = Token::within(options.budget());
+                let start = Instant::now();
+                match solution.solve_within(part, &parsed, &token) {
+                    Some(Ok(_)) => samples.entry(Stage::Part(part)).or_default().push(start.elapsed()),
+                    Some(Err(failure)) => {
+                        let unsolved = Unsolved { part, after: start.elapsed(), failure };
+                        ok = fail(&format!("{:>5}: {}", Stage::Part(part), unsolved.reason()));
+                        continue 'days;
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        for (stage, samples) in samples {
+            let stats = Stats::from_samples(samples);
+            let mut line = format!("{:>5}: {}", stage, stats);
+
+            if let Some(previous) = baseline.get(solution.day(), stage) {
+                let change = stats.compare(previous, threshold);
+                line.push_str(&format!(" (was {}, {})", previous, change));
+
+                if let Change::Regressed(_) = change {
+                    ok = false;
+                }
+            }
+
+            println!("{}", line);
+            if options.save {
+                baseline.insert(solution.day(), stage, stats);
+            }
+        }
+    }
+
+    if options.save {
+        if let Err(e) = baseline.save() {
+            ok = fail(&format!("could not save the {} baseline: {}", year.year, e));
+        }
+    }
+
+    ok
+}
+
+/// Prints a random input for a day.
+fn generate(year: &Year, day: &str, options: &Options) -> bool {
+    let solution = match year.find(day) {
+        Some(solution) => solution,
+        None => return fail(&format!("{} has no day {}", year.year, day)),
+    };
+    let size = match options.sizes[..] {
+        [] => 100,
+        [size] => size,
+        _ => return fail("--size can only be given once to generate"),
+    };
+    let seed = options.seed.unwrap_or_else(Rng::seed_from_time);
+
+    match solution.generate(&mut Rng::new(seed), size) {
+        Some(input) => {
+            if options.seed.is_none() {
+                eprintln!("seed {}", seed);
+            }
+            print!("{}", input);
+            true
+        }
+        None => fail(&format!("{} day {} has no generator", year.year, solution.day())),
+    }
+}
+
+/// Solves generated inputs of each size, reporting how long each part took
+/// and failing on any input a day couldn't handle.
+fn stress(year: &Year, days: &[&str], options: &Options) -> bool {
+    let solutions = match select(year, days, options.all, options) {
+        Some(solutions) => solutions,
+        None => return false,
+    };
+    let sizes = if options.sizes.is_empty() { vec![10, 100, 1000] } else { options.sizes.clone() };
+    let runs = options.runs.unwrap_or(10);
+    let seed = options.seed.unwrap_or_else(Rng::seed_from_time);
+
+    let mut ok = true;
+    for solution in solutions {
+        if solution.generate(&mut Rng::new(seed), 1).is_none() {
+            if !options.all {
+                ok = fail(&format!("{} day {} has no generator", year.year, solution.day()));
+            }
+            continue;
+        }
+        println!("{} day {} ({}), seeds {} to {}", year.year, solution.day(), solution.name(),
+                 seed, seed.wrapping_add(runs as u64 - 1));
+
+        for &size in &sizes {
+            let mut samples = BTreeMap::<Part, Vec<Duration>>::new();
+            let mut failures = vec![];
+
+            for run in 0..runs as u64 {
+                let seed = seed.wrapping_add(run);
+                let input = solution.generate(&mut Rng::new(seed), size).expect("generated once already");
+
+                match generator::trial(solution, &input, options.budget()) {
+                    Ok(timings) => for (part, duration) in timings {
+                        samples.entry(part).or_default().push(duration);
+                    },
+                    Err(e) => failures.push(format!("    seed {}: {}", seed, e)),
+                }
+            }
+
+            let timings = samples.into_iter()
+                .map(|(part, samples)| format!("{} {}", part, Stats::from_samples(samples)))
+                .collect::<Vec<_>>();
+            println!("  size {}: {}/{} solved{}{}", size, runs - failures.len(), runs,
+                     if timings.is_empty() { "" } else { ", " }, timings.join(", "));
+
+            for failure in failures {
+                ok = fail(&failure);
+            }
+        }
+    }
+
+    ok
+}
+
+/// Runs each day's implementations of a part side by side, on its examples,
+/// its input and generated inputs, until they disagree.
+fn compare(year: &Year, days: &[&str], options: &Options) -> bool {
+    let solutions = match select(year, days, options.all, options) {
+        Some(solutions) => solutions,
+        None => return false,
+    };
+    let examples = match Examples::load(Examples::path(year)) {
+        Ok(examples) => examples,
+        Err(e) => return fail(&format!("could not read the {} examples: {}", year.year, e)),
+    };
+    let inputs = Inputs::from_env(options.input.clone());
+    let sizes = if options.sizes.is_empty() { vec![10, 100] } else { options.sizes.clone() };
+    let runs = options.runs.unwrap_or(10);
+    let seed = options.seed.unwrap_or_else(Rng::seed_from_time);
+    let budget = options.budget.unwrap_or(COMPARE_BUDGET);
+
+    let mut ok = true;
+    'days: for solution in solutions {
+        let parts = [Part::A, Part::B].iter()
+            .cloned()
+            .filter(|&part| !solution.alternatives(part).is_empty())
+            .collect::<Vec<_>>();
+        if parts.is_empty() {
+            if !options.all {
+                ok = fail(&format!("{} day {} has no alternatives", year.year, solution.day()));
+            }
+            continue;
+        }
+
+        println!("{} day {} ({})", year.year, solution.day(), solution.name());
+        for &part in &parts {
+            println!("  {}: {}, {}", part, comparer::MAIN, solution.alternatives(part).join(", "));
+        }
+
+        let mut cases = examples.day(solution.day())
+            .map(|example| (format!("example {}", preview(&example.input)), example.input.clone(), vec![example.part]))
+            .collect::<Vec<_>>();
+        if let Ok(input) = inputs.load(year, solution) {
+            cases.push(("the input".to_string(), input, parts.clone()));
+        }
+        let generated = sizes.iter()
+            .flat_map(|&size| (0..runs as u64).map(move |run| (size, seed.wrapping_add(run))))
+            .filter_map(|(size, seed)| {
+                let input = solution.generate(&mut Rng::new(seed), size)?;
+                let name = format!("size {} seed {} (aoc generate {} {} --size {} --seed {})",
+                                   size, seed, year.year, solution.day(), size, seed);
+                Some((name, input, parts.clone()))
+            });
+
+        let (mut agreed, mut inconclusive) = (0, 0);
+        for (name, input, parts) in cases.into_iter().chain(generated) {
+            match comparer::compare(solution, &input, &parts, budget) {
+                Ok(Comparison::Agreed) => agreed += 1,
+                Ok(Comparison::Inconclusive) => inconclusive += 1,
+                Ok(Comparison::Diverged(divergence)) => {
+                    ok = fail(&format!("  {}\n  on {}", divergence, name));
+                    continue 'days;
+                }
+                Err(e) => {
+                    ok = fail(&format!("  could not parse {}: {}", name, e));
+                    continue 'days;
+                }
+            }
+        }
+        println!("  agreed on {} inputs and ran out of time on {}", agreed, inconclusive);
+    }
+
+    ok
+}
+
+/// The parts a day has only registered as placeholders, which `solve` skips
+/// and the commands report instead of an answer.
+fn not_solved_yet(solution: &dyn Solution) -> Vec<Part> {
+    [Part::A, Part::B].iter().cloned().filter(|&part| solution.is_placeholder(part)).collect()
+}
+
+/// Parses the input then runs each part, giving each `budget` before it is
+/// cancelled. Placeholder parts are left out, see [`not_solved_yet`].
+fn solve(year: &Year, solution: &dyn Solution, input: &str, budget: Duration)
+    -> Result<Vec<Result<Record, Unsolved>>, ParseError>
+{
+    let parsed = solution.parse(input)?;
+    let mut records = vec![];
+
+    for &part in &[Part::A, Part::B] {
+        if solution.is_placeholder(part) {
+            continue;
+        }
+
+        let token = Token::within(budget);
+        let start = Instant::now();
+        match solution.solve_within(part, &parsed, &token) {
+            Some(Ok(answer)) => records.push(Ok(Record {
+                year: year.year,
+                day: solution.day(),
+                name: solution.name(),
+                part,
+                answer,
+                duration: start.elapsed(),
+            })),
+            Some(Err(failure)) => records.push(Err(Unsolved { part, after: start.elapsed(), failure })),
+            None => {}
+        }
+    }
+
+    Ok(records)
+}
+
+#[test]
+fn test_options() {
+    let parse = |args: &[&str]| Options::parse(args.iter().map(|s| s.to_string()));
+
+    let options = parse(&["run", "2017", "10", "--input", "-"]).unwrap();
+    assert_eq!(options.args, ["run", "2017", "10"]);
+    assert_eq!(options.input, Some(Source::Stdin));
+    assert!(!options.all);
+
+    let options = parse(&["run", "-i", "mine.txt", "2017", "--all"]).unwrap();
+    assert_eq!(options.args, ["run", "2017"]);
+    assert_eq!(options.input, Some(Source::Path("mine.txt".into())));
+    assert!(options.all);
+
+    assert_eq!(options.format, Format::Human);
+
+    let options = parse(&["run", "2017", "--format", "json", "--all"]).unwrap();
+    assert_eq!(options.format, Format::Json);
+
+    assert_eq!(parse(&["run", "2017", "7", "-vv"]).unwrap().verbosity, 2);
+    assert_eq!(parse(&["run", "2017", "é"]).unwrap().args, ["run", "2017", "é"]);
+    assert_eq!(parse(&["run", "2017", "xv"]).unwrap().verbosity, 0);
+    assert_eq!(parse(&["run", "2017", "7", "-v", "--verbose"]).unwrap().verbosity, 2);
+
+    assert!(!options.save);
+    assert!(parse(&["run", "2017", "10", "--save"]).unwrap().save);
+
+    let options = parse(&["bench", "2017", "15", "--runs", "3", "--threshold", "25"]).unwrap();
+    assert_eq!(options.runs, Some(3));
+    assert_eq!(options.threshold, Some(0.25));
+
+    let options = parse(&["new", "2020", "13", "--example", "-", "--answer", "295", "--answer", "1068781"]).unwrap();
+    assert_eq!(options.args, ["new", "2020", "13"]);
+    assert_eq!(options.example, Some(Source::Stdin));
+    assert_eq!(options.answers, ["295", "1068781"]);
+
+    assert_eq!(parse(&["run", "2017", "13", "--budget", "0.5"]).unwrap().budget(), Duration::from_millis(500));
+    assert_eq!(parse(&["run", "2017", "13"]).unwrap().budget(), DEFAULT_BUDGET);
+    assert!(parse(&["run", "2017", "13", "--budget", "0"]).is_err());
+
+    let options = parse(&["stress", "2020", "8", "--size", "10", "--size", "1000", "--seed", "42"]).unwrap();
+    assert_eq!(options.sizes, [10, 1000]);
+    assert_eq!(options.seed, Some(42));
+
+    assert!(parse(&["bench", "2017", "15", "--runs", "0"]).is_err());
+    assert!(parse(&["stress", "2020", "8", "--size", "0"]).is_err());
+    assert!(parse(&["run", "2017", "10", "--input"]).is_err());
+    assert!(parse(&["run", "2017", "10", "-f", "xml"]).is_err());
+    assert!(parse(&["run", "2017", "--bogus"]).is_err());
+}
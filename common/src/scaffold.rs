@@ -0,0 +1,289 @@
+//! Generating the skeleton of a new day: its module, its binary, its
+//! registration in the year's library and an empty input to paste into.
+//!
+//! Days are named after their number spelled out (`twenty_three`), which is
+//! the name used for the module, the `<year>-<name>` binary and
+//! `inputs/<name>.txt`.
+//!
+//! Both parts start out as stubs registered as placeholders, so the day runs
+//! from the start but `aoc status` and the runner don't take its answers for
+//! real ones until the stub is replaced and its `placeholder` dropped. The
+//! smoke tests are ignored until then as well, as the stubs only return an
+//! empty answer of the example's type.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+const NAMES: [&str; 25] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen",
+    "eighteen", "nineteen", "twenty", "twenty_one", "twenty_two", "twenty_three",
+    "twenty_four", "twenty_five",
+];
+
+/// The name of a day, if it is one of the 25.
+pub fn name(day: u8) -> Option<&'static str> {
+    NAMES.get((day as usize).wrapping_sub(1)).cloned()
+}
+
+/// The day going by `name`, the other way round from [`name`].
+pub fn number(name: &str) -> Option<u8> {
+    NAMES.iter().position(|&n| n == name).map(|i| i as u8 + 1)
+}
+
+#[derive(Debug, Clone)]
+pub struct Scaffold {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    /// An example input to stub the smoke tests with.
+    pub example: Option<String>,
+    /// The example's answers for part A, then part B.
+    pub answers: Vec<String>,
+}
+
+impl Scaffold {
+    pub fn new(year: u16, day: u8) -> Option<Scaffold> {
+        name(day).map(|name| Scaffold { year, day, name, example: None, answers: vec![] })
+    }
+
+    /// The day's module, with the puzzle text left to be pasted in.
+    pub fn module(&self) -> String {
+        let (a_type, a_stub) = self.stub(0);
+        let (b_type, b_stub) = self.stub(1);
+        let mut source = format!("/*
+--- Day {day}: ---
+
+Paste the puzzle text here, where aoc examples will look for its examples.
+*/
+
+use aoc_common::parse::{{ParseError, Parser}};
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {{
+    let p = Parser::new(input);
+
+    p.lines(|line| Ok(line.trim().to_string()))
+}}
+
+/// A placeholder until part one is solved.
+pub fn solve_a(_lines: &[String]) -> {a_type} {{
+    {a_stub}
+}}
+
+/*
+--- Part Two ---
+*/
+
+/// A placeholder until part two is solved.
+pub fn solve_b(_lines: &[String]) -> {b_type} {{
+    {b_stub}
+}}
+", day = self.day, a_type = a_type, a_stub = a_stub, b_type = b_type, b_stub = b_stub);
+
+        if let Some(ref example) = self.example {
+            for (i, part) in ["a", "b"].iter().enumerate() {
+                if i > 0 && self.answers.len() < 2 {
+                    break;
+                }
+
+                let answer = match self.answers.get(i) {
+                    Some(answer) => literal(answer),
+                    None => "0 /* the example's answer */".to_string(),
+                };
+                source += &format!("
+#[test]
+#[ignore = \"part {part} is still a placeholder\"]
+fn smoke_{part}() {{
+    assert_eq!(solve_{part}(&parse({input}).unwrap()), {answer});
+}}
+", part = part, input = raw_string(example.trim_end()), answer = answer);
+            }
+        }
+
+        source
+    }
+
+    /// The return type of part `i`'s stub and what it returns, which is text
+    /// if the example's answer is, so that the smoke test compiles.
+    fn stub(&self, i: usize) -> (&'static str, &'static str) {
+        match self.answers.get(i) {
+            Some(answer) if answer.parse::<i64>().is_err() => ("String", "String::new()"),
+            _ => ("usize", "0"),
+        }
+    }
+
+    /// The per-day binary, which only runs this day.
+    pub fn binary(&self) -> String {
+        format!("extern crate advent_of_code_{year};
+extern crate aoc_common;
+
+fn main() {{
+    aoc_common::runner::main_day(advent_of_code_{year}::year(), {day});
+}}
+", year = self.year, day = self.day)
+    }
+
+    /// `lib` with the module declared after the others and the day
+    /// registered at the end of `year()`.
+    pub fn register(&self, lib: &str) -> Result<String, String> {
+        let lines = lib.lines().collect::<Vec<_>>();
+        let last_mod = lines.iter().rposition(|line| line.starts_with("pub mod ") && line.ends_with(';'))
+            .ok_or("no mod declarations to add to")?;
+        let last_alias = lines.iter().rposition(|line| line.starts_with("pub use ") && line.contains(" as day"));
+        let year_fn = lines.iter().position(|line| line.starts_with("pub fn year() -> Year {"))
+            .ok_or("no year() to register the day in")?;
+        let year_end = lines[year_fn..].iter().position(|&line| line == "}")
+            .map(|i| year_fn + i)
+            .ok_or("year() isn't closed")?;
+
+        if lines.contains(&&*format!("pub mod {};", self.name)) {
+            return Err(format!("day {} is already declared", self.name));
+        }
+
+        let declaration = format!("pub mod {};", self.name);
+        let alias = format!("pub use {} as day{:02};", self.name, self.day);
+        let registration = format!("        .register(Day::new({day}, \"{name}\")
+            .parse({name}::parse)
+            .part_a(|lines| {name}::solve_a(lines))
+            .part_b(|lines| {name}::solve_b(lines))
+            .placeholder(aoc_common::Part::A)
+            .placeholder(aoc_common::Part::B))", day = self.day, name = self.name);
+
+        let mut out = vec![];
+        for (i, &line) in lines.iter().enumerate() {
+            if i == year_end {
+                out.push(&*registration);
+            }
+            out.push(line);
+            if i == last_mod {
+                out.push(&*declaration);
+            }
+            if Some(i) == last_alias {
+                out.push(&*alias);
+            }
+        }
+
+        Ok(out.join("\n") + "\n")
+    }
+
+    /// Writes the day into the year's crate at `dir`, returning the files
+    /// created. Nothing is written if any of them already exists.
+    pub fn create(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let module = dir.join("src").join(format!("{}.rs", self.name));
+        let binary = dir.join("src").join("bin").join(format!("{}-{}.rs", self.year, self.name));
+        let input = dir.join("inputs").join(format!("{}.txt", self.name));
+        let lib_path = dir.join("src").join("lib.rs");
+
+        for path in &[&module, &binary, &input] {
+            if path.exists() {
+                let message = format!("{} already exists", path.display());
+                return Err(io::Error::new(ErrorKind::AlreadyExists, message));
+            }
+        }
+
+        let lib = self.register(&fs::read_to_string(&lib_path)?).map_err(|e| {
+            io::Error::new(ErrorKind::InvalidData, format!("{}: {}", lib_path.display(), e))
+        })?;
+
+        fs::create_dir_all(dir.join("inputs"))?;
+        fs::write(&module, self.module())?;
+        fs::write(&binary, self.binary())?;
+        fs::write(&input, "")?;
+        fs::write(&lib_path, lib)?;
+
+        Ok(vec![module, binary, input, lib_path])
+    }
+}
+
+/// A raw string literal holding `text`, with enough `#`s to not end early.
+fn raw_string(text: &str) -> String {
+    let hashes = (0..).find(|&n| !text.contains(&format!("\"{}", "#".repeat(n)))).unwrap_or(0);
+    let hashes = "#".repeat(hashes);
+
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+/// An answer as a Rust expression: numbers as they are, anything else as a
+/// string.
+fn literal(answer: &str) -> String {
+    if answer.parse::<i64>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{:?}", answer)
+    }
+}
+
+#[test]
+fn test_name() {
+    assert_eq!(name(1), Some("one"));
+    assert_eq!(name(23), Some("twenty_three"));
+    assert_eq!(name(0), None);
+    assert_eq!(name(26), None);
+    assert_eq!(number("twenty_three"), Some(23));
+    assert_eq!(number("day"), None);
+}
+
+#[test]
+fn test_register() {
+    let lib = "extern crate aoc_common;
+
+use aoc_common::{Day, Year};
+
+pub mod one;
+
+pub use one as day01;
+
+/// Every day solved for 2020.
+pub fn year() -> Year {
+    Year::new(2020, env!(\"CARGO_MANIFEST_DIR\"))
+        .register(Day::new(1, \"one\")
+            .parse(one::parse)
+            .part_a(|numbers| one::solve_a(numbers)))
+}
+
+#[cfg(test)]
+mod tests {
+}
+";
+    let scaffold = Scaffold::new(2020, 2).unwrap();
+    let lib = scaffold.register(lib).unwrap();
+
+    assert!(lib.contains("pub mod one;\npub mod two;\n"));
+    assert!(lib.contains("pub use one as day01;\npub use two as day02;\n"));
+    assert!(lib.contains("one::solve_a(numbers)))
+        .register(Day::new(2, \"two\")
+            .parse(two::parse)
+            .part_a(|lines| two::solve_a(lines))
+            .part_b(|lines| two::solve_b(lines))
+            .placeholder(aoc_common::Part::A)
+            .placeholder(aoc_common::Part::B))
+}
+"));
+    assert!(scaffold.register(&lib).is_err());
+    assert!(scaffold.register("pub mod one;\n").is_err());
+}
+
+#[test]
+fn test_module() {
+    let mut scaffold = Scaffold::new(2020, 13).unwrap();
+    assert!(!scaffold.module().contains("#[test]"));
+
+    scaffold.example = Some("939\n7,13,x,\"x\"\n".to_string());
+    let module = scaffold.module();
+    assert!(module.contains("fn smoke_a() {
+    assert_eq!(solve_a(&parse(r#\"939\n7,13,x,\"x\"\"#).unwrap()), 0 /* the example's answer */);"));
+    assert!(!module.contains("smoke_b"));
+
+    scaffold.answers = vec!["295".to_string(), "abc".to_string()];
+    let module = scaffold.module();
+    assert!(module.contains("unwrap()), 295);"));
+    assert!(module.contains("#[ignore = \"part b is still a placeholder\"]
+fn smoke_b() {
+    assert_eq!(solve_b(&parse(r#\"939\n7,13,x,\"x\"\"#).unwrap()), \"abc\");"));
+    assert!(module.contains("pub fn solve_a(_lines: &[String]) -> usize {\n    0\n}"));
+    assert!(module.contains("pub fn solve_b(_lines: &[String]) -> String {\n    String::new()\n}"));
+
+    assert!(!module.contains("unimplemented!"));
+    assert!(scaffold.binary().contains("main_day(advent_of_code_2020::year(), 13)"));
+}
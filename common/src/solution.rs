@@ -0,0 +1,545 @@
+use std::any::Any;
+use std::fmt::{self, Display};
+
+use answer::Answer;
+use cancel::{Cancelled, Token};
+use generate::Rng;
+use input::{self, Normalise};
+use parse::ParseError;
+
+/// Every puzzle comes in two halves, the second unlocked by solving the first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+/// Why a part came to no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// It ran over its budget and gave up.
+    Cancelled,
+    /// The input has no answer, or none that this way of solving can find,
+    /// for the reason given.
+    NoAnswer(String),
+}
+
+impl Failure {
+    pub fn no_answer<S: Into<String>>(why: S) -> Failure {
+        Failure::NoAnswer(why.into())
+    }
+}
+
+impl From<Cancelled> for Failure {
+    fn from(_: Cancelled) -> Failure {
+        Failure::Cancelled
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Failure::Cancelled => write!(f, "{}", Cancelled),
+            Failure::NoAnswer(ref why) => write!(f, "no answer: {}", why),
+        }
+    }
+}
+
+/// What a part can give back: anything that converts into an [`Answer`], or
+/// a `Result` of one for parts that can fail.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, Failure>;
+}
+
+impl<A: Into<Answer>> IntoAnswer for A {
+    fn into_answer(self) -> Result<Answer, Failure> {
+        Ok(self.into())
+    }
+}
+
+impl<A: Into<Answer>, E: Into<Failure>> IntoAnswer for Result<A, E> {
+    fn into_answer(self) -> Result<Answer, Failure> {
+        self.map(Into::into).map_err(Into::into)
+    }
+}
+
+/// A day's input after its parse stage, ready to be handed to either part.
+pub struct Parsed(Box<dyn Any>);
+
+pub trait Solution {
+    /// The day of December the puzzle was released on, `1..=25`.
+    fn day(&self) -> u8;
+
+    /// The name the day's files go by, e.g. `twenty_three`.
+    fn name(&self) -> &'static str;
+
+    /// Whether a part has been implemented yet.
+    fn has_part(&self, part: Part) -> bool;
+
+    /// Whether a part is only a stand-in, registered so the day runs but
+    /// with nothing worth checking behind its answer.
+    fn is_placeholder(&self, _part: Part) -> bool {
+        false
+    }
+
+    /// How much the raw puzzle input is tidied up before it is parsed.
+    fn normalise(&self) -> Normalise {
+        Normalise::default()
+    }
+
+    /// Turns the raw puzzle input into whatever both parts work from, once
+    /// it has been normalised.
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError>;
+
+    /// Solves one part from an already parsed input, giving up if `token`
+    /// is cancelled, or `None` if the part hasn't been implemented yet.
+    fn solve_within(&self, part: Part, parsed: &Parsed, token: &Token) -> Option<Result<Answer, Failure>>;
+
+    /// Why a part's answer is what it is, for days that can say and inputs
+    /// that have one: the entries, path or program the answer was worked
+    /// out from.
+    fn explain(&self, _part: Part, _parsed: &Parsed) -> Option<String> {
+        None
+    }
+
+    /// The names of other implementations of a part, which should always
+    /// give the same answer as the one registered.
+    fn alternatives(&self, _part: Part) -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Solves one part with one of its alternative implementations, or
+    /// `None` if it has none by that name.
+    fn solve_alternative(&self, _part: Part, _name: &str, _parsed: &Parsed, _token: &Token)
+        -> Option<Result<Answer, Failure>>
+    {
+        None
+    }
+
+    /// A random input of roughly `size`, for days with a generator.
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Solves one part from an already parsed input, for however long it
+    /// takes.
+    fn solve_parsed(&self, part: Part, parsed: &Parsed) -> Option<Result<Answer, Failure>> {
+        self.solve_within(part, parsed, &Token::none())
+    }
+
+    /// Parses then solves one part against the raw puzzle input.
+    fn solve(&self, part: Part, input: &str) -> Result<Option<Result<Answer, Failure>>, ParseError> {
+        if !self.has_part(part) {
+            return Ok(None);
+        }
+
+        Ok(self.solve_parsed(part, &self.parse(input)?))
+    }
+}
+
+type Parser<T> = Box<dyn Fn(&str) -> Result<Box<T>, ParseError>>;
+type Solver<T> = Box<dyn Fn(&T, &Token) -> Result<Answer, Failure>>;
+type Explainer<T> = Box<dyn Fn(&T) -> Option<String>>;
+type Generator = Box<dyn Fn(&mut Rng, usize) -> String>;
+
+/// A `Solution` built from plain functions, which is what almost every day
+/// registers.
+///
+/// Both parts are handed the raw input unless a parse stage is given first,
+/// in which case they share its output:
+///
+/// ```text
+/// Day::new(15, "fifteen")
+///     .parse(fifteen::parse_generators)
+///     .part_a(|&(a, b)| fifteen::solve_a(a, b))
+/// ```
+///
+/// Parts that can run for a long time are registered with
+/// `part_a_cancellable`, and are handed a [`Token`] to check as they go.
+/// Parts that can't answer every input return a `Result`, failing with
+/// [`Failure::NoAnswer`] rather than panicking or making an answer up.
+/// Parts that can show their working register an `explain_a` as well, which
+/// returns the witness behind the answer, if there is one, and days that can make up inputs
+/// of their own for stress testing register a `generate`. Days with more than
+/// one way of solving a part register the others with `alternative_a`, so
+/// that `aoc compare` can check they agree.
+///
+/// Inputs are normalised before they're parsed, which days whose whitespace
+/// matters can turn down to just line endings with `normalise`.
+///
+/// Parts registered before they've really been solved, like the stubs
+/// `aoc scaffold` writes, are marked with `placeholder` so that `aoc status`
+/// doesn't count them as done.
+pub struct Day<T: ?Sized = str> {
+    day: u8,
+    name: &'static str,
+    normalise: Normalise,
+    parse: Parser<T>,
+    a: Option<Solver<T>>,
+    b: Option<Solver<T>>,
+    explain_a: Option<Explainer<T>>,
+    explain_b: Option<Explainer<T>>,
+    generate: Option<Generator>,
+    alternatives: Vec<(Part, &'static str, Solver<T>)>,
+    placeholders: Vec<Part>,
+}
+
+impl Day {
+    pub fn new(day: u8, name: &'static str) -> Day {
+        Day {
+            day,
+            name,
+            normalise: Normalise::default(),
+            parse: Box::new(|input| Ok(input.into())),
+            a: None,
+            b: None,
+            explain_a: None,
+            explain_b: None,
+            generate: None,
+            alternatives: vec![],
+            placeholders: vec![],
+        }
+    }
+
+    pub fn parse<F, T>(self, parse: F) -> Day<T>
+        where F: Fn(&str) -> Result<T, ParseError> + 'static, T: 'static
+    {
+        assert!(self.a.is_none() && self.b.is_none(), "parse must come before the parts");
+
+        Day {
+            day: self.day,
+            name: self.name,
+            normalise: self.normalise,
+            parse: Box::new(move |input| parse(input).map(Box::new)),
+            a: None,
+            b: None,
+            explain_a: None,
+            explain_b: None,
+            generate: self.generate,
+            alternatives: vec![],
+            placeholders: vec![],
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> Day<T> {
+    pub fn normalise(mut self, how: Normalise) -> Day<T> {
+        self.normalise = how;
+        self
+    }
+
+    pub fn part_a<F, R>(mut self, solve: F) -> Day<T>
+        where F: Fn(&T) -> R + 'static, R: IntoAnswer
+    {
+        self.a = Some(Box::new(move |input, _| solve(input).into_answer()));
+        self
+    }
+
+    pub fn part_b<F, R>(mut self, solve: F) -> Day<T>
+        where F: Fn(&T) -> R + 'static, R: IntoAnswer
+    {
+        self.b = Some(Box::new(move |input, _| solve(input).into_answer()));
+        self
+    }
+
+    pub fn part_a_cancellable<F, R, E>(mut self, solve: F) -> Day<T>
+        where F: Fn(&T, &Token) -> Result<R, E> + 'static, R: Into<Answer>, E: Into<Failure>
+    {
+        self.a = Some(Box::new(move |input, token| solve(input, token).into_answer()));
+        self
+    }
+
+    pub fn part_b_cancellable<F, R, E>(mut self, solve: F) -> Day<T>
+        where F: Fn(&T, &Token) -> Result<R, E> + 'static, R: Into<Answer>, E: Into<Failure>
+    {
+        self.b = Some(Box::new(move |input, token| solve(input, token).into_answer()));
+        self
+    }
+
+    pub fn explain_a<F, W>(mut self, explain: F) -> Day<T>
+        where F: Fn(&T) -> Option<W> + 'static, W: Display
+    {
+        self.explain_a = Some(Box::new(move |input| explain(input).map(|witness| witness.to_string())));
+        self
+    }
+
+    pub fn explain_b<F, W>(mut self, explain: F) -> Day<T>
+        where F: Fn(&T) -> Option<W> + 'static, W: Display
+    {
+        self.explain_b = Some(Box::new(move |input| explain(input).map(|witness| witness.to_string())));
+        self
+    }
+
+    pub fn generate<F>(mut self, generate: F) -> Day<T>
+        where F: Fn(&mut Rng, usize) -> String + 'static
+    {
+        self.generate = Some(Box::new(generate));
+        self
+    }
+
+    pub fn alternative_a<F, R>(self, name: &'static str, solve: F) -> Day<T>
+        where F: Fn(&T) -> R + 'static, R: IntoAnswer
+    {
+        self.alternative(Part::A, name, Box::new(move |input, _| solve(input).into_answer()))
+    }
+
+    pub fn alternative_b<F, R>(self, name: &'static str, solve: F) -> Day<T>
+        where F: Fn(&T) -> R + 'static, R: IntoAnswer
+    {
+        self.alternative(Part::B, name, Box::new(move |input, _| solve(input).into_answer()))
+    }
+
+    pub fn alternative_a_cancellable<F, R, E>(self, name: &'static str, solve: F) -> Day<T>
+        where F: Fn(&T, &Token) -> Result<R, E> + 'static, R: Into<Answer>, E: Into<Failure>
+    {
+        self.alternative(Part::A, name, Box::new(move |input, token| solve(input, token).into_answer()))
+    }
+
+    pub fn alternative_b_cancellable<F, R, E>(self, name: &'static str, solve: F) -> Day<T>
+        where F: Fn(&T, &Token) -> Result<R, E> + 'static, R: Into<Answer>, E: Into<Failure>
+    {
+        self.alternative(Part::B, name, Box::new(move |input, token| solve(input, token).into_answer()))
+    }
+
+    /// Marks a registered part as a stand-in rather than a solution.
+    pub fn placeholder(mut self, part: Part) -> Day<T> {
+        assert!(self.solver(part).is_some(), "part {} needs registering before it's a placeholder", part);
+
+        self.placeholders.push(part);
+        self
+    }
+
+    fn alternative(mut self, part: Part, name: &'static str, solve: Solver<T>) -> Day<T> {
+        assert!(self.solver(part).is_some(), "part {} needs solving before it has alternatives", part);
+        assert!(self.alternatives.iter().all(|&(p, n, _)| (p, n) != (part, name)), "{} registered twice", name);
+
+        self.alternatives.push((part, name, solve));
+        self
+    }
+
+    fn solver(&self, part: Part) -> Option<&Solver<T>> {
+        match part {
+            Part::A => self.a.as_ref(),
+            Part::B => self.b.as_ref(),
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> Solution for Day<T> {
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn has_part(&self, part: Part) -> bool {
+        self.solver(part).is_some()
+    }
+
+    fn is_placeholder(&self, part: Part) -> bool {
+        self.placeholders.contains(&part)
+    }
+
+    fn normalise(&self) -> Normalise {
+        self.normalise
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        match (self.parse)(&input::normalise(input, self.normalise)) {
+            Ok(parsed) => Ok(Parsed(Box::new(parsed))),
+            Err(e) => Err(e.in_day(self.day)),
+        }
+    }
+
+    fn solve_within(&self, part: Part, parsed: &Parsed, token: &Token) -> Option<Result<Answer, Failure>> {
+        let parsed = parsed.0.downcast_ref::<Box<T>>().expect("parsed by another day");
+
+        self.solver(part).map(|solve| solve(parsed, token))
+    }
+
+    fn explain(&self, part: Part, parsed: &Parsed) -> Option<String> {
+        let parsed = parsed.0.downcast_ref::<Box<T>>().expect("parsed by another day");
+        let explain = match part {
+            Part::A => self.explain_a.as_ref(),
+            Part::B => self.explain_b.as_ref(),
+        };
+
+        explain.and_then(|explain| explain(parsed))
+    }
+
+    fn alternatives(&self, part: Part) -> Vec<&'static str> {
+        self.alternatives.iter().filter(|&&(p, _, _)| p == part).map(|&(_, name, _)| name).collect()
+    }
+
+    fn solve_alternative(&self, part: Part, name: &str, parsed: &Parsed, token: &Token)
+        -> Option<Result<Answer, Failure>>
+    {
+        let parsed = parsed.0.downcast_ref::<Box<T>>().expect("parsed by another day");
+
+        self.alternatives.iter()
+            .find(|&&(p, n, _)| (p, n) == (part, name))
+            .map(|(_, _, solve)| solve(parsed, token))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        self.generate.as_ref().map(|generate| generate(rng, size))
+    }
+}
+
+/// The registry of every day solved for a single year.
+pub struct Year {
+    pub year: u16,
+    /// The root of the year's crate, which `inputs/` lives under.
+    pub dir: &'static str,
+    days: Vec<Box<dyn Solution>>,
+}
+
+impl Year {
+    pub fn new(year: u16, dir: &'static str) -> Year {
+        Year { year, dir, days: vec![] }
+    }
+
+    pub fn register<S: Solution + 'static>(mut self, solution: S) -> Year {
+        assert!(self.days().all(|day| day.day() != solution.day()), "day {} registered twice", solution.day());
+
+        self.days.push(Box::new(solution));
+        self.days.sort_by_key(|day| day.day());
+        self
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = &dyn Solution> {
+        self.days.iter().map(|day| &**day)
+    }
+
+    /// Looks a day up by number (`10`) or by name (`ten`).
+    pub fn find(&self, key: &str) -> Option<&dyn Solution> {
+        let number = key.parse::<u8>().ok();
+
+        self.days().find(|day| Some(day.day()) == number || day.name() == key)
+    }
+}
+
+#[test]
+fn test_day() {
+    let day = Day::new(1, "one").part_a(|input: &str| input.len());
+
+    assert_eq!(day.solve(Part::A, "1122"), Ok(Some(Ok(Answer::Number(4)))));
+    assert_eq!(day.solve(Part::B, "1122"), Ok(None));
+    assert!(day.has_part(Part::A) && !day.has_part(Part::B));
+    assert!(!day.is_placeholder(Part::A));
+
+    let day = day.part_b(|_: &str| 0).placeholder(Part::B);
+    assert!(day.has_part(Part::B) && day.is_placeholder(Part::B) && !day.is_placeholder(Part::A));
+}
+
+#[test]
+fn test_parse() {
+    use parse::Parser;
+
+    let day = Day::new(1, "one")
+        .parse(|input: &str| {
+            let p = Parser::new(input);
+            input.char_indices().map(|(i, c)| {
+                c.to_digit(10).ok_or_else(|| p.error(&input[i..i + 1], "a digit"))
+            }).collect::<Result<Vec<_>, _>>()
+        })
+        .part_a(|digits: &Vec<u32>| digits.iter().sum::<u32>())
+        .part_b(|digits: &Vec<u32>| digits.len());
+
+    let parsed = day.parse("1122").unwrap();
+    assert_eq!(day.solve_parsed(Part::A, &parsed), Some(Ok(Answer::Number(6))));
+    assert_eq!(day.solve_parsed(Part::B, &parsed), Some(Ok(Answer::Number(4))));
+    assert_eq!(day.solve(Part::A, "99"), Ok(Some(Ok(Answer::Number(18)))));
+
+    let error = day.solve(Part::A, "12x4").unwrap_err();
+    assert_eq!((error.day, error.line, error.column), (Some(1), 1, 3));
+    assert_eq!(day.solve(Part::B, "12x4").unwrap_err().found, "x");
+}
+
+#[test]
+fn test_normalise() {
+    let day = Day::new(1, "one").part_a(|input: &str| input.to_string());
+    assert_eq!(day.solve(Part::A, "\u{feff}1122 \r\n\r\n"), Ok(Some(Ok(Answer::from("1122")))));
+
+    assert_eq!(Solution::normalise(&day), Normalise::Everything);
+
+    let day = day.normalise(Normalise::LineEndings);
+    assert_eq!(Solution::normalise(&day), Normalise::LineEndings);
+    assert_eq!(day.solve(Part::A, " 11\r\n22 \r\n"), Ok(Some(Ok(Answer::from(" 11\n22 \n")))));
+}
+
+#[test]
+fn test_cancellable() {
+    let day = Day::new(1, "one").part_a_cancellable(|_: &str, token: &Token| -> Result<usize, Cancelled> {
+        loop {
+            token.check()?;
+        }
+    });
+    let parsed = Solution::parse(&day, "1122").unwrap();
+
+    let token = Token::within(::std::time::Duration::from_millis(10));
+    assert_eq!(day.solve_within(Part::A, &parsed, &token), Some(Err(Failure::Cancelled)));
+    assert_eq!(day.solve_within(Part::B, &parsed, &token), None);
+}
+
+#[test]
+fn test_no_answer() {
+    let day = Day::new(1, "one").part_a(|input: &str| {
+        input.find('(').ok_or_else(|| Failure::no_answer("never goes up"))
+    });
+
+    assert_eq!(day.solve(Part::A, "))("), Ok(Some(Ok(Answer::Number(2)))));
+    assert_eq!(day.solve(Part::A, "))"), Ok(Some(Err(Failure::NoAnswer("never goes up".to_string())))));
+    assert_eq!(Failure::no_answer("never goes up").to_string(), "no answer: never goes up");
+}
+
+#[test]
+fn test_explain() {
+    let day = Day::new(1, "one")
+        .part_a(|input: &str| input.len())
+        .explain_a(|input: &str| Some(format!("{:?} has {} characters", input, input.len())))
+        .explain_b(|input: &str| input.find('(').map(|i| format!("goes up at {}", i)));
+    let parsed = Solution::parse(&day, "1122").unwrap();
+
+    assert_eq!(day.explain(Part::A, &parsed), Some("\"1122\" has 4 characters".to_string()));
+    assert_eq!(day.explain(Part::B, &parsed), None);
+
+    let parsed = Solution::parse(&day, "(").unwrap();
+    assert_eq!(day.explain(Part::B, &parsed), Some("goes up at 0".to_string()));
+}
+
+#[test]
+fn test_alternatives() {
+    let day = Day::new(1, "one")
+        .part_a(|input: &str| input.len())
+        .alternative_a("chars", |input: &str| input.chars().count());
+    let parsed = Solution::parse(&day, "1122").unwrap();
+
+    assert_eq!(day.alternatives(Part::A), ["chars"]);
+    assert!(day.alternatives(Part::B).is_empty());
+    assert_eq!(day.solve_alternative(Part::A, "chars", &parsed, &Token::none()), Some(Ok(Answer::Number(4))));
+    assert_eq!(day.solve_alternative(Part::A, "bytes", &parsed, &Token::none()), None);
+}
+
+#[test]
+fn test_find() {
+    let year = Year::new(2017, ".")
+        .register(Day::new(10, "ten"))
+        .register(Day::new(2, "two"));
+
+    assert_eq!(year.find("10").map(|day| day.name()), Some("ten"));
+    assert_eq!(year.find("two").map(|day| day.day()), Some(2));
+    assert!(year.find("3").is_none());
+    assert_eq!(year.days().map(|day| day.day()).collect::<Vec<_>>(), vec![2, 10]);
+}
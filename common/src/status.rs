@@ -0,0 +1,175 @@
+//! How far each year has got, worked out from what's registered, the answers
+//! ledger and the bench baseline rather than kept by hand.
+
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use bench::{Baseline, Stage};
+use ledger::{Key, Ledger};
+use report::human_duration;
+use solution::{Part, Solution, Year};
+
+/// Every puzzle runs from the 1st to the 25th of December.
+pub const DAYS: u8 = 25;
+
+/// Where one part of one day stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Progress {
+    /// No day registered, or the day doesn't solve this part.
+    Missing,
+    /// Registered, but only as a placeholder until it's really solved.
+    Placeholder,
+    /// Solved, but with no accepted answer for the input there is now.
+    Unverified,
+    /// Solved, and the ledger holds the accepted answer for the input.
+    Verified,
+}
+
+impl Progress {
+    pub fn of(solution: &dyn Solution, part: Part, input: Option<&str>, ledger: &Ledger) -> Progress {
+        if !solution.has_part(part) {
+            return Progress::Missing;
+        }
+        if solution.is_placeholder(part) {
+            return Progress::Placeholder;
+        }
+
+        let key = |input| Key::new(solution.day(), part, input, solution.normalise());
+        match input {
+            Some(input) if ledger.get(&key(input)).is_some() => Progress::Verified,
+            _ => Progress::Unverified,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Progress::Missing => '.',
+            Progress::Placeholder => '?',
+            Progress::Unverified => '+',
+            Progress::Verified => '*',
+        }
+    }
+}
+
+/// One part's progress, and how long it took when last benched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartStatus {
+    pub progress: Progress,
+    pub time: Option<Duration>,
+}
+
+/// One day of the calendar, whether or not anything is registered for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: u8,
+    pub name: Option<&'static str>,
+    pub a: PartStatus,
+    pub b: PartStatus,
+}
+
+impl DayStatus {
+    /// A day with nothing registered for it.
+    pub fn missing(day: u8) -> DayStatus {
+        let part = PartStatus { progress: Progress::Missing, time: None };
+
+        DayStatus { day, name: None, a: part, b: part }
+    }
+
+    pub fn new(solution: &dyn Solution, input: Option<&str>, ledger: &Ledger, baseline: &Baseline) -> DayStatus {
+        let part = |part| PartStatus {
+            progress: Progress::of(solution, part, input, ledger),
+            time: baseline.get(solution.day(), Stage::Part(part)).map(|stats| stats.median),
+        };
+
+        DayStatus { day: solution.day(), name: Some(solution.name()), a: part(Part::A), b: part(Part::B) }
+    }
+
+    pub fn parts(&self) -> [PartStatus; 2] {
+        [self.a, self.b]
+    }
+}
+
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut line = format!("{:>2} {:<14}", self.day, self.name.unwrap_or("-"));
+
+        for (part, status) in [Part::A, Part::B].iter().zip(&self.parts()) {
+            let time = status.time.map(human_duration).unwrap_or_default();
+            line.push_str(&format!(" {} {} {:>8}", part, status.progress.symbol(), time));
+        }
+
+        f.write_str(line.trim_end())
+    }
+}
+
+/// The whole of December for one year, a day at a time.
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    pub year: u16,
+    pub days: Vec<DayStatus>,
+}
+
+impl Calendar {
+    /// Builds the calendar, loading each registered day's input with `input`,
+    /// which gives `None` for days that don't have one.
+    pub fn new<F>(year: &Year, ledger: &Ledger, baseline: &Baseline, input: F) -> Calendar
+        where F: Fn(&dyn Solution) -> Option<String>
+    {
+        let days = (1..=DAYS).map(|day| match year.find(&day.to_string()) {
+            Some(solution) => DayStatus::new(solution, input(solution).as_deref(), ledger, baseline),
+            None => DayStatus::missing(day),
+        }).collect();
+
+        Calendar { year: year.year, days }
+    }
+
+    /// A single line with a symbol per part, a day at a time.
+    pub fn strip(&self) -> String {
+        self.days.iter()
+            .map(|day| day.parts().iter().map(|part| part.progress.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn count(&self, progress: Progress) -> usize {
+        self.days.iter()
+            .flat_map(|day| day.parts().to_vec())
+            .filter(|part| part.progress == progress)
+            .count()
+    }
+}
+
+#[test]
+fn test_calendar() {
+    use std::env;
+    use answer::Answer;
+    use input::Normalise;
+    use solution::Day;
+
+    let year = Year::new(2017, "")
+        .register(Day::new(1, "one").part_a(|input: &str| input.len()).part_b(|input: &str| input.len() * 2))
+        .register(Day::new(3, "three").part_a(|input: &str| input.len()))
+        .register(Day::new(4, "four").part_a(|_: &str| 0).placeholder(Part::A));
+
+    let path = env::temp_dir().join(format!("aoc-status-{}.tsv", std::process::id()));
+    let mut ledger = Ledger::load(&path).unwrap();
+    ledger.insert(Key::new(1, Part::A, "abc", Normalise::Everything), &Answer::Number(3));
+
+    let calendar = Calendar::new(&year, &ledger, &Baseline::default(), |day| {
+        if day.day() == 1 { Some("abc".to_string()) } else { None }
+    });
+
+    assert_eq!(calendar.days.len(), 25);
+    assert_eq!(calendar.days[0].a.progress, Progress::Verified);
+    assert_eq!(calendar.days[0].b.progress, Progress::Unverified);
+    assert_eq!(calendar.days[1], DayStatus::missing(2));
+    assert_eq!(calendar.days[2].b.progress, Progress::Missing);
+    assert_eq!(calendar.days[1].to_string(), " 2 -              a .          b .");
+    assert_eq!(calendar.days[3].a.progress, Progress::Placeholder);
+    assert!(calendar.strip().starts_with("*+ .. +. ?. .."));
+    assert_eq!(
+        (calendar.count(Progress::Verified), calendar.count(Progress::Unverified),
+         calendar.count(Progress::Placeholder), calendar.count(Progress::Missing)),
+        (1, 2, 1, 46)
+    );
+}
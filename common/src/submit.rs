@@ -0,0 +1,372 @@
+//! Submitting answers, and remembering every guess so that a value the site
+//! has already turned down is never sent again.
+//!
+//! Each year keeps a `guesses.tsv` at its crate root, one
+//! `<day>\t<part>\t<answer>\t<verdict>` line per submission in the order
+//! they were made, with answers escaped the same way as in `answers.tsv`.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use fetch::{Client, FetchError};
+use ledger::{escape, unescape};
+use solution::{Part, Year};
+
+const HEADER: &str = "# day\tpart\tanswer\tverdict";
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way.
+    Wrong,
+    /// Not judged, as the last answer was too recent.
+    RateLimited,
+    /// Not judged, as the part isn't the one the day is waiting on.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page the site answers a submission with.
+    pub fn from_page(page: &str) -> Option<Verdict> {
+        let text = article(page);
+
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if text.contains("answer is too low") {
+            Some(Verdict::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if text.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+
+    fn parse(name: &str) -> Option<Verdict> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            "rate-limited" => Some(Verdict::RateLimited),
+            "wrong-level" => Some(Verdict::WrongLevel),
+            _ => None,
+        }
+    }
+
+    /// Whether the site judged the answer at all.
+    fn judged(self) -> bool {
+        self != Verdict::RateLimited && self != Verdict::WrongLevel
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate-limited",
+            Verdict::WrongLevel => "wrong-level",
+        })
+    }
+}
+
+/// The text of a page's `<article>`, which is where the site says what it
+/// made of an answer, without the markup.
+pub fn article(page: &str) -> String {
+    let start = page.find("<article").unwrap_or(0);
+    let end = page[start..].find("</article>").map_or(page.len(), |end| start + end);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Why a guess isn't worth submitting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part has already been solved.
+    Solved { answer: String },
+    /// The same answer has already been turned down.
+    Repeated { verdict: Verdict },
+    /// The answer is at least one that was too high.
+    AboveBound { bound: String },
+    /// The answer is at most one that was too low.
+    BelowBound { bound: String },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Refusal::Solved { ref answer } => write!(f, "already solved with {}", answer),
+            Refusal::Repeated { verdict } => write!(f, "already guessed, and it was {}", verdict),
+            Refusal::AboveBound { ref bound } => write!(f, "{} was already too high", bound),
+            Refusal::BelowBound { ref bound } => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Guesses {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl Guesses {
+    pub fn path(year: &Year) -> PathBuf {
+        Path::new(year.dir).join("guesses.tsv")
+    }
+
+    /// Reads the guesses at `path`; a missing file means none yet.
+    pub fn load<P: Into<PathBuf>>(path: P) -> io::Result<Guesses> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(ref e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut guesses = vec![];
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let guess = parse_line(line).ok_or_else(|| {
+                let message = format!("{}:{}: malformed guess {:?}", path.display(), i + 1, line);
+                io::Error::new(ErrorKind::InvalidData, message)
+            })?;
+            guesses.push(guess);
+        }
+
+        Ok(Guesses { path, guesses })
+    }
+
+    /// Every guess at one part, oldest first.
+    pub fn part(&self, day: u8, part: Part) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |guess| guess.day == day && guess.part == part)
+    }
+
+    /// Whether `answer` could still be right, given the verdicts so far.
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Result<(), Refusal> {
+        let number = answer.parse::<i128>().ok();
+
+        for guess in self.part(day, part) {
+            if guess.verdict == Verdict::Correct {
+                return Err(Refusal::Solved { answer: guess.answer.clone() });
+            }
+            if guess.verdict.judged() && guess.answer == answer {
+                return Err(Refusal::Repeated { verdict: guess.verdict });
+            }
+
+            let bound = guess.answer.parse::<i128>().ok();
+            match (number, bound, guess.verdict) {
+                (Some(n), Some(high), Verdict::TooHigh) if n >= high => {
+                    return Err(Refusal::AboveBound { bound: guess.answer.clone() })
+                }
+                (Some(n), Some(low), Verdict::TooLow) if n <= low => {
+                    return Err(Refusal::BelowBound { bound: guess.answer.clone() })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, guess: Guess) {
+        self.guesses.push(guess);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+
+        for guess in &self.guesses {
+            text.push_str(&format!("{}\t{}\t{}\t{}\n", guess.day, guess.part, escape(&guess.answer), guess.verdict));
+        }
+
+        fs::write(&self.path, text)
+    }
+}
+
+/// Submits an answer unless the guesses so far rule it out, recording the
+/// verdict. Returns the verdict and what the site said about it.
+pub fn submit(client: &Client, guesses: &mut Guesses, year: u16, day: u8, part: Part, answer: &str)
+    -> Result<(Verdict, String), SubmitError>
+{
+    guesses.check(day, part, answer).map_err(SubmitError::Refused)?;
+
+    let level = match part {
+        Part::A => 1,
+        Part::B => 2,
+    };
+    let page = client.answer(year, day, level, answer).map_err(SubmitError::Fetch)?;
+    let message = article(&page);
+    let verdict = Verdict::from_page(&page).ok_or_else(|| SubmitError::Unrecognised(message.clone()))?;
+
+    guesses.record(Guess { day, part, answer: answer.to_string(), verdict });
+    Ok((verdict, message))
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Fetch(FetchError),
+    /// The site answered with something that isn't a verdict.
+    Unrecognised(String),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SubmitError::Refused(ref refusal) => write!(f, "not submitting, {}", refusal),
+            SubmitError::Fetch(ref e) => e.fmt(f),
+            SubmitError::Unrecognised(ref message) => write!(f, "could not tell the verdict from {:?}", message),
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Option<Guess> {
+    let mut fields = line.splitn(4, '\t');
+
+    let day = fields.next()?.parse().ok()?;
+    let part = match fields.next()? {
+        "a" => Part::A,
+        "b" => Part::B,
+        _ => return None,
+    };
+    let answer = unescape(fields.next()?)?;
+    let verdict = Verdict::parse(fields.next()?)?;
+
+    Some(Guess { day, part, answer, verdict })
+}
+
+#[cfg(test)]
+fn page(message: &str) -> String {
+    format!("<!DOCTYPE html>\n<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>", message)
+}
+
+#[test]
+fn test_verdict() {
+    let verdict = |message| Verdict::from_page(&page(message));
+
+    assert_eq!(verdict("That's the right answer!  You are <em>one gold star</em> closer."), Some(Verdict::Correct));
+    assert_eq!(verdict("That's not the right answer; your answer is too high.  If you're stuck..."), Some(Verdict::TooHigh));
+    assert_eq!(verdict("That's not the right answer; your answer is too low."), Some(Verdict::TooLow));
+    assert_eq!(verdict("That's not the right answer.  If you're stuck..."), Some(Verdict::Wrong));
+    assert_eq!(verdict("You gave an answer too recently.  You have 36s left to wait."), Some(Verdict::RateLimited));
+    assert_eq!(verdict("You don't seem to be solving the right level.  Did you already complete it?"), Some(Verdict::WrongLevel));
+    assert_eq!(verdict("Puzzle inputs differ by user."), None);
+
+    assert_eq!(article(&page("That's the right answer!  You are <em>one gold star</em>\n closer.")),
+               "That's the right answer! You are one gold star closer.");
+}
+
+#[test]
+fn test_check() {
+    let mut guesses = Guesses::default();
+    let guess = |answer: &str, verdict| Guess { day: 3, part: Part::A, answer: answer.to_string(), verdict };
+
+    assert_eq!(guesses.check(3, Part::A, "500"), Ok(()));
+
+    guesses.record(guess("500", Verdict::TooHigh));
+    guesses.record(guess("100", Verdict::TooLow));
+    guesses.record(guess("300", Verdict::Wrong));
+    guesses.record(guess("250", Verdict::RateLimited));
+
+    assert_eq!(guesses.check(3, Part::A, "300"), Err(Refusal::Repeated { verdict: Verdict::Wrong }));
+    assert_eq!(guesses.check(3, Part::A, "600"), Err(Refusal::AboveBound { bound: "500".to_string() }));
+    assert_eq!(guesses.check(3, Part::A, "100"), Err(Refusal::Repeated { verdict: Verdict::TooLow }));
+    assert_eq!(guesses.check(3, Part::A, "-4"), Err(Refusal::BelowBound { bound: "100".to_string() }));
+    assert_eq!(guesses.check(3, Part::A, "250"), Ok(()));
+    assert_eq!(guesses.check(3, Part::A, "abc"), Ok(()));
+    assert_eq!(guesses.check(3, Part::B, "600"), Ok(()));
+
+    guesses.record(guess("250", Verdict::Correct));
+    assert_eq!(guesses.check(3, Part::A, "251"), Err(Refusal::Solved { answer: "250".to_string() }));
+}
+
+#[test]
+fn test_submit() {
+    use fetch::stub_server;
+
+    let correct = "HTTP/1.1 200 OK\r\n\r\n<main><article><p>That's the right answer!</p></article></main>";
+    let (url, requests) = stub_server(vec![correct]);
+    let client = Client::new(url, "abc123");
+
+    let mut guesses = Guesses::default();
+    guesses.record(Guess { day: 1, part: Part::B, answer: "1200".to_string(), verdict: Verdict::TooHigh });
+
+    let refused = submit(&client, &mut guesses, 2017, 1, Part::B, "1201").unwrap_err();
+    assert_eq!(refused.to_string(), "not submitting, 1200 was already too high");
+
+    let (verdict, message) = submit(&client, &mut guesses, 2017, 1, Part::B, "1060").unwrap();
+    assert_eq!((verdict, &*message), (Verdict::Correct, "That's the right answer!"));
+    assert_eq!(guesses.part(1, Part::B).last().unwrap().answer, "1060");
+
+    let request = requests.recv().unwrap();
+    assert!(request.starts_with("POST /2017/day/1/answer HTTP/1.1\r\n"));
+    assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+    assert!(request.ends_with("\r\n\r\nlevel=2&answer=1060"));
+}
+
+#[test]
+fn test_round_trip() {
+    use std::env;
+
+    let path = env::temp_dir().join(format!("aoc-guesses-{}.tsv", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut guesses = Guesses::load(&path).unwrap();
+    guesses.record(Guess { day: 7, part: Part::A, answer: "tknk".to_string(), verdict: Verdict::Wrong });
+    guesses.record(Guess { day: 7, part: Part::B, answer: "60".to_string(), verdict: Verdict::TooLow });
+    guesses.save().unwrap();
+
+    let loaded = Guesses::load(&path).unwrap();
+    assert_eq!(loaded.guesses, guesses.guesses);
+
+    let mut guesses = loaded;
+    let drawn = "#..#\n#\t\\.".to_string();
+    guesses.record(Guess { day: 8, part: Part::B, answer: drawn.clone(), verdict: Verdict::Wrong });
+    guesses.save().unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 4);
+    assert_eq!(Guesses::load(&path).unwrap().part(8, Part::B).last().unwrap().answer, drawn);
+    assert_eq!(Guesses::load(&path).unwrap().check(8, Part::B, &drawn), Err(Refusal::Repeated { verdict: Verdict::Wrong }));
+
+    fs::write(&path, "7\ta\ttknk\tmaybe\n").unwrap();
+    assert_eq!(Guesses::load(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+    fs::write(&path, "7\ta\ttk\\xk\twrong\n").unwrap();
+    assert_eq!(Guesses::load(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    fs::remove_file(&path).unwrap();
+}
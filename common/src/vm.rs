@@ -0,0 +1,432 @@
+//! A register machine for the puzzles whose input is a program in some
+//! made-up assembly language.
+//!
+//! Each puzzle brings its own instruction set, as a type implementing
+//! [`Instruction`], and the [`Machine`] does the rest: it keeps the program
+//! counter and registers, passes values in and out through queues, and stops
+//! when the program runs off either end, halts, waits on input that isn't
+//! there yet, or is about to go round a loop. Programs are decoded once by an
+//! [`Assembler`], so that operands are already register numbers or values and
+//! running never looks at the text again.
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::str::{FromStr, SplitWhitespace};
+
+use cancel::{Cancelled, Token};
+use parse::{ParseError, Parser};
+
+/// What an instruction reads from: a register, by number, or a value given
+/// in the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(usize),
+    Value(i64),
+}
+
+/// Everything an instruction can see and change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    pub registers: Vec<i64>,
+    /// Values waiting for the program to read them, oldest first.
+    pub input: VecDeque<i64>,
+    /// Values the program has written, oldest first.
+    pub output: VecDeque<i64>,
+}
+
+impl State {
+    pub fn get(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.registers[r],
+            Operand::Value(v) => v,
+        }
+    }
+}
+
+/// Where the program goes after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    /// Jumps by this many instructions from this one.
+    Jump(i64),
+    /// Stays on this instruction, which is waiting for input.
+    Wait,
+    /// Stops on this instruction.
+    Halt,
+}
+
+/// One instruction of a puzzle's instruction set.
+pub trait Instruction: Copy {
+    fn execute(self, state: &mut State) -> Flow;
+}
+
+/// Why a machine stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The program counter went off either end of the program.
+    Exited,
+    /// An instruction halted.
+    Halted,
+    /// An instruction is waiting for input, and carries on once some is
+    /// queued and the machine is run again.
+    Waiting,
+    /// The next instruction has already been run once.
+    Looped,
+}
+
+/// A decoded program, along with the names of the registers it uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program<I> {
+    instructions: Vec<I>,
+    registers: Vec<String>,
+}
+
+impl<I> Program<I> {
+    /// The names of the registers, in the order they're numbered.
+    pub fn registers(&self) -> &[String] {
+        &self.registers
+    }
+
+    /// The number of the register called `name`.
+    pub fn register(&self, name: &str) -> Option<usize> {
+        self.registers.iter().position(|register| register == name)
+    }
+
+    /// A machine about to run the program, with every register at 0.
+    pub fn machine(&self) -> Machine<'_, I> {
+        Machine::new(&self.instructions, self.registers.len())
+    }
+}
+
+impl<I> Deref for Program<I> {
+    type Target = [I];
+
+    fn deref(&self) -> &[I] {
+        &self.instructions
+    }
+}
+
+/// Patches instructions in place.
+impl<I> DerefMut for Program<I> {
+    fn deref_mut(&mut self) -> &mut [I] {
+        &mut self.instructions
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<'a, I: 'a> {
+    program: &'a [I],
+    pc: i64,
+    steps: u64,
+    pub state: State,
+}
+
+impl<'a, I> Machine<'a, I> {
+    pub fn new(program: &'a [I], registers: usize) -> Machine<'a, I> {
+        let state = State { registers: vec![0; registers], input: VecDeque::new(), output: VecDeque::new() };
+
+        Machine { program, pc: 0, steps: 0, state }
+    }
+
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
+
+    /// How many instructions have run, not counting any that waited or
+    /// halted.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// The instruction about to run, unless the program counter is off the
+    /// end of the program.
+    pub fn current(&self) -> Option<&'a I> {
+        usize::try_from(self.pc).ok().and_then(|pc| self.program.get(pc))
+    }
+}
+
+impl<'a, I: Instruction> Machine<'a, I> {
+    /// Runs a single instruction, unless the machine stops instead.
+    pub fn step(&mut self) -> Option<Stop> {
+        let instruction = match self.current() {
+            Some(&instruction) => instruction,
+            None => return Some(Stop::Exited),
+        };
+
+        match instruction.execute(&mut self.state) {
+            Flow::Next => self.pc += 1,
+            Flow::Jump(offset) => self.pc += offset,
+            Flow::Wait => return Some(Stop::Waiting),
+            Flow::Halt => return Some(Stop::Halted),
+        }
+
+        self.steps += 1;
+        None
+    }
+
+    /// Runs until the machine stops.
+    pub fn run(&mut self, token: &Token) -> Result<Stop, Cancelled> {
+        loop {
+            token.check()?;
+
+            if let Some(stop) = self.step() {
+                return Ok(stop);
+            }
+        }
+    }
+
+    /// Runs until the machine stops, or is about to run an instruction for
+    /// the second time. For a program whose jumps don't depend on its
+    /// registers, that means it would go round forever.
+    pub fn run_until_loop(&mut self) -> Stop {
+        let mut seen = vec![false; self.program.len()];
+
+        loop {
+            let pc = usize::try_from(self.pc).ok();
+            if let Some(seen) = pc.and_then(|pc| seen.get_mut(pc)) {
+                if mem::replace(seen, true) {
+                    return Stop::Looped;
+                }
+            }
+
+            if let Some(stop) = self.step() {
+                return stop;
+            }
+        }
+    }
+}
+
+/// Decodes a program a line at a time, numbering registers as it goes.
+#[derive(Debug, Clone)]
+pub struct Assembler<'a> {
+    p: Parser<'a>,
+    registers: Registers,
+}
+
+#[derive(Debug, Clone)]
+struct Registers {
+    names: Vec<String>,
+    /// Whether any other name is a new register, rather than an error.
+    open: bool,
+}
+
+impl Registers {
+    fn number(&mut self, name: &str) -> Option<usize> {
+        if let Some(r) = self.names.iter().position(|register| register == name) {
+            return Some(r);
+        }
+
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if self.open && valid {
+            self.names.push(name.to_owned());
+            Some(self.names.len() - 1)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Assembler<'a> {
+    /// For a program with just the registers in `names`, numbered in that
+    /// order.
+    pub fn new(input: &'a str, names: &[&str]) -> Assembler<'a> {
+        let names = names.iter().map(|&name| name.to_owned()).collect();
+
+        Assembler { p: Parser::new(input), registers: Registers { names, open: false } }
+    }
+
+    /// For a program with `count` registers named by letter from `a`.
+    pub fn letters(input: &'a str, count: usize) -> Assembler<'a> {
+        let names = (b'a'..).take(count).map(|c| (c as char).to_string()).collect();
+
+        Assembler { p: Parser::new(input), registers: Registers { names, open: false } }
+    }
+
+    /// For a program whose registers can have any names, numbered in the
+    /// order they're first mentioned.
+    pub fn named(input: &'a str) -> Assembler<'a> {
+        Assembler { p: Parser::new(input), registers: Registers { names: vec![], open: true } }
+    }
+
+    /// Decodes every non-empty line, trimmed, with `decode`, which takes the
+    /// words it needs from the [`Line`]. Any words it leaves are an error.
+    pub fn assemble<I, F>(self, mut decode: F) -> Result<Program<I>, ParseError>
+        where F: FnMut(&mut Line<'a, '_>) -> Result<I, ParseError>
+    {
+        let Assembler { p, mut registers } = self;
+
+        let instructions = p.lines(|text| {
+            let text = text.trim();
+            let mut line = Line { p, text, words: text.split_whitespace(), registers: &mut registers };
+            let instruction = decode(&mut line)?;
+            p.end(&mut line.words)?;
+
+            Ok(instruction)
+        })?;
+
+        Ok(Program { instructions, registers: registers.names })
+    }
+}
+
+/// The words of one line of a program, taken in turn.
+pub struct Line<'a, 'r> {
+    p: Parser<'a>,
+    text: &'a str,
+    words: SplitWhitespace<'a>,
+    registers: &'r mut Registers,
+}
+
+impl<'a, 'r> Line<'a, 'r> {
+    /// The next word, complaining that it was `expected` if there isn't one.
+    pub fn word(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        self.p.token(&mut self.words, self.text, expected)
+    }
+
+    pub fn keyword(&mut self, word: &str) -> Result<(), ParseError> {
+        let text = self.word(&format!("{:?}", word))?;
+
+        self.p.keyword(text, word)
+    }
+
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let text = self.word("a number")?;
+
+        self.p.number(text)
+    }
+
+    pub fn register(&mut self) -> Result<usize, ParseError> {
+        let text = self.word("a register")?;
+
+        self.registers.number(text).ok_or_else(|| self.p.error(text, "a register"))
+    }
+
+    pub fn operand(&mut self) -> Result<Operand, ParseError> {
+        let text = self.word("a register or number")?;
+
+        match self.registers.number(text) {
+            Some(r) => Ok(Operand::Register(r)),
+            None => text.parse().map(Operand::Value).map_err(|_| self.p.error(text, "a register or number")),
+        }
+    }
+
+    /// An error pointing at `text`, a word taken from this line.
+    pub fn error<S: Into<String>>(&self, text: &str, expected: S) -> ParseError {
+        self.p.error(text, expected)
+    }
+}
+
+/// Enough of an instruction set to try the machine out with.
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Toy {
+    Inc(usize),
+    Dec(usize),
+    Jnz(Operand, Operand),
+    In(usize),
+    Out(Operand),
+    Hlt,
+}
+
+#[cfg(test)]
+impl Instruction for Toy {
+    fn execute(self, state: &mut State) -> Flow {
+        match self {
+            Toy::Inc(r) => state.registers[r] += 1,
+            Toy::Dec(r) => state.registers[r] -= 1,
+            Toy::Jnz(x, offset) => if state.get(x) != 0 {
+                return Flow::Jump(state.get(offset));
+            },
+            Toy::In(r) => match state.input.pop_front() {
+                Some(value) => state.registers[r] = value,
+                None => return Flow::Wait,
+            },
+            Toy::Out(x) => {
+                let value = state.get(x);
+                state.output.push_back(value);
+            }
+            Toy::Hlt => return Flow::Halt,
+        }
+
+        Flow::Next
+    }
+}
+
+#[cfg(test)]
+fn assemble(assembler: Assembler) -> Result<Program<Toy>, ParseError> {
+    assembler.assemble(|line| {
+        let op = line.word("an instruction")?;
+
+        Ok(match op {
+            "inc" => Toy::Inc(line.register()?),
+            "dec" => Toy::Dec(line.register()?),
+            "jnz" => Toy::Jnz(line.operand()?, line.operand()?),
+            "in" => Toy::In(line.register()?),
+            "out" => Toy::Out(line.operand()?),
+            "hlt" => Toy::Hlt,
+            _ => return Err(line.error(op, "an instruction")),
+        })
+    })
+}
+
+#[test]
+fn test_assemble() {
+    let program = assemble(Assembler::letters("inc b\n  jnz b -1\n\nout 7", 2)).unwrap();
+    assert_eq!(*program, [Toy::Inc(1), Toy::Jnz(Operand::Register(1), Operand::Value(-1)), Toy::Out(Operand::Value(7))]);
+    assert_eq!(program.register("b"), Some(1));
+
+    let program = assemble(Assembler::named("inc count\ndec total\nout count")).unwrap();
+    assert_eq!(program.registers(), ["count", "total"]);
+    assert_eq!(program[2], Toy::Out(Operand::Register(0)));
+
+    let error = assemble(Assembler::letters("inc a\ninc c", 2)).unwrap_err();
+    assert_eq!((error.line, error.column, &*error.found, &*error.expected), (2, 5, "c", "a register"));
+    let error = assemble(Assembler::named("jnz x2 2x")).unwrap_err();
+    assert_eq!((error.column, &*error.found, &*error.expected), (8, "2x", "a register or number"));
+    let error = assemble(Assembler::new("hlt now", &["acc"])).unwrap_err();
+    assert_eq!((error.column, &*error.expected), (5, "the end of the line"));
+    let error = assemble(Assembler::new("jnz 1", &["acc"])).unwrap_err();
+    assert_eq!((error.column, &*error.found), (6, ""));
+}
+
+#[test]
+fn test_run() {
+    let token = Token::none();
+
+    // Counts `a` down to 0, then reads a value and writes it back doubled.
+    let program = assemble(Assembler::letters("inc a\ninc a\ndec a\njnz a -1\nin b\nout b\nout b\nhlt\ninc a", 2)).unwrap();
+    let mut machine = program.machine();
+    assert_eq!(machine.run(&token), Ok(Stop::Waiting));
+    assert_eq!((machine.pc(), machine.steps()), (4, 6));
+
+    machine.state.input.push_back(21);
+    assert_eq!(machine.run(&token), Ok(Stop::Halted));
+    assert_eq!(machine.state.output, [21, 21]);
+    assert_eq!(machine.current(), Some(&Toy::Hlt));
+
+    let program = assemble(Assembler::letters("inc a\njnz a 5", 1)).unwrap();
+    let mut machine = program.machine();
+    assert_eq!(machine.run(&token), Ok(Stop::Exited));
+    assert_eq!((machine.pc(), machine.current()), (6, None));
+
+    let spinning = assemble(Assembler::letters("jnz 1 0", 1)).unwrap();
+    let token = Token::none();
+    token.cancel();
+    assert_eq!(spinning.machine().run(&token), Err(Cancelled));
+}
+
+#[test]
+fn test_loop() {
+    let program = assemble(Assembler::letters("inc a\njnz 1 2\nhlt\ninc a\njnz 1 -3", 1)).unwrap();
+    let mut machine = program.machine();
+    assert_eq!(machine.run_until_loop(), Stop::Looped);
+    assert_eq!((machine.pc(), machine.state.registers[0]), (1, 2));
+
+    let mut program = program;
+    program[4] = Toy::Jnz(Operand::Value(1), Operand::Value(-2));
+    let mut machine = program.machine();
+    assert_eq!(machine.run_until_loop(), Stop::Halted);
+    assert_eq!(machine.state.registers[0], 2);
+}